@@ -0,0 +1,120 @@
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use super::types::SearchCacheStats;
+
+/// Least-recently-used cache of search outcomes, keyed by normalized request parameters `K`.
+///
+/// Entries remember the index generation they were computed from and are only served while it
+/// is still current, so in-place index updates invalidate them as well as a full reindex.
+pub(crate) struct SearchCache<K, V> {
+    capacity: usize,
+    ttl: Duration,
+    entries: HashMap<K, CacheEntry<V>>,
+    /// Keys by the tick they were last used at; the first entry is evicted when full.
+    recency: BTreeMap<u64, K>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+struct CacheEntry<V> {
+    value: V,
+    generation: String,
+    inserted_at: Instant,
+    last_used: u64,
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> SearchCache<K, V> {
+    /// A cache holding up to `capacity` entries for `ttl` each; a zero capacity disables it.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            capacity,
+            ttl,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            tick: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Returns the entry for `key` if it is fresh and was computed from `generation`.
+    pub fn get(&mut self, key: &K, generation: &str) -> Option<V> {
+        let fresh = self
+            .entries
+            .get(key)
+            .map(|entry| entry.generation == generation && entry.inserted_at.elapsed() < self.ttl);
+        match fresh {
+            Some(true) => {
+                self.tick += 1;
+                let entry = self.entries.get_mut(key).expect("entry checked above");
+                self.recency.remove(&entry.last_used);
+                entry.last_used = self.tick;
+                self.recency.insert(self.tick, key.clone());
+                self.hits += 1;
+                Some(entry.value.clone())
+            }
+            Some(false) => {
+                self.remove(key);
+                self.misses += 1;
+                None
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Stores `value` under `key`, evicting the least recently used entry when full.
+    pub fn insert(&mut self, key: K, generation: String, value: V) {
+        if !self.is_enabled() {
+            return;
+        }
+        self.remove(&key);
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.recency.insert(self.tick, key.clone());
+        self.entries.insert(
+            key,
+            CacheEntry {
+                value,
+                generation,
+                inserted_at: Instant::now(),
+                last_used: self.tick,
+            },
+        );
+    }
+
+    /// Drops every entry; the hit and miss counters are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+
+    pub fn stats(&self) -> SearchCacheStats {
+        SearchCacheStats {
+            capacity: self.capacity,
+            entries: self.entries.len(),
+            hits: self.hits,
+            misses: self.misses,
+        }
+    }
+
+    fn remove(&mut self, key: &K) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.recency.remove(&entry.last_used);
+        }
+    }
+}
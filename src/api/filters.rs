@@ -0,0 +1,227 @@
+use tantivy::Searcher;
+use tantivy::schema::{Field, Schema};
+use tracing::warn;
+
+use crate::config::AppConfig;
+use crate::indexer::{NameIndex, PopularityTier, TitleIndex};
+
+use super::types::{FilterDescriptor, FilterKind, FiltersResponse, SortMode};
+use super::validation::MAX_RESULT_WINDOW;
+
+/// Observed `[min, max]` of a numeric fast field across all segments.
+type FieldRange = Option<(f64, f64)>;
+
+/// First year of the decade containing `year`.
+fn decade_of(year: f64) -> f64 {
+    (year / 10.0).floor() * 10.0
+}
+
+/// Describes the supported search filters, including corpus bounds for numeric filters.
+///
+/// Bounds are read from fast-field column statistics, so this is cheap enough to run once at
+/// startup; the result is cached in [`super::AppState`].
+pub fn describe_filters(
+    titles: &TitleIndex,
+    names: &NameIndex,
+    config: &AppConfig,
+) -> FiltersResponse {
+    let title_searcher = titles.reader.searcher();
+    let title_schema = title_searcher.schema();
+    let start_year = i64_range(&title_searcher, title_schema, titles.fields.start_year);
+    let end_year = i64_range(&title_searcher, title_schema, titles.fields.end_year);
+    let rating = f64_range(&title_searcher, title_schema, titles.fields.average_rating);
+    let votes = i64_range(&title_searcher, title_schema, titles.fields.num_votes);
+    let runtime = i64_range(&title_searcher, title_schema, titles.fields.runtime_minutes);
+    let season = i64_range(&title_searcher, title_schema, titles.fields.season_number);
+
+    let sorts = SortMode::ALL
+        .into_iter()
+        .filter(|mode| {
+            config
+                .allowed_sorts
+                .as_ref()
+                .is_none_or(|allowed| allowed.contains(mode))
+        })
+        .map(|mode| mode.as_str().to_string())
+        .collect();
+
+    let mut title_filters = vec![
+        text("query"),
+        numeric(
+            "offset",
+            FilterKind::Integer,
+            Some((0.0, (MAX_RESULT_WINDOW - 1) as f64)),
+        ),
+        text("title_type"),
+        numeric("start_year_min", FilterKind::Integer, start_year),
+        numeric("start_year_max", FilterKind::Integer, start_year),
+        numeric(
+            "decade",
+            FilterKind::Integer,
+            start_year.map(|(min, max)| (decade_of(min), decade_of(max))),
+        ),
+        numeric("year", FilterKind::Integer, start_year),
+        numeric("end_year_min", FilterKind::Integer, end_year),
+        numeric("end_year_max", FilterKind::Integer, end_year),
+        numeric("min_rating", FilterKind::Float, rating),
+        numeric("max_rating", FilterKind::Float, rating),
+        numeric("min_votes", FilterKind::Integer, votes),
+        numeric("max_votes", FilterKind::Integer, votes),
+        enumeration(
+            "tier",
+            PopularityTier::ALL
+                .into_iter()
+                .map(|tier| tier.as_str().to_string())
+                .collect(),
+        ),
+        numeric("min_runtime", FilterKind::Integer, runtime),
+        numeric("max_runtime", FilterKind::Integer, runtime),
+        repeatable(text("genres")),
+        text("person"),
+        text("parent"),
+        numeric("season", FilterKind::Integer, season),
+        enumeration("genre_mode", vec!["all".to_string(), "any".to_string()]),
+        repeatable(text("exclude_genres")),
+        enumeration("sort", sorts),
+        boolean("strip_articles"),
+        text("lang"),
+        text("language"),
+        text("region"),
+        text("aka_language"),
+        enumeration("group_by", vec!["title_type".to_string()]),
+        boolean("include_adult"),
+        boolean("normalize_scores"),
+        boolean("fuzzy"),
+        numeric("boost_recent_years", FilterKind::Integer, None),
+        enumeration("match", vec!["any".to_string(), "phrase".to_string()]),
+        enumeration("default_operator", operators()),
+        boolean("debug"),
+        boolean("highlight"),
+        boolean("facets"),
+        boolean("diagnose"),
+        boolean("debug_score"),
+        boolean("suggest"),
+        boolean("no_cache"),
+        enumeration("format", vec!["json".to_string(), "csv".to_string()]),
+    ];
+    if titles.fields.primary_title_ngram.is_some() {
+        title_filters.push(boolean("infix"));
+    }
+
+    let name_searcher = names.reader.searcher();
+    let name_schema = name_searcher.schema();
+    let birth_year = i64_range(&name_searcher, name_schema, names.fields.birth_year);
+
+    let name_filters = vec![
+        text("query"),
+        numeric("birth_year_min", FilterKind::Integer, birth_year),
+        numeric("birth_year_max", FilterKind::Integer, birth_year),
+        repeatable(text("primary_profession")),
+        enumeration(
+            "profession_mode",
+            vec!["all".to_string(), "any".to_string()],
+        ),
+        boolean("alive"),
+        boolean("normalize_scores"),
+        boolean("fuzzy"),
+        boolean("phonetic"),
+        boolean("resolve_known_for"),
+        enumeration("default_operator", operators()),
+        enumeration(
+            "sort",
+            vec![
+                "relevance".to_string(),
+                "birth_year_desc".to_string(),
+                "birth_year_asc".to_string(),
+            ],
+        ),
+    ];
+
+    FiltersResponse {
+        titles: title_filters,
+        names: name_filters,
+    }
+}
+
+fn operators() -> Vec<String> {
+    vec!["or".to_string(), "and".to_string()]
+}
+
+fn text(name: &str) -> FilterDescriptor {
+    FilterDescriptor {
+        name: name.to_string(),
+        kind: FilterKind::Text,
+        repeatable: false,
+        min: None,
+        max: None,
+        values: Vec::new(),
+    }
+}
+
+fn boolean(name: &str) -> FilterDescriptor {
+    FilterDescriptor {
+        kind: FilterKind::Boolean,
+        ..text(name)
+    }
+}
+
+fn repeatable(descriptor: FilterDescriptor) -> FilterDescriptor {
+    FilterDescriptor {
+        repeatable: true,
+        ..descriptor
+    }
+}
+
+fn enumeration(name: &str, values: Vec<String>) -> FilterDescriptor {
+    FilterDescriptor {
+        kind: FilterKind::Enum,
+        values,
+        ..text(name)
+    }
+}
+
+fn numeric(name: &str, kind: FilterKind, range: FieldRange) -> FilterDescriptor {
+    FilterDescriptor {
+        kind,
+        min: range.map(|(min, _)| min),
+        max: range.map(|(_, max)| max),
+        ..text(name)
+    }
+}
+
+fn i64_range(searcher: &Searcher, schema: &Schema, field: Field) -> FieldRange {
+    let name = schema.get_field_name(field);
+    let mut range: FieldRange = None;
+    for segment_reader in searcher.segment_readers() {
+        match segment_reader.fast_fields().i64(name) {
+            Ok(column) if column.values.num_vals() > 0 => {
+                range = merge(range, column.min_value() as f64, column.max_value() as f64);
+            }
+            Ok(_) => {}
+            Err(err) => warn!(field = name, error = %err, "failed to read fast field stats"),
+        }
+    }
+    range
+}
+
+fn f64_range(searcher: &Searcher, schema: &Schema, field: Field) -> FieldRange {
+    let name = schema.get_field_name(field);
+    let mut range: FieldRange = None;
+    for segment_reader in searcher.segment_readers() {
+        match segment_reader.fast_fields().f64(name) {
+            Ok(column) if column.values.num_vals() > 0 => {
+                range = merge(range, column.min_value(), column.max_value());
+            }
+            Ok(_) => {}
+            Err(err) => warn!(field = name, error = %err, "failed to read fast field stats"),
+        }
+    }
+    range
+}
+
+fn merge(range: FieldRange, min: f64, max: f64) -> FieldRange {
+    Some(match range {
+        Some((lo, hi)) => (lo.min(min), hi.max(max)),
+        None => (min, max),
+    })
+}
@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use tantivy::Searcher;
+use tantivy::collector::Count;
+use tantivy::query::{BooleanQuery, Occur, Query as TantivyQuery};
+
+use crate::indexer::TitleIndex;
+
+use super::types::{RelatedGenre, RelatedGenresResponse};
+use super::utils::phrase_or_term_query;
+
+/// Genres used by IMDb in `title.basics.tsv`.
+pub const IMDB_GENRES: &[&str] = &[
+    "Action",
+    "Adult",
+    "Adventure",
+    "Animation",
+    "Biography",
+    "Comedy",
+    "Crime",
+    "Documentary",
+    "Drama",
+    "Family",
+    "Fantasy",
+    "Film-Noir",
+    "Game-Show",
+    "History",
+    "Horror",
+    "Music",
+    "Musical",
+    "Mystery",
+    "News",
+    "Reality-TV",
+    "Romance",
+    "Sci-Fi",
+    "Short",
+    "Sport",
+    "Talk-Show",
+    "Thriller",
+    "War",
+    "Western",
+];
+
+/// Maximum number of entries returned by `/genres/{genre}/related`.
+pub const RELATED_GENRES_LIMIT: usize = 10;
+
+/// Returns the canonical spelling of a known genre, ignoring case.
+pub fn canonical_genre(genre: &str) -> Option<&'static str> {
+    IMDB_GENRES
+        .iter()
+        .copied()
+        .find(|known| known.eq_ignore_ascii_case(genre.trim()))
+}
+
+/// Matches a genre against the tokenized `genres` field, so multi-token genres such as
+/// `Sci-Fi` become phrase queries and casing does not matter.
+pub(super) fn genre_query(title_index: &TitleIndex, genre: &str) -> Option<Box<dyn TantivyQuery>> {
+    let mut analyzer = title_index.tokenizers.get("default")?;
+    phrase_or_term_query(&mut analyzer, title_index.fields.genres, genre)
+}
+
+/// Counts, per known genre, the titles matching every clause of `filters` plus that genre.
+/// Genres without matches are left out.
+pub fn genre_facet_counts(
+    title_index: &TitleIndex,
+    searcher: &Searcher,
+    filters: &[(Occur, Box<dyn TantivyQuery>)],
+) -> tantivy::Result<BTreeMap<String, u64>> {
+    let mut counts = BTreeMap::new();
+    for genre in IMDB_GENRES.iter().copied() {
+        let Some(query) = genre_query(title_index, genre) else {
+            continue;
+        };
+        let mut clauses: Vec<(Occur, Box<dyn TantivyQuery>)> = filters
+            .iter()
+            .map(|(occur, clause)| (*occur, clause.box_clone()))
+            .collect();
+        clauses.push((Occur::Must, query));
+        let count = searcher.search(&BooleanQuery::new(clauses), &Count)?;
+        if count > 0 {
+            counts.insert(genre.to_string(), count as u64);
+        }
+    }
+    Ok(counts)
+}
+
+/// Ranks the other known genres by how many titles they share with `genre`.
+pub fn related_genres(
+    title_index: &TitleIndex,
+    searcher: &Searcher,
+    genre: &'static str,
+) -> tantivy::Result<RelatedGenresResponse> {
+    let Some(base) = genre_query(title_index, genre) else {
+        return Ok(RelatedGenresResponse {
+            genre: genre.to_string(),
+            titles: 0,
+            related: Vec::new(),
+        });
+    };
+    let titles = searcher.search(base.as_ref(), &Count)?;
+
+    let mut related = Vec::new();
+    if titles > 0 {
+        for other in IMDB_GENRES.iter().copied().filter(|other| *other != genre) {
+            let Some(other_query) = genre_query(title_index, other) else {
+                continue;
+            };
+            let both = BooleanQuery::new(vec![
+                (Occur::Must, base.box_clone()),
+                (Occur::Must, other_query),
+            ]);
+            let count = searcher.search(&both, &Count)?;
+            if count > 0 {
+                related.push(RelatedGenre {
+                    genre: other.to_string(),
+                    count,
+                });
+            }
+        }
+    }
+    related.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.genre.cmp(&b.genre)));
+    related.truncate(RELATED_GENRES_LIMIT);
+
+    Ok(RelatedGenresResponse {
+        genre: genre.to_string(),
+        titles,
+        related,
+    })
+}
@@ -1,29 +1,97 @@
+use std::borrow::Cow;
 use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::ops::Bound;
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, Instant};
 
 use axum::Json;
 use axum::extract::{Path, Query as AxumQuery, State};
-use tantivy::collector::TopDocs;
+use axum::http::header::{CONTENT_TYPE, ETAG};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Response};
+use chrono::{Datelike, Utc};
+use rand::rngs::SmallRng;
+use rand::{Rng, SeedableRng};
+use tantivy::collector::{Count, DocSetCollector, TopDocs};
+use tantivy::fastfield::FastValue;
 use tantivy::query::{
-    AllQuery, BooleanQuery, BoostQuery, FuzzyTermQuery, Occur, Query as TantivyQuery, RangeQuery,
-    TermQuery,
+    AllQuery, BooleanQuery, BoostQuery, EmptyQuery, ExistsQuery, FuzzyTermQuery, Occur,
+    Query as TantivyQuery, QueryParser, QueryParserError, RangeQuery, TermQuery,
 };
+use tantivy::query_grammar::{self, UserInputAst, UserInputLeaf};
 use tantivy::schema::{Field, TantivyDocument};
-use tantivy::{DocAddress, Order, Score, Term};
-use tracing::{debug, instrument};
+use tantivy::snippet::SnippetGenerator;
+use tantivy::{DocAddress, DocId, Order, Score, Searcher, Term};
+use tracing::{debug, error, info, instrument, warn};
 
-use super::scoring::compute_title_relevance_score;
+use crate::datasets;
+use crate::indexer::{
+    self, BuildMeta, BuildSummary, FuzzyDistance, NameIndex, TITLE_NGRAM_TOKENIZER, TitleIndex,
+    phonetic_codes, title_sort_key,
+};
+
+use super::genres::{self, canonical_genre, genre_query};
+use super::scoring::{genre_match_multiplier, normalize_scores, recent_window_multiplier};
 use super::state::AppState;
+use super::suggest::suggest_title_query;
 use super::types::{
-    ApiError, NameSearchParams, NameSearchResponse, NameSearchResult, SortMode, TitleSearchParams,
-    TitleSearchResponse, TitleSearchResult,
+    ApiError, CombinedSearchParams, CombinedSearchResponse, EpisodeSummary, ErrorBody,
+    FilterDiagnostic, FilterMode, FiltersResponse, Format, GroupBy, GroupedTitleSearchResponse,
+    IndexSegments, IndexStats, KnownForTitle, MatchMode, MetaResponse, NameLookupParams,
+    NameSearchParams, NameSearchResponse, NameSearchResult, NameSortMode, Operator,
+    RandomTitleParams, ReindexJob, RelatedGenresResponse, ScoreBreakdown, SegmentInfo,
+    SegmentsResponse, SimilarTitlesParams, SimilarTitlesResponse, SortMode, StatsResponse,
+    TieBreak, TitleCastResponse, TitleEpisodesParams, TitleEpisodesResponse, TitleLookupParams,
+    TitleSearchOutput, TitleSearchParams, TitleSearchResponse, TitleSearchResult,
+    TitleTypesResponse,
 };
-use super::utils::{document_to_name_result, document_to_title_result};
+use super::utils::{
+    INDEX_GENERATION_HEADER, constant_time_eq, document_etag, document_to_cast,
+    document_to_name_result, document_to_title_result, etag_matches, get_first_f64, get_first_i64,
+    get_first_text, index_generation, normalize_imdb_id, phrase_or_term_query, split_embedded_ids,
+    title_results_csv,
+};
+use super::validation::{MAX_RESULT_WINDOW, ValidatedQuery};
+
+/// JSON body paired with a single response header (e.g. `X-Index-Generation`).
+type WithHeader<T> = ([(&'static str, String); 1], Json<T>);
 
+/// Liveness check; always `ok`.
+#[utoipa::path(get, path = "/healthz", tag = "meta", responses((status = 200, body = String)))]
 pub async fn healthz() -> &'static str {
     "ok"
 }
 
+/// Filters accepted by the search endpoints, with observed ranges.
+#[utoipa::path(get, path = "/filters", tag = "meta", responses((status = 200, body = FiltersResponse)))]
+pub async fn list_filters(State(state): State<AppState>) -> Json<FiltersResponse> {
+    Json(FiltersResponse::clone(&state.filters.load()))
+}
+
+/// Distinct title types in the index with their title counts.
+#[utoipa::path(
+    get,
+    path = "/title-types",
+    tag = "titles",
+    responses((status = 200, body = TitleTypesResponse))
+)]
+pub async fn list_title_types(State(state): State<AppState>) -> Json<TitleTypesResponse> {
+    Json(TitleTypesResponse::clone(&state.title_types.load()))
+}
+
+fn is_slow(state: &AppState, elapsed: Duration) -> bool {
+    state
+        .config
+        .slow_query_ms
+        .is_some_and(|threshold| elapsed >= Duration::from_millis(threshold))
+}
+
+/// Page size the relevance candidate pool is sized for. It is fixed rather than taken from the
+/// request so every page of a search re-ranks the same pool.
+const RERANK_PAGE: usize = 50;
+
 fn candidate_limit_for(query: &str, limit: usize) -> usize {
     let qlen = query.chars().filter(|c| c.is_alphanumeric()).count();
     match qlen {
@@ -35,50 +103,922 @@ fn candidate_limit_for(query: &str, limit: usize) -> usize {
     }
 }
 
+/// A sorted page to collect: the `window` best documents matching `query`, with documents tied
+/// on the sort value settled by `tiebreak`.
+#[derive(Clone, Copy)]
+struct SortedWindow<'a> {
+    searcher: &'a Searcher,
+    title_index: &'a TitleIndex,
+    query: &'a dyn TantivyQuery,
+    window: usize,
+    tiebreak: TieBreak,
+}
+
+/// Collects the `window` best documents by a fast field. Documents tied on the last value of
+/// the window are re-selected by `tiebreak`, so the window is stable however ties are laid out
+/// in the index.
+fn collect_by_fast_field<T>(
+    sorted: SortedWindow,
+    field: Field,
+    order: Order,
+    to_term: impl Fn(Field, T) -> Term,
+) -> Result<(usize, Vec<(T, DocAddress)>), ApiError>
+where
+    T: FastValue + PartialEq,
+{
+    let name = sorted.title_index.schema.get_field_entry(field).name();
+    let collector = TopDocs::with_limit(sorted.window).order_by_fast_field::<T>(name, order);
+    let (total, docs) = sorted
+        .searcher
+        .search(sorted.query, &(Count, collector))
+        .map_err(|err| ApiError::internal(err.into()))?;
+    let docs = resolve_boundary_ties(sorted, (total, docs), |value| to_term(field, *value))?;
+    Ok((total, docs))
+}
+
+/// Like [`collect_by_fast_field`], for a raw-indexed string fast field.
+fn collect_by_string_field(
+    sorted: SortedWindow,
+    field: Field,
+    order: Order,
+) -> Result<(usize, Vec<(String, DocAddress)>), ApiError> {
+    let name = sorted.title_index.schema.get_field_entry(field).name();
+    let collector = TopDocs::with_limit(sorted.window).order_by_string_fast_field(name, order);
+    let (total, docs) = sorted
+        .searcher
+        .search(sorted.query, &(Count, collector))
+        .map_err(|err| ApiError::internal(err.into()))?;
+    let docs = resolve_boundary_ties(sorted, (total, docs), |value| {
+        Term::from_field_text(field, value)
+    })?;
+    Ok((total, docs))
+}
+
+/// Replaces the documents tied on the last value of a full window with the first ones in
+/// `tiebreak` order.
+fn resolve_boundary_ties<T: Clone + PartialEq>(
+    sorted: SortedWindow,
+    (total, docs): (usize, Vec<(T, DocAddress)>),
+    to_term: impl Fn(&T) -> Term,
+) -> Result<Vec<(T, DocAddress)>, ApiError> {
+    let SortedWindow {
+        searcher,
+        title_index,
+        query,
+        window,
+        tiebreak,
+    } = sorted;
+    let Some((boundary, _)) = docs.last().cloned() else {
+        return Ok(docs);
+    };
+    if docs.len() < window || total == window {
+        return Ok(docs);
+    }
+
+    let mut resolved: Vec<(T, DocAddress)> = docs
+        .iter()
+        .filter(|(value, _)| *value != boundary)
+        .cloned()
+        .collect();
+    let slots = window - resolved.len();
+    let tie_query = BooleanQuery::new(vec![
+        (Occur::Must, query.box_clone()),
+        (
+            Occur::Must,
+            Box::new(TermQuery::new(to_term(&boundary), Default::default())),
+        ),
+    ]);
+    let tied = collect_ties(searcher, title_index, &tie_query, slots, tiebreak)
+        .map_err(|err| ApiError::internal(err.into()))?;
+    // Documents without a value sort as a default and cannot be matched by term; keep the
+    // original window rather than return a short page.
+    if tied.len() < slots {
+        return Ok(docs);
+    }
+    resolved.extend(tied.into_iter().map(|addr| (boundary.clone(), addr)));
+    Ok(resolved)
+}
+
+/// Sort values of collected documents, `None` for titles without one.
+type OptionalHits = Vec<(Option<i64>, DocAddress)>;
+
+/// Collects the `window` best documents by an `i64` fast field that not every title has, such
+/// as the start year or runtime. Titles without a value only fill the window after every
+/// title with one, in `tiebreak` order, whichever way `order` runs.
+fn collect_by_optional_field(
+    sorted: SortedWindow,
+    field: Field,
+    order: Order,
+) -> Result<(usize, OptionalHits), ApiError> {
+    let SortedWindow {
+        searcher,
+        title_index,
+        query,
+        window,
+        tiebreak,
+    } = sorted;
+    let name = title_index.schema.get_field_entry(field).name().to_string();
+    let with_value = |occur| {
+        BooleanQuery::new(vec![
+            (Occur::Must, query.box_clone()),
+            (
+                occur,
+                Box::new(ExistsQuery::new(name.clone(), false)) as Box<dyn TantivyQuery>,
+            ),
+        ])
+    };
+
+    let valued_query = with_value(Occur::Must);
+    let (valued_total, valued) = collect_by_fast_field::<i64>(
+        SortedWindow {
+            query: &valued_query,
+            ..sorted
+        },
+        field,
+        order,
+        Term::from_field_i64,
+    )?;
+    let missing_query = with_value(Occur::MustNot);
+    let missing_total = searcher
+        .search(&missing_query, &Count)
+        .map_err(|err| ApiError::internal(err.into()))?;
+
+    let mut docs: OptionalHits = valued
+        .into_iter()
+        .map(|(value, addr)| (Some(value), addr))
+        .collect();
+    if docs.len() < window && missing_total > 0 {
+        let missing = collect_ties(
+            searcher,
+            title_index,
+            &missing_query,
+            window - docs.len(),
+            tiebreak,
+        )
+        .map_err(|err| ApiError::internal(err.into()))?;
+        docs.extend(missing.into_iter().map(|addr| (None, addr)));
+    }
+    Ok((valued_total + missing_total, docs))
+}
+
+/// Returns the first `limit` documents matching `query` in `tiebreak` order.
+fn collect_ties(
+    searcher: &Searcher,
+    title_index: &TitleIndex,
+    query: &dyn TantivyQuery,
+    limit: usize,
+    tiebreak: TieBreak,
+) -> tantivy::Result<Vec<DocAddress>> {
+    let fields = &title_index.fields;
+    let name = |field: Field| title_index.schema.get_field_entry(field).name().to_string();
+    let top = TopDocs::with_limit(limit);
+    let addresses = match tiebreak {
+        TieBreak::Votes => searcher
+            .search(
+                query,
+                &top.order_by_fast_field::<i64>(name(fields.num_votes), Order::Desc),
+            )?
+            .into_iter()
+            .map(|(_, addr)| addr)
+            .collect(),
+        TieBreak::Year => searcher
+            .search(
+                query,
+                &top.order_by_fast_field::<i64>(name(fields.start_year), Order::Desc),
+            )?
+            .into_iter()
+            .map(|(_, addr)| addr)
+            .collect(),
+        TieBreak::Title => searcher
+            .search(
+                query,
+                &top.order_by_string_fast_field(name(fields.primary_title_sort), Order::Asc),
+            )?
+            .into_iter()
+            .map(|(_, addr)| addr)
+            .collect(),
+        TieBreak::Tconst => searcher
+            .search(
+                query,
+                &top.order_by_string_fast_field(name(fields.tconst), Order::Asc),
+            )?
+            .into_iter()
+            .map(|(_, addr)| addr)
+            .collect(),
+    };
+    Ok(addresses)
+}
+
+/// Orders two results that tie on the requested sort; `tconst` settles anything still tied.
+fn compare_tiebreak(tiebreak: TieBreak, a: &TitleSearchResult, b: &TitleSearchResult) -> Ordering {
+    let ordering = match tiebreak {
+        TieBreak::Votes => b.num_votes.cmp(&a.num_votes),
+        TieBreak::Year => b.start_year.cmp(&a.start_year),
+        TieBreak::Title => a
+            .primary_title
+            .to_lowercase()
+            .cmp(&b.primary_title.to_lowercase()),
+        TieBreak::Tconst => Ordering::Equal,
+    };
+    ordering.then_with(|| a.tconst.cmp(&b.tconst))
+}
+
+/// `tvSeries` without an end year, which end-year filters treat as ending this year.
+///
+/// Miniseries are left out: IMDb usually lists one that aired within a single year with no
+/// end year, so a missing end year does not mean it is still airing.
+fn running_series_query(title_index: &TitleIndex) -> Box<dyn TantivyQuery> {
+    let fields = &title_index.fields;
+    let series = Term::from_field_text(fields.title_type, "tvSeries");
+    let end_year = title_index.schema.get_field_entry(fields.end_year).name();
+    Box::new(BooleanQuery::new(vec![
+        (
+            Occur::Must,
+            Box::new(TermQuery::new(series, Default::default())),
+        ),
+        (
+            Occur::MustNot,
+            Box::new(ExistsQuery::new(end_year.to_string(), false)),
+        ),
+    ]))
+}
+
+/// Upper bound on the `Count` probes run for `diagnose=true`, one per relaxed filter.
+const MAX_DIAGNOSTIC_PROBES: usize = 8;
+
+fn clone_clauses(
+    clauses: &[(Occur, Box<dyn TantivyQuery>)],
+) -> Vec<(Occur, Box<dyn TantivyQuery>)> {
+    clauses
+        .iter()
+        .map(|(occur, query)| (*occur, query.box_clone()))
+        .collect()
+}
+
+/// `Must` clauses for the labelled filters accepted by `keep`.
+fn must_filters<'a>(
+    filters: &'a [(&'static str, Box<dyn TantivyQuery>)],
+    keep: impl Fn(&str) -> bool + 'a,
+) -> impl Iterator<Item = (Occur, Box<dyn TantivyQuery>)> + 'a {
+    filters
+        .iter()
+        .filter(move |(label, _)| keep(label))
+        .map(|(_, query)| (Occur::Must, query.box_clone()))
+}
+
+/// Joins query clauses and filters into one query; no clauses at all matches everything.
+fn assemble_query(
+    mut clauses: Vec<(Occur, Box<dyn TantivyQuery>)>,
+    filters: impl Iterator<Item = (Occur, Box<dyn TantivyQuery>)>,
+) -> Box<dyn TantivyQuery> {
+    clauses.extend(filters);
+    match clauses.len() {
+        0 => Box::new(AllQuery),
+        1 => clauses.remove(0).1,
+        _ => Box::new(BooleanQuery::from(clauses)),
+    }
+}
+
+/// Counts the matches left when each filter (grouped by label) is dropped in turn, so callers
+/// can see which one eliminated every result.
+fn diagnose_filters(
+    searcher: &Searcher,
+    clauses: &[(Occur, Box<dyn TantivyQuery>)],
+    filters: &[(&'static str, Box<dyn TantivyQuery>)],
+) -> Result<Vec<FilterDiagnostic>, ApiError> {
+    let mut labels: Vec<&'static str> = Vec::new();
+    for (label, _) in filters {
+        if !labels.contains(label) {
+            labels.push(label);
+        }
+    }
+    labels
+        .into_iter()
+        .take(MAX_DIAGNOSTIC_PROBES)
+        .map(|relaxed| {
+            let query = assemble_query(
+                clone_clauses(clauses),
+                must_filters(filters, |label| label != relaxed),
+            );
+            let results_without = searcher
+                .search(query.as_ref(), &Count)
+                .map_err(|err| ApiError::internal(err.into()))?;
+            Ok(FilterDiagnostic {
+                filter: relaxed.to_string(),
+                results_without,
+            })
+        })
+        .collect()
+}
+
+/// Boost for documents whose id appears verbatim in the query, enough to outrank text matches.
+const ID_MATCH_BOOST: f32 = 1_000.0;
+
+/// One boosted `Should` clause per id, matched exactly against the id `field`.
+fn id_match_clauses(field: Field, ids: &[String]) -> Vec<(Occur, Box<dyn TantivyQuery>)> {
+    ids.iter()
+        .map(|id| {
+            let term = TermQuery::new(Term::from_field_text(field, id), Default::default());
+            let query: Box<dyn TantivyQuery> =
+                Box::new(BoostQuery::new(Box::new(term), ID_MATCH_BOOST));
+            (Occur::Should, query)
+        })
+        .collect()
+}
+
+/// Named filter clauses for the `genres` of a title search, combined per `mode`, and for
+/// `excluded` genres. Both sides go through [`genre_query`], so a genre is matched the same way
+/// whether it is required or excluded.
+fn genre_filters(
+    title_index: &TitleIndex,
+    genres: &[String],
+    mode: FilterMode,
+    excluded: &[String],
+) -> Vec<(&'static str, Box<dyn TantivyQuery>)> {
+    let queries = |genres: &[String], occur: Occur| -> Vec<(Occur, Box<dyn TantivyQuery>)> {
+        genres
+            .iter()
+            .filter(|genre| !genre.is_empty())
+            .filter_map(|genre| genre_query(title_index, genre))
+            .map(|query| (occur, query))
+            .collect()
+    };
+    let mut filters: Vec<(&'static str, Box<dyn TantivyQuery>)> = Vec::new();
+    match mode {
+        FilterMode::All => filters.extend(
+            queries(genres, Occur::Must)
+                .into_iter()
+                .map(|(_, query)| ("genres", query)),
+        ),
+        FilterMode::Any => {
+            let shoulds = queries(genres, Occur::Should);
+            if !shoulds.is_empty() {
+                filters.push(("genres", Box::new(BooleanQuery::from(shoulds))));
+            }
+        }
+    }
+
+    let exclusions = queries(excluded, Occur::MustNot);
+    if !exclusions.is_empty() {
+        // A lone `MustNot` matches nothing, so exclusions subtract from every document.
+        let mut exclusion: Vec<(Occur, Box<dyn TantivyQuery>)> =
+            vec![(Occur::Must, Box::new(AllQuery))];
+        exclusion.extend(exclusions);
+        filters.push(("exclude_genres", Box::new(BooleanQuery::new(exclusion))));
+    }
+    filters
+}
+
+/// Requires at least one of the `Should` clauses in `matchers`, unwrapping a lone clause.
+fn combine_matchers(mut matchers: Vec<(Occur, Box<dyn TantivyQuery>)>) -> Box<dyn TantivyQuery> {
+    match matchers.len() {
+        1 => matchers.remove(0).1,
+        _ => Box::new(BooleanQuery::from(matchers)),
+    }
+}
+
+/// Fields that may be targeted with `field:value` syntax in a title query, as
+/// `(name accepted in the query, schema field name)`.
+const QUERYABLE_TITLE_FIELDS: &[(&str, &str)] = &[
+    ("title", "primaryTitle"),
+    ("genre", "genres"),
+    ("genres", "genres"),
+    ("title_type", "titleType"),
+    ("year", "startYear"),
+];
+
+/// Parses a user title query, allowing `field:value` only for [`QUERYABLE_TITLE_FIELDS`].
+fn parse_title_query(
+    title_index: &TitleIndex,
+    fuzzy: FuzzyDistance,
+    operator: Operator,
+    text: &str,
+) -> Result<Box<dyn TantivyQuery>, ApiError> {
+    let mut ast = query_grammar::parse_query(text).map_err(|_| {
+        ApiError::bad_request(format!(
+            "invalid query: syntax error; quote special characters, and use `field:value` only \
+             with {}",
+            queryable_title_fields()
+        ))
+    })?;
+    restrict_query_fields(&mut ast)?;
+    lift_negated_groups(&mut ast);
+    build_fuzzy_query(ast, fuzzy, operator, &|distance| {
+        title_index.query_parser(distance)
+    })
+}
+
+/// Parses a user name query.
+fn parse_name_query(
+    name_index: &NameIndex,
+    fuzzy: FuzzyDistance,
+    operator: Operator,
+    text: &str,
+) -> Result<Box<dyn TantivyQuery>, ApiError> {
+    let mut ast = query_grammar::parse_query(text)
+        .map_err(|_| ApiError::bad_request("invalid query: syntax error"))?;
+    lift_negated_groups(&mut ast);
+    build_fuzzy_query(ast, fuzzy, operator, &|distance| {
+        name_index.query_parser(distance)
+    })
+}
+
+/// Builds a parsed query, giving every term the fuzzy distance `fuzzy` picks for it.
+///
+/// The stored parsers tolerate one distance on every field, so each literal is built on its
+/// own with the parser for its distance and the clauses are combined as the parser would.
+fn build_fuzzy_query<'a>(
+    ast: UserInputAst,
+    fuzzy: FuzzyDistance,
+    operator: Operator,
+    parser_for: &dyn Fn(u8) -> &'a QueryParser,
+) -> Result<Box<dyn TantivyQuery>, ApiError> {
+    let invalid = |err: QueryParserError| ApiError::bad_request(format!("invalid query: {err}"));
+    // Checks that need the whole query, such as rejecting one that only excludes.
+    with_operator(parser_for(0), operator)
+        .build_query_from_user_input_ast(ast.clone())
+        .map_err(invalid)?;
+    build_terms(ast, fuzzy, operator, parser_for).map_err(invalid)
+}
+
+fn build_terms<'a>(
+    ast: UserInputAst,
+    fuzzy: FuzzyDistance,
+    operator: Operator,
+    parser_for: &dyn Fn(u8) -> &'a QueryParser,
+) -> Result<Box<dyn TantivyQuery>, QueryParserError> {
+    match ast {
+        UserInputAst::Clause(clauses) => {
+            let default_occur = match operator {
+                Operator::Or => Occur::Should,
+                Operator::And => Occur::Must,
+            };
+            let mut subqueries = Vec::with_capacity(clauses.len());
+            for (occur, clause) in clauses {
+                let query = build_terms(clause, fuzzy, operator, parser_for)?;
+                // Literals without any token, such as punctuation, are dropped like the parser
+                // does rather than becoming required clauses that match nothing.
+                if !query.is::<EmptyQuery>() {
+                    subqueries.push((occur.unwrap_or(default_occur), query));
+                }
+            }
+            if subqueries.is_empty() {
+                return Ok(Box::new(EmptyQuery));
+            }
+            Ok(Box::new(BooleanQuery::new(subqueries)))
+        }
+        UserInputAst::Boost(inner, boost) => {
+            let query = build_terms(*inner, fuzzy, operator, parser_for)?;
+            Ok(Box::new(BoostQuery::new(query, boost as Score)))
+        }
+        UserInputAst::Leaf(leaf) => {
+            let distance = match leaf.as_ref() {
+                UserInputLeaf::Literal(literal) => fuzzy.for_term(&literal.phrase),
+                _ => 0,
+            };
+            with_operator(parser_for(distance), operator)
+                .build_query_from_user_input_ast(UserInputAst::Leaf(leaf))
+        }
+    }
+}
+
+/// `parser` combining plain query words with `operator`; the stored parsers OR them.
+fn with_operator(parser: &QueryParser, operator: Operator) -> Cow<'_, QueryParser> {
+    match operator {
+        Operator::Or => Cow::Borrowed(parser),
+        Operator::And => {
+            let mut parser = parser.clone();
+            parser.set_conjunction_by_default();
+            Cow::Owned(parser)
+        }
+    }
+}
+
+/// Comma-separated query names of [`QUERYABLE_TITLE_FIELDS`], for error messages.
+fn queryable_title_fields() -> String {
+    QUERYABLE_TITLE_FIELDS
+        .iter()
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Maps every field named in `ast` to its schema name, rejecting fields outside the whitelist.
+fn restrict_query_fields(ast: &mut UserInputAst) -> Result<(), ApiError> {
+    let resolve = |field: &mut String| -> Result<(), ApiError> {
+        let (_, schema_name) = QUERYABLE_TITLE_FIELDS
+            .iter()
+            .find(|(name, _)| *name == field.as_str())
+            .ok_or_else(|| {
+                ApiError::bad_request(format!(
+                    "field `{field}` cannot be queried; allowed fields: {}",
+                    queryable_title_fields()
+                ))
+            })?;
+        *field = schema_name.to_string();
+        Ok(())
+    };
+    match ast {
+        UserInputAst::Clause(clauses) => {
+            for (_, clause) in clauses {
+                restrict_query_fields(clause)?;
+            }
+            Ok(())
+        }
+        UserInputAst::Boost(inner, _) => restrict_query_fields(inner),
+        UserInputAst::Leaf(leaf) => match leaf.as_mut() {
+            UserInputLeaf::Literal(literal) => literal.field_name.as_mut().map_or(Ok(()), resolve),
+            UserInputLeaf::Range { field, .. } | UserInputLeaf::Set { field, .. } => {
+                field.as_mut().map_or(Ok(()), resolve)
+            }
+            UserInputLeaf::Exists { field } => resolve(field),
+            UserInputLeaf::All => Ok(()),
+        },
+    }
+}
+
+/// Turns required groups made only of exclusions, as `a AND NOT b` parses to, into the
+/// exclusions themselves. A required group that can only exclude would match nothing.
+fn lift_negated_groups(ast: &mut UserInputAst) {
+    match ast {
+        UserInputAst::Clause(clauses) => {
+            for (_, clause) in clauses.iter_mut() {
+                lift_negated_groups(clause);
+            }
+            *clauses = std::mem::take(clauses)
+                .into_iter()
+                .flat_map(|(occur, clause)| match clause {
+                    UserInputAst::Clause(group)
+                        if occur == Some(Occur::Must)
+                            && !group.is_empty()
+                            && group
+                                .iter()
+                                .all(|(occur, _)| *occur == Some(Occur::MustNot)) =>
+                    {
+                        group
+                    }
+                    clause => vec![(occur, clause)],
+                })
+                .collect();
+        }
+        UserInputAst::Boost(inner, _) => lift_negated_groups(inner),
+        UserInputAst::Leaf(_) => {}
+    }
+}
+
+/// Weight of the `match=phrase` clause, added on top of the normal match so titles holding the
+/// words together and in order outrank those that merely contain them.
+const PHRASE_MATCH_BOOST: f32 = 10.0;
+
+/// Matches `text` as a phrase in `primaryTitle` or `searchTitles` (which also holds original
+/// and AKA titles). A single-word query degrades to a plain term query; `None` when `text` has
+/// no indexable words.
+fn build_title_phrase_query(
+    title_index: &TitleIndex,
+    text: &str,
+) -> Result<Option<Box<dyn TantivyQuery>>, ApiError> {
+    let mut analyzer = title_index
+        .tokenizers
+        .get("default")
+        .ok_or_else(|| ApiError::internal(anyhow::anyhow!("default tokenizer missing")))?;
+    let shoulds: Vec<(Occur, Box<dyn TantivyQuery>)> = [
+        title_index.fields.primary_title,
+        title_index.fields.search_titles,
+    ]
+    .into_iter()
+    .filter_map(|field| phrase_or_term_query(&mut analyzer, field, text))
+    .map(|query| (Occur::Should, query))
+    .collect();
+    Ok((!shoulds.is_empty())
+        .then(|| Box::new(BooleanQuery::from(shoulds)) as Box<dyn TantivyQuery>))
+}
+
+/// Wraps the query terms found in each result's `primaryTitle` in `<em>` tags. Highlights come
+/// from the exact parse of `text`, so titles matched only fuzzily or via another field get none.
+fn highlight_titles(
+    title_index: &TitleIndex,
+    searcher: &Searcher,
+    text: &str,
+    results: &mut [TitleSearchResult],
+) -> Result<(), ApiError> {
+    let Ok(query) = parse_title_query(title_index, FuzzyDistance::Fixed(0), Operator::Or, text)
+    else {
+        return Ok(());
+    };
+    let mut generator =
+        SnippetGenerator::create(searcher, query.as_ref(), title_index.fields.primary_title)
+            .map_err(|err| ApiError::internal(err.into()))?;
+    for result in results {
+        // Titles are short, so keep the whole title rather than a fragment of it.
+        generator.set_max_num_chars(result.primary_title.len());
+        let mut snippet = generator.snippet(&result.primary_title);
+        if snippet.highlighted().is_empty() {
+            continue;
+        }
+        snippet.set_snippet_prefix_postfix("<em>", "</em>");
+        result.highlighted_title = Some(snippet.to_html());
+    }
+    Ok(())
+}
+
+/// Requires every ngram of `text` to appear in the title's ngram field, approximating a
+/// substring match. Returns `None` when the text is shorter than the minimum gram size.
+fn build_infix_query(
+    title_index: &TitleIndex,
+    text: &str,
+) -> Result<Option<Box<dyn TantivyQuery>>, ApiError> {
+    let (Some(field), Some(mut analyzer)) = (
+        title_index.fields.primary_title_ngram,
+        title_index.tokenizers.get(TITLE_NGRAM_TOKENIZER),
+    ) else {
+        return Err(ApiError::bad_request(
+            "infix search is not enabled on this deployment",
+        ));
+    };
+
+    let mut grams = BTreeSet::new();
+    analyzer.token_stream(text).process(&mut |token| {
+        grams.insert(token.text.clone());
+    });
+    if grams.is_empty() {
+        return Ok(None);
+    }
+
+    let clauses: Vec<(Occur, Box<dyn TantivyQuery>)> = grams
+        .into_iter()
+        .map(|gram| {
+            let term = Term::from_field_text(field, &gram);
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(term, Default::default())) as Box<dyn TantivyQuery>,
+            )
+        })
+        .collect();
+    Ok(Some(Box::new(BooleanQuery::from(clauses))))
+}
+
+/// How many hits per requested bucket are fetched before partitioning grouped searches.
+const GROUP_OVERFETCH: usize = 5;
+
+/// Buckets already-sorted results by title type, keeping at most `limit` per bucket.
+fn group_by_title_type(
+    results: Vec<TitleSearchResult>,
+    limit: usize,
+) -> BTreeMap<String, Vec<TitleSearchResult>> {
+    let mut groups: BTreeMap<String, Vec<TitleSearchResult>> = BTreeMap::new();
+    for result in results {
+        let bucket = groups
+            .entry(result.title_type.clone().unwrap_or_default())
+            .or_default();
+        if bucket.len() < limit {
+            bucket.push(result);
+        }
+    }
+    groups
+}
+
+/// Searches titles.
+#[utoipa::path(
+    get,
+    path = "/titles/search",
+    tag = "titles",
+    params(TitleSearchParams),
+    responses(
+        (status = 200, headers(("x-index-generation" = String)), content(
+            (TitleSearchOutput = "application/json"),
+            (String = "text/csv"),
+        )),
+        (status = 400, body = ErrorBody),
+    )
+)]
 #[instrument(skip_all)]
 pub async fn search_titles(
     State(state): State<AppState>,
-    AxumQuery(params): AxumQuery<TitleSearchParams>,
-) -> Result<Json<TitleSearchResponse>, ApiError> {
-    let limit = params.limit.unwrap_or(10).clamp(1, 50);
+    ValidatedQuery(params): ValidatedQuery<TitleSearchParams>,
+) -> Result<Response, ApiError> {
+    // Cloned only when the outcome is shared with the cache, outside its lock.
+    let outcome = Arc::unwrap_or_clone(cached_title_search(&state, &params)?);
+    if params.format == Some(Format::Csv) {
+        let body =
+            title_results_csv(&outcome.results).map_err(|err| ApiError::internal(err.into()))?;
+        return Ok((
+            [
+                (CONTENT_TYPE.as_str(), "text/csv; charset=utf-8".to_string()),
+                (INDEX_GENERATION_HEADER, outcome.generation),
+            ],
+            body,
+        )
+            .into_response());
+    }
+    Ok((
+        [(INDEX_GENERATION_HEADER, outcome.generation)],
+        Json(match params.group_by {
+            Some(GroupBy::TitleType) => TitleSearchOutput::Grouped(GroupedTitleSearchResponse {
+                total: outcome.total,
+                groups: group_by_title_type(outcome.results, outcome.limit),
+                facet_counts: outcome.facet_counts,
+                diagnostics: outcome.diagnostics,
+                query_debug: outcome.query_debug,
+                did_you_mean: outcome.did_you_mean,
+            }),
+            None => TitleSearchOutput::Flat(TitleSearchResponse {
+                total: outcome.total,
+                results: outcome.results,
+                facet_counts: outcome.facet_counts,
+                diagnostics: outcome.diagnostics,
+                query_debug: outcome.query_debug,
+                did_you_mean: outcome.did_you_mean,
+            }),
+        }),
+    )
+        .into_response())
+}
+
+/// Runs a title search through [`AppState::search_cache`] unless `no_cache` is set or the
+/// request is a CSV export, whose pages (up to `IMDB_MAX_EXPORT_LIMIT`) would crowd out the
+/// interactive searches the cache is for.
+///
+/// Keys are the request parameters with the query's whitespace collapsed, so `marvel` and
+/// ` marvel ` share an entry; entries computed from an older index generation are recomputed.
+fn cached_title_search(
+    state: &AppState,
+    params: &TitleSearchParams,
+) -> Result<Arc<TitleSearchOutcome>, ApiError> {
+    if params.no_cache.unwrap_or(false) || params.format == Some(Format::Csv) {
+        return run_title_search(state, params).map(Arc::new);
+    }
+    let key = TitleSearchKey::new(params)?;
+    let generation = index_generation(&state.title_index.load().reader.searcher());
+    {
+        // A panic while holding the lock cannot leave an entry half-written, so a poisoned
+        // cache is still safe to use.
+        let mut cache = state
+            .search_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if !cache.is_enabled() {
+            drop(cache);
+            return run_title_search(state, params).map(Arc::new);
+        }
+        if let Some(outcome) = cache.get(&key, &generation) {
+            return Ok(outcome);
+        }
+    }
+
+    let outcome = Arc::new(run_title_search(state, params)?);
+    state
+        .search_cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, outcome.generation.clone(), Arc::clone(&outcome));
+    Ok(outcome)
+}
+
+/// Cache key of a title search: its parameters serialized with the query whitespace collapsed
+/// and `no_cache` cleared, so equivalent requests share an entry.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TitleSearchKey(String);
+
+impl TitleSearchKey {
+    fn new(params: &TitleSearchParams) -> Result<Self, ApiError> {
+        let normalized = TitleSearchParams {
+            query: params
+                .query
+                .as_deref()
+                .map(|query| query.split_whitespace().collect::<Vec<_>>().join(" ")),
+            no_cache: None,
+            ..params.clone()
+        };
+        serde_json::to_string(&normalized)
+            .map(Self)
+            .map_err(|err| ApiError::internal(err.into()))
+    }
+}
+
+/// A title search before it is rendered as JSON or CSV.
+#[derive(Clone)]
+pub(crate) struct TitleSearchOutcome {
+    total: usize,
+    /// Requested page size; `results` holds more when grouping over-fetched.
+    limit: usize,
+    results: Vec<TitleSearchResult>,
+    facet_counts: Option<BTreeMap<String, u64>>,
+    diagnostics: Option<Vec<FilterDiagnostic>>,
+    query_debug: Option<String>,
+    did_you_mean: Option<String>,
+    generation: String,
+}
+
+fn run_title_search(
+    state: &AppState,
+    params: &TitleSearchParams,
+) -> Result<TitleSearchOutcome, ApiError> {
+    // CSV exports may ask for bigger pages than interactive JSON searches.
+    let max_limit = match params.format.unwrap_or_default() {
+        Format::Json => state.config.max_limit,
+        Format::Csv => state.config.max_export_limit,
+    };
+    let limit = params.limit.unwrap_or(10).clamp(1, max_limit);
+    let offset = params.offset.unwrap_or(0);
+    if offset.saturating_add(limit) > MAX_RESULT_WINDOW {
+        return Err(ApiError::bad_request(format!(
+            "offset + limit must not exceed {MAX_RESULT_WINDOW}"
+        )));
+    }
+    // Grouping partitions one over-fetched page, so `limit` applies per bucket.
+    let fetch_limit = if params.group_by.is_some() {
+        limit * GROUP_OVERFETCH
+    } else {
+        limit
+    };
     let sort_mode = params.sort.unwrap_or_default();
+    if let Some(allowed) = state.config.allowed_sorts.as_ref()
+        && !allowed.contains(&sort_mode)
+    {
+        return Err(ApiError::bad_request(format!(
+            "sort mode `{}` is not enabled on this deployment",
+            sort_mode.as_str()
+        )));
+    }
 
     let query_text = params.query.as_deref().unwrap_or("").trim().to_string();
-    let default_title_types = vec!["movie".to_string(), "tvSeries".to_string()];
+    let min_query_len = state.config.min_query_len;
+    if !query_text.is_empty() && query_text.chars().count() < min_query_len && !params.has_filters()
+    {
+        return Err(ApiError::bad_request(format!(
+            "query must be at least {min_query_len} characters unless a filter is given"
+        )));
+    }
+    let (query_ids, text_query) = split_embedded_ids(&query_text, "tt");
+    // The browse defaults target movies and series, so they would hide a show's episodes.
+    let implicit_defaults = !state.config.disable_implicit_defaults
+        && params.parent.is_none()
+        && params.season.is_none();
+    let default_title_types = if implicit_defaults {
+        state.config.default_title_types.clone()
+    } else {
+        Vec::new()
+    };
     let title_types: Vec<String> = match params.title_type.as_ref() {
         Some(value) if !value.is_empty() => vec![value.clone()],
         _ => default_title_types,
     };
 
-    let query_lower = if query_text.is_empty() {
+    let query_lower = if text_query.is_empty() {
         None
     } else {
-        Some(query_text.to_lowercase())
+        Some(text_query.to_lowercase())
     };
 
-    if query_text.is_empty()
+    // Logged only when both defaults named below actually take effect; `has_filters` is
+    // about query length, not about which defaults apply.
+    if implicit_defaults
+        && query_text.is_empty()
         && params.title_type.is_none()
-        && params.start_year_min.is_none()
-        && params.min_rating.is_none()
-        && params.max_rating.is_none()
-        && params.min_votes.is_none()
-        && params.max_votes.is_none()
-        && params.genres.is_empty()
+        && params.start_year_bounds().0.is_none()
     {
-        debug!("applying default title filters: titleType in [movie,tvSeries], start_year>=1980");
+        debug!(
+            title_types = ?state.config.default_title_types,
+            "applying default title filters: configured title types, start_year>=1980"
+        );
     }
 
-    let title_index = &state.title_index;
+    let title_index = &*state.title_index.load_full();
     let searcher = title_index.reader.searcher();
 
     let mut clauses: Vec<(Occur, Box<dyn TantivyQuery>)> = Vec::new();
 
+    let fuzzy = if params.fuzzy.unwrap_or(true) {
+        state.config.fuzzy_distance
+    } else {
+        FuzzyDistance::Fixed(0)
+    };
     if !query_text.is_empty() {
-        let parsed_query = title_index
-            .query_parser
-            .parse_query(&query_text)
-            .map_err(|err| ApiError::bad_request(format!("invalid query: {}", err)))?;
-        clauses.push((Occur::Must, parsed_query));
+        let mut matchers = id_match_clauses(title_index.fields.tconst, &query_ids);
+        if !text_query.is_empty() {
+            let parsed_query = parse_title_query(
+                title_index,
+                fuzzy,
+                params.default_operator.unwrap_or_default(),
+                &text_query,
+            )?;
+            matchers.push((Occur::Should, parsed_query));
+            // Titles with the words together and in order rank first; the rest still match.
+            if params.match_mode.unwrap_or_default() == MatchMode::Phrase
+                && let Some(phrase_query) = build_title_phrase_query(title_index, &text_query)?
+            {
+                clauses.push((
+                    Occur::Should,
+                    Box::new(BoostQuery::new(phrase_query, PHRASE_MATCH_BOOST)),
+                ));
+            }
+            if params.infix.unwrap_or(false)
+                && let Some(infix_query) = build_infix_query(title_index, &text_query)?
+            {
+                matchers.push((Occur::Should, infix_query));
+            }
+        }
+        clauses.push((Occur::Must, combine_matchers(matchers)));
 
         if let Some(ref qlc) = query_lower {
             let term = Term::from_field_text(title_index.fields.primary_title, qlc);
@@ -98,19 +1038,23 @@ pub async fn search_titles(
                 );
                 clauses.push((Occur::Should, Box::new(boosted_exact)));
 
-                if qlc.len() >= 3 {
-                    let fuzzy = FuzzyTermQuery::new(term_exact, 1, true);
-                    let boosted_fuzzy = BoostQuery::new(Box::new(fuzzy), 30.0);
+                let distance = fuzzy.for_term(qlc);
+                if distance > 0 {
+                    let fuzzy_query = FuzzyTermQuery::new(term_exact, distance, true);
+                    let boosted_fuzzy = BoostQuery::new(Box::new(fuzzy_query), 30.0);
                     clauses.push((Occur::Should, Box::new(boosted_fuzzy)));
                 }
             }
         }
     }
 
+    // Filters are labelled by parameter so `diagnose=true` can relax them one at a time.
+    let mut filters: Vec<(&'static str, Box<dyn TantivyQuery>)> = Vec::new();
+
     if title_types.len() == 1 {
         let term = Term::from_field_text(title_index.fields.title_type, &title_types[0]);
         let query = TermQuery::new(term, Default::default());
-        clauses.push((Occur::Must, Box::new(query)));
+        filters.push(("title_type", Box::new(query)));
     } else {
         let shoulds: Vec<(Occur, Box<dyn TantivyQuery>)> = title_types
             .into_iter()
@@ -123,18 +1067,12 @@ pub async fn search_titles(
             })
             .collect();
         if !shoulds.is_empty() {
-            clauses.push((Occur::Must, Box::new(BooleanQuery::from(shoulds))));
+            filters.push(("title_type", Box::new(BooleanQuery::from(shoulds))));
         }
     }
 
-    let mut year_min = params.start_year_min.unwrap_or(1980);
-    let mut year_max = params.start_year_max;
-    if let Some(explicit_min) = params.start_year_min {
-        year_min = explicit_min;
-    }
-    if let Some(explicit_max) = params.start_year_max {
-        year_max = Some(explicit_max);
-    }
+    let (start_year_min, year_max) = params.start_year_bounds();
+    let year_min = start_year_min.unwrap_or(if implicit_defaults { 1980 } else { 0 });
 
     if year_min != 0 || year_max.is_some() {
         let lower = Bound::Included(Term::from_field_i64(
@@ -146,10 +1084,22 @@ pub async fn search_titles(
                 Bound::Included(Term::from_field_i64(title_index.fields.start_year, value))
             })
             .unwrap_or(Bound::Unbounded);
-        let range = RangeQuery::new(lower, upper);
-        clauses.push((Occur::Must, Box::new(range)));
+        let range: Box<dyn TantivyQuery> = Box::new(RangeQuery::new(lower, upper));
+        // The implicit floor keeps browsing current, which a show still airing is whenever
+        // it started.
+        let filter = if start_year_min.is_none() && year_max.is_none() {
+            Box::new(BooleanQuery::new(vec![
+                (Occur::Should, range),
+                (Occur::Should, running_series_query(title_index)),
+            ]))
+        } else {
+            range
+        };
+        filters.push(("start_year", filter));
     }
 
+    // There is no implicit end-year bound: only finished series carry an end year, so a
+    // default floor would silently drop everything else.
     if params.end_year_min.is_some() || params.end_year_max.is_some() {
         let lower = params
             .end_year_min
@@ -159,13 +1109,32 @@ pub async fn search_titles(
             .end_year_max
             .map(|value| Bound::Included(Term::from_field_i64(title_index.fields.end_year, value)))
             .unwrap_or(Bound::Unbounded);
-        let range = RangeQuery::new(lower, upper);
-        clauses.push((Occur::Must, Box::new(range)));
+        let range: Box<dyn TantivyQuery> = Box::new(RangeQuery::new(lower, upper));
+        let current_year = i64::from(Utc::now().year());
+        let covers_current_year = params.end_year_min.is_none_or(|min| min <= current_year)
+            && params.end_year_max.is_none_or(|max| max >= current_year);
+        let filter = if covers_current_year {
+            Box::new(BooleanQuery::new(vec![
+                (Occur::Should, range),
+                (Occur::Should, running_series_query(title_index)),
+            ]))
+        } else {
+            range
+        };
+        filters.push(("end_year", filter));
     }
 
-    if params.min_rating.is_some() || params.max_rating.is_some() {
-        let lower = params
-            .min_rating
+    // Like the 1980 year floor, the browse rating floor only applies without query text.
+    let min_rating = params.min_rating.or_else(|| {
+        if implicit_defaults && query_text.is_empty() {
+            state.config.browse_min_rating
+        } else {
+            None
+        }
+    });
+
+    if min_rating.is_some() || params.max_rating.is_some() {
+        let lower = min_rating
             .map(|value| {
                 Bound::Included(Term::from_field_f64(
                     title_index.fields.average_rating,
@@ -183,7 +1152,7 @@ pub async fn search_titles(
             })
             .unwrap_or(Bound::Unbounded);
         let range = RangeQuery::new(lower, upper);
-        clauses.push((Occur::Must, Box::new(range)));
+        filters.push(("rating", Box::new(range)));
     }
 
     if params.min_votes.is_some() || params.max_votes.is_some() {
@@ -196,84 +1165,215 @@ pub async fn search_titles(
             .map(|value| Bound::Included(Term::from_field_i64(title_index.fields.num_votes, value)))
             .unwrap_or(Bound::Unbounded);
         let range = RangeQuery::new(lower, upper);
-        clauses.push((Occur::Must, Box::new(range)));
+        filters.push(("votes", Box::new(range)));
     }
 
-    for genre in params.genres.iter().filter(|genre| !genre.is_empty()) {
-        let term = Term::from_field_text(title_index.fields.genres, genre);
-        let query = TermQuery::new(term, Default::default());
-        clauses.push((Occur::Must, Box::new(query)));
+    if let Some(tier) = params.tier {
+        let term = Term::from_field_text(title_index.fields.popularity_tier, tier.as_str());
+        filters.push(("tier", Box::new(TermQuery::new(term, Default::default()))));
     }
 
-    let combined_query: Box<dyn TantivyQuery> = match clauses.len() {
-        0 => Box::new(AllQuery),
-        1 => clauses.into_iter().next().unwrap().1,
-        _ => Box::new(BooleanQuery::from(clauses)),
+    // Adult titles are hidden unless asked for; the exclusion counts as an implicit default.
+    if !params.include_adult.unwrap_or(!implicit_defaults) {
+        let term = Term::from_field_i64(title_index.fields.is_adult, 0);
+        filters.push((
+            "include_adult",
+            Box::new(TermQuery::new(term, Default::default())),
+        ));
+    }
+
+    if params.min_runtime.is_some() || params.max_runtime.is_some() {
+        let lower = params
+            .min_runtime
+            .map(|value| {
+                Bound::Included(Term::from_field_i64(
+                    title_index.fields.runtime_minutes,
+                    value,
+                ))
+            })
+            .unwrap_or(Bound::Unbounded);
+        let upper = params
+            .max_runtime
+            .map(|value| {
+                Bound::Included(Term::from_field_i64(
+                    title_index.fields.runtime_minutes,
+                    value,
+                ))
+            })
+            .unwrap_or(Bound::Unbounded);
+        let range = RangeQuery::new(lower, upper);
+        filters.push(("runtime", Box::new(range)));
+    }
+
+    let requested_genres: Vec<String> = params
+        .genres
+        .iter()
+        .filter(|genre| !genre.is_empty())
+        .cloned()
+        .collect();
+    filters.extend(genre_filters(
+        title_index,
+        &requested_genres,
+        params.genre_mode.unwrap_or_default(),
+        &params.exclude_genres,
+    ));
+
+    if let Some(person) = params
+        .person
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        let term = Term::from_field_text(title_index.fields.people_ids, person);
+        filters.push(("person", Box::new(TermQuery::new(term, Default::default()))));
+    }
+
+    if let Some(parent) = params
+        .parent
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        let term = Term::from_field_text(title_index.fields.parent_tconst, parent);
+        filters.push(("parent", Box::new(TermQuery::new(term, Default::default()))));
+    }
+    if let Some(season) = params.season {
+        let bound = |value| {
+            Bound::Included(Term::from_field_i64(
+                title_index.fields.season_number,
+                value,
+            ))
+        };
+        filters.push((
+            "season",
+            Box::new(RangeQuery::new(bound(season), bound(season))),
+        ));
+    }
+
+    for (label, field, value) in [
+        (
+            "region",
+            title_index.fields.aka_regions,
+            params.region.as_deref(),
+        ),
+        (
+            "aka_language",
+            title_index.fields.aka_languages,
+            params.aka_language.as_deref(),
+        ),
+    ] {
+        if let Some(value) = value.map(str::trim).filter(|value| !value.is_empty()) {
+            let term = Term::from_field_text(field, &value.to_lowercase());
+            filters.push((label, Box::new(TermQuery::new(term, Default::default()))));
+        }
+    }
+
+    // Facets ignore the genre filter so sibling genres keep their counts.
+    let facet_counts = if params.facets.unwrap_or(false) {
+        let mut facet_clauses = clone_clauses(&clauses);
+        facet_clauses.extend(must_filters(&filters, |label| label != "genres"));
+        Some(
+            genres::genre_facet_counts(title_index, &searcher, &facet_clauses)
+                .map_err(|err| ApiError::internal(err.into()))?,
+        )
+    } else {
+        None
     };
 
-    let field_name = |field: Field| title_index.schema.get_field_entry(field).name().to_string();
+    let combined_query = assemble_query(clone_clauses(&clauses), must_filters(&filters, |_| true));
 
     enum CollectedDocs {
         Score(Vec<(Score, DocAddress)>),
         F64(Vec<(f64, DocAddress)>),
         I64(Vec<(i64, DocAddress)>),
+        Optional(OptionalHits),
+        Text(Vec<(String, DocAddress)>),
     }
 
-    let hits = match sort_mode {
+    let tiebreak = state.config.tiebreak;
+    let strip_articles = params.strip_articles.unwrap_or(false);
+    let fast_field_order = match sort_mode {
+        SortMode::RatingAsc
+        | SortMode::VotesAsc
+        | SortMode::YearAsc
+        | SortMode::RuntimeAsc
+        | SortMode::TitleAsc => Order::Asc,
+        _ => Order::Desc,
+    };
+
+    let candidate_basis = query_lower.as_deref().unwrap_or(query_text.as_str());
+    let rerank_window = candidate_limit_for(candidate_basis, RERANK_PAGE);
+
+    let sorted = SortedWindow {
+        searcher: &searcher,
+        title_index,
+        query: combined_query.as_ref(),
+        window: offset + fetch_limit,
+        tiebreak,
+    };
+
+    let started = Instant::now();
+    // Counted alongside the page so `total` always reflects the exact same query.
+    let (total, hits) = match sort_mode {
         SortMode::Relevance => {
-            let candidate_basis = query_lower
-                .as_deref()
-                .unwrap_or_else(|| query_text.as_str());
-            let candidate_limit = candidate_limit_for(candidate_basis, limit);
-            CollectedDocs::Score(
-                searcher
-                    .search(&combined_query, &TopDocs::with_limit(candidate_limit))
-                    .map_err(|err| ApiError::internal(err.into()))?,
-            )
+            // Pages beyond the re-rank window are fetched too, but keep their BM25 order.
+            let candidate_limit = rerank_window.max(offset + fetch_limit);
+            let (total, docs) = searcher
+                .search(
+                    &combined_query,
+                    &(Count, TopDocs::with_limit(candidate_limit)),
+                )
+                .map_err(|err| ApiError::internal(err.into()))?;
+            (total, CollectedDocs::Score(docs))
         }
-        SortMode::RatingDesc => {
-            let collector = TopDocs::with_limit(limit).order_by_fast_field::<f64>(
-                field_name(title_index.fields.average_rating),
-                Order::Desc,
-            );
-            CollectedDocs::F64(
-                searcher
-                    .search(&combined_query, &collector)
-                    .map_err(|err| ApiError::internal(err.into()))?,
-            )
+        SortMode::RatingDesc | SortMode::RatingAsc => {
+            let (total, docs) = collect_by_fast_field::<f64>(
+                sorted,
+                title_index.fields.average_rating,
+                fast_field_order.clone(),
+                Term::from_field_f64,
+            )?;
+            (total, CollectedDocs::F64(docs))
         }
-        SortMode::RatingAsc => {
-            let collector = TopDocs::with_limit(limit).order_by_fast_field::<f64>(
-                field_name(title_index.fields.average_rating),
-                Order::Asc,
-            );
-            CollectedDocs::F64(
-                searcher
-                    .search(&combined_query, &collector)
-                    .map_err(|err| ApiError::internal(err.into()))?,
-            )
+        SortMode::VotesDesc | SortMode::VotesAsc => {
+            let (total, docs) = collect_by_fast_field::<i64>(
+                sorted,
+                title_index.fields.num_votes,
+                fast_field_order.clone(),
+                Term::from_field_i64,
+            )?;
+            (total, CollectedDocs::I64(docs))
         }
-        SortMode::VotesDesc => {
-            let collector = TopDocs::with_limit(limit)
-                .order_by_fast_field::<i64>(field_name(title_index.fields.num_votes), Order::Desc);
-            CollectedDocs::I64(
-                searcher
-                    .search(&combined_query, &collector)
-                    .map_err(|err| ApiError::internal(err.into()))?,
-            )
+        SortMode::YearDesc | SortMode::YearAsc => {
+            let (total, docs) = collect_by_optional_field(
+                sorted,
+                title_index.fields.start_year,
+                fast_field_order.clone(),
+            )?;
+            (total, CollectedDocs::Optional(docs))
         }
-        SortMode::VotesAsc => {
-            let collector = TopDocs::with_limit(limit)
-                .order_by_fast_field::<i64>(field_name(title_index.fields.num_votes), Order::Asc);
-            CollectedDocs::I64(
-                searcher
-                    .search(&combined_query, &collector)
-                    .map_err(|err| ApiError::internal(err.into()))?,
-            )
+        SortMode::RuntimeDesc | SortMode::RuntimeAsc => {
+            let (total, docs) = collect_by_optional_field(
+                sorted,
+                title_index.fields.runtime_minutes,
+                fast_field_order.clone(),
+            )?;
+            (total, CollectedDocs::Optional(docs))
+        }
+        SortMode::TitleAsc | SortMode::TitleDesc => {
+            let field = if strip_articles {
+                title_index.fields.primary_title_sort_stripped
+            } else {
+                title_index.fields.primary_title_sort
+            };
+            let (total, docs) = collect_by_string_field(sorted, field, fast_field_order.clone())?;
+            (total, CollectedDocs::Text(docs))
         }
     };
 
     let mut results = Vec::new();
+    let localize = params.localization();
+    let debug_score = params.debug_score.unwrap_or(false);
 
     match hits {
         CollectedDocs::Score(docs) => {
@@ -281,9 +1381,33 @@ pub async fn search_titles(
                 let doc = searcher
                     .doc::<TantivyDocument>(addr)
                     .map_err(|err| ApiError::internal(err.into()))?;
-                let mut result = document_to_title_result(&doc, &title_index.fields)?;
-                let final_score =
-                    compute_title_relevance_score(base_score, &result, query_lower.as_deref());
+                let mut result = document_to_title_result(
+                    &doc,
+                    &title_index.fields,
+                    localize,
+                    &state.config.scoring,
+                )?;
+                let recent = recent_window_multiplier(&result, params.boost_recent_years);
+                let genre = genre_match_multiplier(
+                    &result,
+                    &requested_genres,
+                    state.config.genre_match_boost,
+                );
+                let final_score = state
+                    .scorer
+                    .score(base_score, &result, query_lower.as_deref())
+                    * recent
+                    * genre;
+                if debug_score {
+                    result.score_breakdown = state
+                        .scorer
+                        .breakdown(base_score, &result, query_lower.as_deref())
+                        .map(|breakdown| ScoreBreakdown {
+                            recent_window_multiplier: f64::from(recent),
+                            genre_multiplier: f64::from(genre),
+                            ..breakdown
+                        });
+                }
                 result.score = Some(final_score);
                 results.push(result);
             }
@@ -293,7 +1417,12 @@ pub async fn search_titles(
                 let doc = searcher
                     .doc::<TantivyDocument>(addr)
                     .map_err(|err| ApiError::internal(err.into()))?;
-                let mut result = document_to_title_result(&doc, &title_index.fields)?;
+                let mut result = document_to_title_result(
+                    &doc,
+                    &title_index.fields,
+                    localize,
+                    &state.config.scoring,
+                )?;
                 result.sort_value = Some(value);
                 results.push(result);
             }
@@ -303,37 +1432,336 @@ pub async fn search_titles(
                 let doc = searcher
                     .doc::<TantivyDocument>(addr)
                     .map_err(|err| ApiError::internal(err.into()))?;
-                let mut result = document_to_title_result(&doc, &title_index.fields)?;
+                let mut result = document_to_title_result(
+                    &doc,
+                    &title_index.fields,
+                    localize,
+                    &state.config.scoring,
+                )?;
                 result.sort_value = Some(value as f64);
                 results.push(result);
             }
         }
+        CollectedDocs::Optional(docs) => {
+            for (value, addr) in docs {
+                let doc = searcher
+                    .doc::<TantivyDocument>(addr)
+                    .map_err(|err| ApiError::internal(err.into()))?;
+                let mut result = document_to_title_result(
+                    &doc,
+                    &title_index.fields,
+                    localize,
+                    &state.config.scoring,
+                )?;
+                result.sort_value = value.map(|value| value as f64);
+                results.push(result);
+            }
+        }
+        CollectedDocs::Text(docs) => {
+            for (_, addr) in docs {
+                let doc = searcher
+                    .doc::<TantivyDocument>(addr)
+                    .map_err(|err| ApiError::internal(err.into()))?;
+                results.push(document_to_title_result(
+                    &doc,
+                    &title_index.fields,
+                    localize,
+                    &state.config.scoring,
+                )?);
+            }
+        }
     }
 
     if matches!(sort_mode, SortMode::Relevance) {
-        results.sort_by(|a, b| {
+        // Only the re-rank window is reordered; its size does not depend on `offset` or `limit`,
+        // so consecutive pages never repeat or skip a title.
+        let window_len = rerank_window.min(results.len());
+        results[..window_len].sort_by(|a, b| {
             let left = a.score.unwrap_or(f32::NEG_INFINITY);
             let right = b.score.unwrap_or(f32::NEG_INFINITY);
-            match right.partial_cmp(&left).unwrap_or(Ordering::Equal) {
-                Ordering::Equal => a.tconst.cmp(&b.tconst),
-                other => other,
+            right
+                .partial_cmp(&left)
+                .unwrap_or(Ordering::Equal)
+                .then_with(|| compare_tiebreak(tiebreak, a, b))
+        });
+        // Normalize against the re-rank window so later pages stay below 1.0.
+        if params.normalize_scores.unwrap_or(false) {
+            let best = results.first().and_then(|result| result.score);
+            normalize_scores(
+                results[..window_len]
+                    .iter_mut()
+                    .map(|result| &mut result.score),
+            );
+            if let Some(best) = best.filter(|best| best.is_finite() && *best > 0.0) {
+                for score in results[window_len..]
+                    .iter_mut()
+                    .filter_map(|r| r.score.as_mut())
+                {
+                    *score = (*score / best).clamp(0.0, 1.0);
+                }
             }
+        }
+        results = results.into_iter().skip(offset).take(fetch_limit).collect();
+        if params.highlight.unwrap_or(false) && !query_text.is_empty() {
+            highlight_titles(title_index, &searcher, &query_text, &mut results)?;
+        }
+    } else {
+        let by_title = matches!(sort_mode, SortMode::TitleAsc | SortMode::TitleDesc);
+        results.sort_by(|a, b| {
+            let primary = if by_title {
+                title_sort_key(&a.primary_title, strip_articles)
+                    .cmp(&title_sort_key(&b.primary_title, strip_articles))
+            } else {
+                a.sort_value
+                    .partial_cmp(&b.sort_value)
+                    .unwrap_or(Ordering::Equal)
+            };
+            let primary = match &fast_field_order {
+                Order::Desc => primary.reverse(),
+                Order::Asc => primary,
+            };
+            // Titles without a sort value go last whichever way the sort runs.
+            a.sort_value
+                .is_none()
+                .cmp(&b.sort_value.is_none())
+                .then(primary)
+                .then_with(|| compare_tiebreak(tiebreak, a, b))
         });
-        results.truncate(limit);
+        results = results.into_iter().skip(offset).take(fetch_limit).collect();
+    }
+
+    let elapsed = started.elapsed();
+    if is_slow(state, elapsed) {
+        warn!(
+            query = %query_text,
+            filters = ?params,
+            sort = sort_mode.as_str(),
+            hits = results.len(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            effective_query = ?combined_query,
+            "slow title search"
+        );
+    }
+
+    let diagnostics = if total == 0 && params.diagnose.unwrap_or(false) {
+        Some(diagnose_filters(&searcher, &clauses, &filters)?)
+    } else {
+        None
+    };
+    let did_you_mean = if total == 0 && params.suggest.unwrap_or(false) && !text_query.is_empty() {
+        suggest_title_query(title_index, &searcher, &text_query)
+            .map_err(|err| ApiError::internal(err.into()))?
+    } else {
+        None
+    };
+    let query_debug = params
+        .debug
+        .unwrap_or(false)
+        .then(|| format!("{combined_query:?}"));
+    Ok(TitleSearchOutcome {
+        total,
+        limit,
+        results,
+        facet_counts,
+        diagnostics,
+        query_debug,
+        did_you_mean,
+        generation: index_generation(&searcher),
+    })
+}
+
+/// Titles and names returned by `/search` when no `limit` is given.
+const COMBINED_SEARCH_LIMIT: usize = 5;
+
+/// Searches titles and names at once, e.g. for a unified search box.
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "search",
+    params(CombinedSearchParams),
+    responses((status = 200, body = CombinedSearchResponse), (status = 400, body = ErrorBody))
+)]
+#[instrument(skip_all)]
+pub async fn search_all(
+    State(state): State<AppState>,
+    ValidatedQuery(params): ValidatedQuery<CombinedSearchParams>,
+) -> Result<Json<CombinedSearchResponse>, ApiError> {
+    let limit = params
+        .limit
+        .unwrap_or(COMBINED_SEARCH_LIMIT)
+        .clamp(1, state.config.max_limit);
+    // A query too short for title search leaves that section empty rather than failing the
+    // whole request, since names have no minimum length.
+    let search_titles = params.query.trim().chars().count() >= state.config.min_query_len;
+    let title_params = TitleSearchParams {
+        query: Some(params.query.clone()),
+        limit: Some(limit),
+        fuzzy: params.fuzzy,
+        ..TitleSearchParams::default()
+    };
+    let name_params = NameSearchParams {
+        query: params.query,
+        limit: Some(limit),
+        fuzzy: params.fuzzy,
+        ..NameSearchParams::default()
+    };
+
+    // Both searches block on tantivy, so they run concurrently on the blocking pool rather than
+    // stalling runtime workers.
+    let titles = tokio::task::spawn_blocking({
+        let state = state.clone();
+        move || {
+            if search_titles {
+                run_title_search(&state, &title_params).map(|outcome| outcome.results)
+            } else {
+                Ok(Vec::new())
+            }
+        }
+    });
+    let names = tokio::task::spawn_blocking(move || run_name_search(&state, name_params));
+    let (titles, names) = tokio::join!(titles, names);
+    let titles = titles.map_err(|err| ApiError::internal(err.into()))??;
+    let (_, Json(names)) = names.map_err(|err| ApiError::internal(err.into()))??;
+
+    Ok(Json(CombinedSearchResponse {
+        titles,
+        names: names.results,
+    }))
+}
+
+/// Weight of sound-alike matches relative to the text match, so exact spellings rank first.
+const PHONETIC_BOOST: f32 = 0.5;
+
+/// The words of a name query that should sound like the name: `AND`/`OR` and field names are
+/// query syntax, and words excluded with `-` or `NOT` must not be required.
+fn phonetic_query_words(text: &str) -> Vec<&str> {
+    let mut words = Vec::new();
+    let mut tokens = text.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "AND" | "OR" => {}
+            "NOT" => {
+                tokens.next();
+            }
+            _ if token.starts_with('-') => {}
+            _ => words.push(token.split_once(':').map_or(token, |(_, value)| value)),
+        }
+    }
+    words
+}
+
+/// Requires every word of `text` to sound like a word of the name; `None` when no word has
+/// a phonetic code.
+fn build_phonetic_name_query(name_index: &NameIndex, text: &str) -> Option<Box<dyn TantivyQuery>> {
+    let clauses: Vec<(Occur, Box<dyn TantivyQuery>)> = phonetic_query_words(text)
+        .into_iter()
+        .flat_map(phonetic_codes)
+        .map(|code| {
+            let term = Term::from_field_text(name_index.fields.primary_name_phonetic, &code);
+            (
+                Occur::Must,
+                Box::new(TermQuery::new(term, Default::default())) as Box<dyn TantivyQuery>,
+            )
+        })
+        .collect();
+    if clauses.is_empty() {
+        return None;
     }
+    Some(Box::new(BoostQuery::new(
+        Box::new(BooleanQuery::new(clauses)),
+        PHONETIC_BOOST,
+    )))
+}
+
+/// Name hits with their relevance score, if ranked by relevance.
+type NameHits = Vec<(Option<f32>, DocAddress)>;
+
+/// Collects the first `limit` names matching `query` in `sort` order, with scores only when
+/// sorting by relevance. Names without a birth year follow every dated name in either
+/// birth-year direction, best match first.
+fn collect_names(
+    searcher: &Searcher,
+    name_index: &NameIndex,
+    query: &dyn TantivyQuery,
+    sort: NameSortMode,
+    limit: usize,
+) -> tantivy::Result<(usize, NameHits)> {
+    let order = match sort {
+        NameSortMode::Relevance => {
+            let (total, hits) = searcher.search(query, &(Count, TopDocs::with_limit(limit)))?;
+            let hits = hits
+                .into_iter()
+                .map(|(score, addr)| (Some(score), addr))
+                .collect();
+            return Ok((total, hits));
+        }
+        NameSortMode::BirthYearDesc => Order::Desc,
+        NameSortMode::BirthYearAsc => Order::Asc,
+    };
 
-    Ok(Json(TitleSearchResponse { results }))
+    let birth_year = searcher
+        .schema()
+        .get_field_entry(name_index.fields.birth_year)
+        .name()
+        .to_string();
+    let with_birth_year = |occur| {
+        BooleanQuery::new(vec![
+            (Occur::Must, query.box_clone()),
+            (
+                occur,
+                Box::new(ExistsQuery::new(birth_year.clone(), false)) as Box<dyn TantivyQuery>,
+            ),
+        ])
+    };
+
+    let total = searcher.search(query, &Count)?;
+    let mut hits: NameHits = searcher
+        .search(
+            &with_birth_year(Occur::Must),
+            &TopDocs::with_limit(limit).order_by_fast_field::<i64>(birth_year.as_str(), order),
+        )?
+        .into_iter()
+        .map(|(_, addr)| (None, addr))
+        .collect();
+    if hits.len() < limit {
+        let undated = searcher.search(
+            &with_birth_year(Occur::MustNot),
+            &TopDocs::with_limit(limit - hits.len()),
+        )?;
+        hits.extend(undated.into_iter().map(|(_, addr)| (None, addr)));
+    }
+    Ok((total, hits))
 }
 
+/// Searches people.
+#[utoipa::path(
+    get,
+    path = "/names/search",
+    tag = "names",
+    params(NameSearchParams),
+    responses(
+        (status = 200, body = NameSearchResponse, headers(("x-index-generation" = String))),
+        (status = 400, body = ErrorBody),
+    )
+)]
 #[instrument(skip_all)]
 pub async fn search_names(
     State(state): State<AppState>,
-    AxumQuery(params): AxumQuery<NameSearchParams>,
-) -> Result<Json<NameSearchResponse>, ApiError> {
+    ValidatedQuery(params): ValidatedQuery<NameSearchParams>,
+) -> Result<WithHeader<NameSearchResponse>, ApiError> {
+    run_name_search(&state, params)
+}
+
+/// Runs a name search; blocks on tantivy.
+fn run_name_search(
+    state: &AppState,
+    params: NameSearchParams,
+) -> Result<WithHeader<NameSearchResponse>, ApiError> {
     let query_text = params.query.trim();
     let has_filters = params.birth_year_min.is_some()
         || params.birth_year_max.is_some()
-        || !params.primary_profession.is_empty();
+        || !params.primary_profession.is_empty()
+        || params.alive.is_some();
 
     if query_text.is_empty() && !has_filters {
         return Err(ApiError::bad_request(
@@ -341,18 +1769,35 @@ pub async fn search_names(
         ));
     }
 
-    let limit = params.limit.unwrap_or(10).clamp(1, 50);
-    let name_index = &state.name_index;
+    let limit = params.limit.unwrap_or(10).clamp(1, state.config.max_limit);
+    let name_index = &*state.name_index.load_full();
     let searcher = name_index.reader.searcher();
 
     let mut clauses: Vec<(Occur, Box<dyn TantivyQuery>)> = Vec::new();
 
     if !query_text.is_empty() {
-        let parsed_query = name_index
-            .query_parser
-            .parse_query(query_text)
-            .map_err(|err| ApiError::bad_request(format!("invalid query: {}", err)))?;
-        clauses.push((Occur::Must, parsed_query));
+        let (query_ids, text_query) = split_embedded_ids(query_text, "nm");
+        let mut matchers = id_match_clauses(name_index.fields.nconst, &query_ids);
+        if !text_query.is_empty() {
+            let fuzzy = if params.fuzzy.unwrap_or(true) {
+                state.config.fuzzy_distance
+            } else {
+                FuzzyDistance::Fixed(0)
+            };
+            let parsed_query = parse_name_query(
+                name_index,
+                fuzzy,
+                params.default_operator.unwrap_or_default(),
+                &text_query,
+            )?;
+            matchers.push((Occur::Should, parsed_query));
+            if params.phonetic.unwrap_or(false)
+                && let Some(phonetic_query) = build_phonetic_name_query(name_index, &text_query)
+            {
+                matchers.push((Occur::Should, phonetic_query));
+            }
+        }
+        clauses.push((Occur::Must, combine_matchers(matchers)));
     }
 
     if params.birth_year_min.is_some() || params.birth_year_max.is_some() {
@@ -368,14 +1813,42 @@ pub async fn search_names(
         clauses.push((Occur::Must, Box::new(range)));
     }
 
-    for profession in params
+    let professions: Vec<Box<dyn TantivyQuery>> = params
         .primary_profession
         .iter()
         .filter(|value| !value.is_empty())
-    {
-        let term = Term::from_field_text(name_index.fields.primary_profession, profession);
-        let query = TermQuery::new(term, Default::default());
-        clauses.push((Occur::Must, Box::new(query)));
+        .map(|profession| {
+            let term = Term::from_field_text(name_index.fields.primary_profession, profession);
+            Box::new(TermQuery::new(term, Default::default())) as Box<dyn TantivyQuery>
+        })
+        .collect();
+    match params.profession_mode.unwrap_or_default() {
+        FilterMode::All => {
+            clauses.extend(professions.into_iter().map(|query| (Occur::Must, query)));
+        }
+        FilterMode::Any if !professions.is_empty() => {
+            let shoulds = professions
+                .into_iter()
+                .map(|query| (Occur::Should, query))
+                .collect::<Vec<_>>();
+            clauses.push((Occur::Must, Box::new(BooleanQuery::from(shoulds))));
+        }
+        FilterMode::Any => {}
+    }
+
+    // IMDb leaves `deathYear` empty for the living, so being alive means having none.
+    if let Some(alive) = params.alive {
+        let death_year = searcher
+            .schema()
+            .get_field_entry(name_index.fields.death_year)
+            .name()
+            .to_string();
+        let occur = if alive { Occur::MustNot } else { Occur::Must };
+        if alive && clauses.is_empty() {
+            // A purely negative query matches nothing.
+            clauses.push((Occur::Must, Box::new(AllQuery)));
+        }
+        clauses.push((occur, Box::new(ExistsQuery::new(death_year, false))));
     }
 
     let combined_query: Box<dyn TantivyQuery> = match clauses.len() {
@@ -384,9 +1857,15 @@ pub async fn search_names(
         _ => Box::new(BooleanQuery::from(clauses)),
     };
 
-    let hits = searcher
-        .search(&combined_query, &TopDocs::with_limit(limit))
-        .map_err(|err| ApiError::internal(err.into()))?;
+    let started = Instant::now();
+    let (total, hits) = collect_names(
+        &searcher,
+        name_index,
+        &*combined_query,
+        params.sort.unwrap_or_default(),
+        limit,
+    )
+    .map_err(|err| ApiError::internal(err.into()))?;
 
     let mut results = Vec::with_capacity(hits.len());
     for (score, addr) in hits {
@@ -394,46 +1873,505 @@ pub async fn search_names(
             .doc::<TantivyDocument>(addr)
             .map_err(|err| ApiError::internal(err.into()))?;
         let mut result = document_to_name_result(&doc, &name_index.fields)?;
-        result.score = Some(score);
+        result.score = score;
         results.push(result);
     }
+    if params.normalize_scores.unwrap_or(false) {
+        normalize_scores(results.iter_mut().map(|result| &mut result.score));
+    }
+    if params.resolve_known_for.unwrap_or(false) {
+        let title_index = &*state.title_index.load_full();
+        let title_searcher = title_index.reader.searcher();
+        for result in &mut results {
+            resolve_known_for_titles(title_index, &title_searcher, result)?;
+        }
+    }
 
-    Ok(Json(NameSearchResponse { results }))
+    let elapsed = started.elapsed();
+    if is_slow(state, elapsed) {
+        warn!(
+            query = %query_text,
+            filters = ?params,
+            hits = results.len(),
+            elapsed_ms = elapsed.as_millis() as u64,
+            effective_query = ?combined_query,
+            "slow name search"
+        );
+    }
+
+    let query_debug = params
+        .debug
+        .unwrap_or(false)
+        .then(|| format!("{combined_query:?}"));
+    let generation = index_generation(&searcher);
+    Ok((
+        [(INDEX_GENERATION_HEADER, generation)],
+        Json(NameSearchResponse {
+            total,
+            results,
+            query_debug,
+        }),
+    ))
 }
 
+/// Fetches a title by id.
+#[utoipa::path(
+    get,
+    path = "/titles/{tconst}",
+    tag = "titles",
+    params(("tconst" = String, Path, description = "IMDb title id, e.g. `tt0133093`"), TitleLookupParams),
+    responses(
+        (status = 200, body = TitleSearchResult, headers(("etag" = String))),
+        (status = 304, description = "`If-None-Match` matched the current `ETag`"),
+        (status = 404, body = ErrorBody),
+    )
+)]
 #[instrument(skip_all)]
 pub async fn get_title_by_id(
     State(state): State<AppState>,
     Path(tconst): Path<String>,
-) -> Result<Json<TitleSearchResult>, ApiError> {
-    let title_index = &state.title_index;
+    AxumQuery(params): AxumQuery<TitleLookupParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let tconst = normalize_imdb_id(&tconst, "tt");
+    let title_index = &*state.title_index.load_full();
     let searcher = title_index.reader.searcher();
-    let term = Term::from_field_text(title_index.fields.tconst, &tconst);
-    let query = TermQuery::new(term, Default::default());
+    let Some((score, doc)) = find_title_doc(title_index, &searcher, &tconst)? else {
+        return Err(ApiError::not_found("title not found"));
+    };
+
+    let etag = document_etag(&searcher, &tconst, params.localization());
+    if etag_matches(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+    }
+    let mut result = document_to_title_result(
+        &doc,
+        &title_index.fields,
+        params.localization(),
+        &state.config.scoring,
+    )?;
+    result.score = Some(score);
+    Ok(([(ETAG, etag)], Json(result)).into_response())
+}
 
+/// Looks up the document of `tconst` with its match score, or `None` for unknown titles.
+fn find_title_doc(
+    title_index: &TitleIndex,
+    searcher: &Searcher,
+    tconst: &str,
+) -> Result<Option<(Score, TantivyDocument)>, ApiError> {
+    let term = Term::from_field_text(title_index.fields.tconst, tconst);
     let hits = searcher
-        .search(&query, &TopDocs::with_limit(1))
+        .search(
+            &TermQuery::new(term, Default::default()),
+            &TopDocs::with_limit(1),
+        )
         .map_err(|err| ApiError::internal(err.into()))?;
+    let Some((score, addr)) = hits.into_iter().next() else {
+        return Ok(None);
+    };
+    let doc = searcher
+        .doc::<TantivyDocument>(addr)
+        .map_err(|err| ApiError::internal(err.into()))?;
+    Ok(Some((score, doc)))
+}
 
-    if let Some((score, addr)) = hits.into_iter().next() {
+/// Principal cast and crew of a title, in billing order.
+#[utoipa::path(
+    get,
+    path = "/titles/{tconst}/cast",
+    tag = "titles",
+    params(("tconst" = String, Path, description = "IMDb title id")),
+    responses((status = 200, body = TitleCastResponse), (status = 404, body = ErrorBody))
+)]
+#[instrument(skip_all)]
+pub async fn get_title_cast(
+    State(state): State<AppState>,
+    Path(tconst): Path<String>,
+) -> Result<Json<TitleCastResponse>, ApiError> {
+    let tconst = normalize_imdb_id(&tconst, "tt");
+    let title_index = &*state.title_index.load_full();
+    let searcher = title_index.reader.searcher();
+    let Some((_, doc)) = find_title_doc(title_index, &searcher, &tconst)? else {
+        return Err(ApiError::not_found("title not found"));
+    };
+    let cast = document_to_cast(&doc, &title_index.fields);
+    Ok(Json(TitleCastResponse { tconst, cast }))
+}
+
+/// Episodes of a series from title.episode, ordered by season and episode number.
+#[utoipa::path(
+    get,
+    path = "/titles/{tconst}/episodes",
+    tag = "titles",
+    params(
+        ("tconst" = String, Path, description = "IMDb title id of the series"),
+        TitleEpisodesParams
+    ),
+    responses(
+        (status = 200, body = TitleEpisodesResponse),
+        (status = 400, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+#[instrument(skip_all)]
+pub async fn get_title_episodes(
+    State(state): State<AppState>,
+    Path(tconst): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<TitleEpisodesParams>,
+) -> Result<Json<TitleEpisodesResponse>, ApiError> {
+    let tconst = normalize_imdb_id(&tconst, "tt");
+    let limit = params
+        .limit
+        .unwrap_or(state.config.max_limit)
+        .clamp(1, state.config.max_limit);
+    let offset = params.offset.unwrap_or(0);
+    let title_index = &*state.title_index.load_full();
+    let searcher = title_index.reader.searcher();
+    let fields = &title_index.fields;
+
+    let series = TermQuery::new(
+        Term::from_field_text(fields.tconst, &tconst),
+        Default::default(),
+    );
+    let exists = searcher
+        .search(&series, &Count)
+        .map_err(|err| ApiError::internal(err.into()))?;
+    if exists == 0 {
+        return Err(ApiError::not_found("title not found"));
+    }
+
+    let query = TermQuery::new(
+        Term::from_field_text(fields.parent_tconst, &tconst),
+        Default::default(),
+    );
+    let hits = searcher
+        .search(&query, &DocSetCollector)
+        .map_err(|err| ApiError::internal(err.into()))?;
+    let total = hits.len();
+    // Ordered from fast fields, so only the requested page is loaded from the doc store.
+    let mut keyed = episode_order_keys(&searcher, title_index, hits)?;
+    keyed.sort_unstable();
+
+    let mut episodes = Vec::with_capacity(limit.min(total));
+    for (_, addr) in keyed.into_iter().skip(offset).take(limit) {
         let doc = searcher
             .doc::<TantivyDocument>(addr)
             .map_err(|err| ApiError::internal(err.into()))?;
-        let mut result = document_to_title_result(&doc, &title_index.fields)?;
-        result.score = Some(score);
-        return Ok(Json(result));
+        episodes.push(EpisodeSummary {
+            tconst: get_first_text(&doc, fields.tconst).unwrap_or_default(),
+            primary_title: get_first_text(&doc, fields.primary_title).unwrap_or_default(),
+            season_number: get_first_i64(&doc, fields.season_number),
+            episode_number: get_first_i64(&doc, fields.episode_number),
+            average_rating: get_first_f64(&doc, fields.average_rating),
+            num_votes: get_first_i64(&doc, fields.num_votes),
+        });
+    }
+
+    Ok(Json(TitleEpisodesResponse {
+        tconst,
+        total,
+        episodes,
+    }))
+}
+
+/// Episode order: season, then episode number, unnumbered ones last; tconst keeps it stable.
+type EpisodeOrderKey = ((bool, Option<i64>), (bool, Option<i64>), String);
+
+fn episode_order_keys(
+    searcher: &Searcher,
+    title_index: &TitleIndex,
+    hits: HashSet<DocAddress>,
+) -> Result<Vec<(EpisodeOrderKey, DocAddress)>, ApiError> {
+    let name = |field| title_index.schema.get_field_name(field);
+    let number_key = |value: Option<i64>| (value.is_none(), value);
+    let mut columns = HashMap::new();
+    let mut keyed = Vec::with_capacity(hits.len());
+    for addr in hits {
+        let (seasons, episodes, tconsts) = match columns.entry(addr.segment_ord) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let fast_fields = searcher.segment_reader(addr.segment_ord).fast_fields();
+                let open = || -> tantivy::Result<_> {
+                    Ok((
+                        fast_fields.i64(name(title_index.fields.season_number))?,
+                        fast_fields.i64(name(title_index.fields.episode_number))?,
+                        fast_fields.str(name(title_index.fields.tconst))?,
+                    ))
+                };
+                entry.insert(open().map_err(|err| ApiError::internal(err.into()))?)
+            }
+        };
+        let mut tconst = String::new();
+        if let Some(column) = tconsts.as_ref()
+            && let Some(ord) = column.term_ords(addr.doc_id).next()
+        {
+            column
+                .ord_to_str(ord, &mut tconst)
+                .map_err(|err| ApiError::internal(err.into()))?;
+        }
+        let key = (
+            number_key(seasons.first(addr.doc_id)),
+            number_key(episodes.first(addr.doc_id)),
+            tconst,
+        );
+        keyed.push((key, addr));
+    }
+    Ok(keyed)
+}
+
+/// Principals (in billing order) whose names feed the `/titles/{tconst}/similar` query.
+const SIMILAR_PRINCIPALS: usize = 3;
+
+/// Titles sharing genres or leading principals with a title.
+#[utoipa::path(
+    get,
+    path = "/titles/{tconst}/similar",
+    tag = "titles",
+    params(("tconst" = String, Path, description = "IMDb title id"), SimilarTitlesParams),
+    responses((status = 200, body = SimilarTitlesResponse), (status = 404, body = ErrorBody))
+)]
+#[instrument(skip_all)]
+pub async fn similar_titles(
+    State(state): State<AppState>,
+    Path(tconst): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<SimilarTitlesParams>,
+) -> Result<Json<SimilarTitlesResponse>, ApiError> {
+    let tconst = normalize_imdb_id(&tconst, "tt");
+    let limit = params.limit.unwrap_or(10).clamp(1, state.config.max_limit);
+    let title_index = &*state.title_index.load_full();
+    let searcher = title_index.reader.searcher();
+    let Some((_, doc)) = find_title_doc(title_index, &searcher, &tconst)? else {
+        return Err(ApiError::not_found("title not found"));
+    };
+    let source = document_to_title_result(&doc, &title_index.fields, None, &state.config.scoring)?;
+
+    let Some(query) = build_similar_query(
+        title_index,
+        &doc,
+        &source,
+        Term::from_field_text(title_index.fields.tconst, &tconst),
+    ) else {
+        return Ok(Json(SimilarTitlesResponse {
+            tconst,
+            results: Vec::new(),
+        }));
+    };
+
+    let candidates = searcher
+        .search(&query, &TopDocs::with_limit(limit * 5))
+        .map_err(|err| ApiError::internal(err.into()))?;
+    // Titles sharing more of the source's genres rank higher, as with `genres` in search.
+    let source_genres = source.genres.as_deref().unwrap_or_default();
+    let mut results = Vec::with_capacity(candidates.len());
+    for (base_score, addr) in candidates {
+        let doc = searcher
+            .doc::<TantivyDocument>(addr)
+            .map_err(|err| ApiError::internal(err.into()))?;
+        let mut result =
+            document_to_title_result(&doc, &title_index.fields, None, &state.config.scoring)?;
+        let overlap =
+            genre_match_multiplier(&result, source_genres, state.config.genre_match_boost);
+        result.score = Some(state.scorer.score(base_score, &result, None) * overlap);
+        results.push(result);
     }
+    results.sort_by(|a, b| {
+        let left = a.score.unwrap_or(f32::NEG_INFINITY);
+        let right = b.score.unwrap_or(f32::NEG_INFINITY);
+        right
+            .partial_cmp(&left)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| a.tconst.cmp(&b.tconst))
+    });
+    results.truncate(limit);
+
+    Ok(Json(SimilarTitlesResponse { tconst, results }))
+}
+
+/// Matches titles sharing a genre or a top-billed principal with `source`. Without genres it
+/// falls back to titles of the same type from the same decade. Adult titles only match adult
+/// sources, and the source itself is always excluded.
+fn build_similar_query(
+    title_index: &TitleIndex,
+    doc: &TantivyDocument,
+    source: &TitleSearchResult,
+    tconst_term: Term,
+) -> Option<BooleanQuery> {
+    let fields = &title_index.fields;
+    let mut clauses: Vec<(Occur, Box<dyn TantivyQuery>)> = Vec::new();
 
-    Err(ApiError::not_found("title not found"))
+    let genres = source.genres.as_deref().unwrap_or_default();
+    if genres.is_empty() {
+        let title_type = source
+            .title_type
+            .as_deref()
+            .filter(|value| !value.is_empty())?;
+        let term = Term::from_field_text(fields.title_type, title_type);
+        clauses.push((
+            Occur::Must,
+            Box::new(TermQuery::new(term, Default::default())),
+        ));
+        if let Some(year) = source.start_year {
+            let decade = year - year.rem_euclid(10);
+            let range = RangeQuery::new(
+                Bound::Included(Term::from_field_i64(fields.start_year, decade)),
+                Bound::Excluded(Term::from_field_i64(fields.start_year, decade + 10)),
+            );
+            clauses.push((Occur::Must, Box::new(range)));
+        }
+    } else {
+        let mut shoulds: Vec<(Occur, Box<dyn TantivyQuery>)> = genres
+            .iter()
+            .filter_map(|genre| genre_query(title_index, genre))
+            .map(|query| (Occur::Should, query))
+            .collect();
+        if let Some(mut analyzer) = title_index.tokenizers.get("default") {
+            shoulds.extend(
+                document_to_cast(doc, fields)
+                    .iter()
+                    .take(SIMILAR_PRINCIPALS)
+                    .filter_map(|member| {
+                        phrase_or_term_query(
+                            &mut analyzer,
+                            fields.search_titles,
+                            &member.primary_name,
+                        )
+                    })
+                    .map(|query| (Occur::Should, query)),
+            );
+        }
+        clauses.push((Occur::Must, Box::new(BooleanQuery::from(shoulds))));
+    }
+
+    if get_first_i64(doc, fields.is_adult) != Some(1) {
+        let term = Term::from_field_i64(fields.is_adult, 0);
+        clauses.push((
+            Occur::Must,
+            Box::new(TermQuery::new(term, Default::default())),
+        ));
+    }
+    clauses.push((
+        Occur::MustNot,
+        Box::new(TermQuery::new(tconst_term, Default::default())),
+    ));
+    Some(BooleanQuery::new(clauses))
+}
+
+/// Header echoing the seed used by `/titles/random` so a pick can be reproduced.
+const RANDOM_SEED_HEADER: &str = "x-random-seed";
+
+/// A random title.
+#[utoipa::path(
+    get,
+    path = "/titles/random",
+    tag = "titles",
+    params(RandomTitleParams),
+    responses((status = 200, body = TitleSearchResult, headers(("x-random-seed" = u64))))
+)]
+#[instrument(skip_all)]
+pub async fn random_title(
+    State(state): State<AppState>,
+    AxumQuery(params): AxumQuery<RandomTitleParams>,
+) -> Result<WithHeader<TitleSearchResult>, ApiError> {
+    let title_index = &*state.title_index.load_full();
+    let searcher = title_index.reader.searcher();
+    let total = searcher.num_docs();
+    if total == 0 {
+        return Err(ApiError::not_found("title index is empty"));
+    }
+
+    let seed = params.seed.unwrap_or_else(|| rand::thread_rng().r#gen());
+    let mut offset = SmallRng::seed_from_u64(seed).gen_range(0..total);
+
+    for (segment_ord, segment_reader) in searcher.segment_readers().iter().enumerate() {
+        let alive = u64::from(segment_reader.num_docs());
+        if offset >= alive {
+            offset -= alive;
+            continue;
+        }
+        // Without deletes the live documents are exactly `0..alive`, so no scan is needed.
+        let doc_id = if segment_reader.has_deletes() {
+            let Some(doc_id) = segment_reader.doc_ids_alive().nth(offset as usize) else {
+                break;
+            };
+            doc_id
+        } else {
+            offset as DocId
+        };
+        let doc = searcher
+            .doc::<TantivyDocument>(DocAddress::new(segment_ord as u32, doc_id))
+            .map_err(|err| ApiError::internal(err.into()))?;
+        let result =
+            document_to_title_result(&doc, &title_index.fields, None, &state.config.scoring)?;
+        return Ok(([(RANDOM_SEED_HEADER, seed.to_string())], Json(result)));
+    }
+
+    Err(ApiError::internal(anyhow::anyhow!(
+        "random offset fell outside the live documents"
+    )))
+}
+
+/// Known-for titles resolved per person; IMDb lists at most four.
+const KNOWN_FOR_LIMIT: usize = 4;
+
+/// Fills `result.known_for` from the title index in IMDb's order, skipping titles that are not
+/// indexed (e.g. filtered out at build time).
+fn resolve_known_for_titles(
+    title_index: &TitleIndex,
+    searcher: &Searcher,
+    result: &mut NameSearchResult,
+) -> Result<(), ApiError> {
+    let fields = &title_index.fields;
+    let mut known_for = Vec::new();
+    for tconst in result
+        .known_for_titles
+        .iter()
+        .flatten()
+        .take(KNOWN_FOR_LIMIT)
+    {
+        let Some((_, doc)) = find_title_doc(title_index, searcher, tconst)? else {
+            continue;
+        };
+        let Some(primary_title) = get_first_text(&doc, fields.primary_title) else {
+            continue;
+        };
+        known_for.push(KnownForTitle {
+            tconst: tconst.clone(),
+            primary_title,
+            start_year: get_first_i64(&doc, fields.start_year),
+        });
+    }
+    result.known_for = Some(known_for);
+    Ok(())
 }
 
+/// Fetches a person by id.
+#[utoipa::path(
+    get,
+    path = "/names/{nconst}",
+    tag = "names",
+    params(("nconst" = String, Path, description = "IMDb name id, e.g. `nm0000206`"), NameLookupParams),
+    responses(
+        (status = 200, body = NameSearchResult, headers(("etag" = String))),
+        (status = 304, description = "`If-None-Match` matched the current `ETag`"),
+        (status = 404, body = ErrorBody),
+    )
+)]
 #[instrument(skip_all)]
 pub async fn get_name_by_id(
     State(state): State<AppState>,
     Path(nconst): Path<String>,
-) -> Result<Json<NameSearchResult>, ApiError> {
-    let name_index = &state.name_index;
+    AxumQuery(params): AxumQuery<NameLookupParams>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let nconst = normalize_imdb_id(&nconst, "nm");
+    let name_index = &*state.name_index.load_full();
     let searcher = name_index.reader.searcher();
+    let title_index = &*state.title_index.load_full();
+    let title_searcher = title_index.reader.searcher();
+    let resolve = params.resolve_known_for.unwrap_or(false);
+    // Resolved titles come from the title index, which is rebuilt on its own schedule.
+    let variant = resolve.then(|| format!("known_for-{}", index_generation(&title_searcher)));
     let term = Term::from_field_text(name_index.fields.nconst, &nconst);
     let query = TermQuery::new(term, Default::default());
 
@@ -442,13 +2380,265 @@ pub async fn get_name_by_id(
         .map_err(|err| ApiError::internal(err.into()))?;
 
     if let Some((score, addr)) = hits.into_iter().next() {
+        let etag = document_etag(&searcher, &nconst, variant.as_deref());
+        if etag_matches(&headers, &etag) {
+            return Ok((StatusCode::NOT_MODIFIED, [(ETAG, etag)]).into_response());
+        }
         let doc = searcher
             .doc::<TantivyDocument>(addr)
             .map_err(|err| ApiError::internal(err.into()))?;
         let mut result = document_to_name_result(&doc, &name_index.fields)?;
         result.score = Some(score);
-        return Ok(Json(result));
+        if resolve {
+            resolve_known_for_titles(title_index, &title_searcher, &mut result)?;
+        }
+        return Ok(([(ETAG, etag)], Json(result)).into_response());
     }
 
     Err(ApiError::not_found("name not found"))
 }
+
+/// Genres most often shared with a genre.
+#[utoipa::path(
+    get,
+    path = "/genres/{genre}/related",
+    tag = "titles",
+    params(("genre" = String, Path, description = "Genre name, case-insensitive")),
+    responses((status = 200, body = RelatedGenresResponse), (status = 404, body = ErrorBody))
+)]
+#[instrument(skip_all)]
+pub async fn related_genres(
+    State(state): State<AppState>,
+    Path(genre): Path<String>,
+) -> Result<Json<RelatedGenresResponse>, ApiError> {
+    let Some(genre) = canonical_genre(&genre) else {
+        return Err(ApiError::not_found(format!("unknown genre `{genre}`")));
+    };
+
+    let title_index = &*state.title_index.load_full();
+    let searcher = title_index.reader.searcher();
+    let generation = index_generation(&searcher);
+    let cached = state
+        .related_genres
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(genre)
+        .filter(|(computed_for, _)| *computed_for == generation)
+        .map(|(_, response)| Arc::clone(response));
+    let response = match cached {
+        Some(response) => response,
+        None => {
+            let response = Arc::new(
+                genres::related_genres(title_index, &searcher, genre)
+                    .map_err(|err| ApiError::internal(err.into()))?,
+            );
+            state
+                .related_genres
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .insert(genre, (generation, Arc::clone(&response)));
+            response
+        }
+    };
+
+    Ok(Json(RelatedGenresResponse::clone(&response)))
+}
+
+fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<(), ApiError> {
+    let Some(expected) = state.config.admin_token.as_deref() else {
+        return Err(ApiError::not_found("admin endpoints are disabled"));
+    };
+    let provided = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    if !provided.is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes()))
+    {
+        return Err(ApiError::unauthorized("missing or invalid admin token"));
+    }
+    Ok(())
+}
+
+/// Starts a background job that prepares the datasets (honouring `IMDB_REFRESH`), rebuilds both
+/// indexes and swaps them in. Answers `202` with the job to poll, or `409` while one is running.
+#[utoipa::path(
+    post,
+    path = "/admin/reindex",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses(
+        (status = 202, body = ReindexJob),
+        (status = 401, body = ErrorBody),
+        (status = 409, body = ErrorBody),
+    )
+)]
+#[instrument(skip_all)]
+pub async fn admin_reindex(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<(StatusCode, Json<ReindexJob>), ApiError> {
+    require_admin(&state, &headers)?;
+
+    let job = {
+        let mut jobs = state.reindex_jobs.lock().expect("reindex jobs poisoned");
+        if let Some(running) = jobs.running() {
+            return Err(ApiError::conflict(format!(
+                "reindex job {} is still running",
+                running.job_id
+            )));
+        }
+        jobs.start()
+    };
+
+    let job_id = job.job_id;
+    info!(job_id, "starting reindex");
+    tokio::spawn(async move {
+        // Awaiting the rebuild as its own task turns a panic into an error, so the job is
+        // marked failed instead of staying `running` forever.
+        let task_state = state.clone();
+        let result = match tokio::spawn(async move { run_reindex(&task_state).await }).await {
+            Ok(result) => result,
+            Err(err) => Err(anyhow::anyhow!("reindex task failed: {err}")),
+        };
+        match &result {
+            Ok(()) => info!(job_id, "reindex finished"),
+            Err(err) => error!(job_id, error = %format!("{err:#}"), "reindex failed"),
+        }
+        state
+            .reindex_jobs
+            .lock()
+            .expect("reindex jobs poisoned")
+            .finish(job_id, &result);
+    });
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+async fn run_reindex(state: &AppState) -> anyhow::Result<()> {
+    let config = &state.config;
+    let datasets =
+        datasets::prepare_datasets(config, &datasets::configured_datasets(config)).await?;
+    let rebuilt = indexer::rebuild_indexes(config, &datasets).await?;
+    state.swap_indexes(rebuilt.indexes);
+    indexer::remove_retired_indexes(&rebuilt.retired).await
+}
+
+/// Reports a reindex job.
+#[utoipa::path(
+    get,
+    path = "/admin/reindex/{job_id}",
+    tag = "admin",
+    security(("admin_token" = [])),
+    params(("job_id" = u64, Path)),
+    responses(
+        (status = 200, body = ReindexJob),
+        (status = 401, body = ErrorBody),
+        (status = 404, body = ErrorBody),
+    )
+)]
+#[instrument(skip_all)]
+pub async fn admin_reindex_status(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(job_id): Path<u64>,
+) -> Result<Json<ReindexJob>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    state
+        .reindex_jobs
+        .lock()
+        .expect("reindex jobs poisoned")
+        .get(job_id)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found("unknown reindex job"))
+}
+
+fn collect_segments(searcher: &Searcher) -> Result<IndexSegments, ApiError> {
+    let mut segments = Vec::with_capacity(searcher.segment_readers().len());
+    for segment_reader in searcher.segment_readers() {
+        let size_bytes = segment_reader
+            .space_usage()
+            .map_err(|err| ApiError::internal(err.into()))?
+            .total()
+            .get_bytes();
+        segments.push(SegmentInfo {
+            segment_id: segment_reader.segment_id().uuid_string(),
+            num_docs: segment_reader.num_docs(),
+            num_deleted_docs: segment_reader.num_deleted_docs(),
+            max_doc: segment_reader.max_doc(),
+            size_bytes,
+        });
+    }
+
+    Ok(IndexSegments {
+        segment_count: segments.len(),
+        num_docs: segments.iter().map(|s| u64::from(s.num_docs)).sum(),
+        num_deleted_docs: segments.iter().map(|s| u64::from(s.num_deleted_docs)).sum(),
+        size_bytes: segments.iter().map(|s| s.size_bytes).sum(),
+        segments,
+    })
+}
+
+/// Document counts and last build summary of both indexes, plus search cache usage.
+#[utoipa::path(get, path = "/stats", tag = "meta", responses((status = 200, body = StatsResponse)))]
+#[instrument(skip_all)]
+pub async fn stats(State(state): State<AppState>) -> Result<Json<StatsResponse>, ApiError> {
+    let titles = collect_stats(&state.title_index.load().reader.searcher())?;
+    let names = collect_stats(&state.name_index.load().reader.searcher())?;
+    let search_cache = state
+        .search_cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .stats();
+    Ok(Json(StatsResponse {
+        titles,
+        names,
+        search_cache,
+    }))
+}
+
+/// Build metadata of both indexes, derived from the summary `/stats` reports, as written to
+/// each index's `build_meta.json`.
+#[utoipa::path(get, path = "/meta", tag = "meta", responses((status = 200, body = MetaResponse)))]
+#[instrument(skip_all)]
+pub async fn build_meta(State(state): State<AppState>) -> Result<Json<MetaResponse>, ApiError> {
+    let load = |searcher: Searcher| {
+        BuildSummary::load(searcher.index())
+            .map(|summary| summary.as_ref().map(BuildMeta::from))
+            .map_err(ApiError::internal)
+    };
+    Ok(Json(MetaResponse {
+        titles: load(state.title_index.load().reader.searcher())?,
+        names: load(state.name_index.load().reader.searcher())?,
+    }))
+}
+
+fn collect_stats(searcher: &Searcher) -> Result<IndexStats, ApiError> {
+    Ok(IndexStats {
+        num_docs: searcher.num_docs(),
+        last_build: BuildSummary::load(searcher.index()).map_err(ApiError::internal)?,
+    })
+}
+
+/// Per-segment statistics of both indexes.
+#[utoipa::path(
+    get,
+    path = "/admin/segments",
+    tag = "admin",
+    security(("admin_token" = [])),
+    responses((status = 200, body = SegmentsResponse), (status = 401, body = ErrorBody))
+)]
+#[instrument(skip_all)]
+pub async fn admin_segments(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> Result<Json<SegmentsResponse>, ApiError> {
+    require_admin(&state, &headers)?;
+
+    let titles = collect_segments(&state.title_index.load().reader.searcher())?;
+    let names = collect_segments(&state.name_index.load().reader.searcher())?;
+
+    Ok(Json(SegmentsResponse { titles, names }))
+}
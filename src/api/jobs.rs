@@ -0,0 +1,78 @@
+use std::collections::VecDeque;
+
+use chrono::{SecondsFormat, Utc};
+
+use super::types::{JobStatus, ReindexJob};
+
+/// Finished jobs kept for status polling; older ones are forgotten.
+const FINISHED_JOBS_KEPT: usize = 16;
+
+/// Reindex jobs started through `/admin/reindex`, oldest first. At most one runs at a time.
+#[derive(Debug, Default)]
+pub struct ReindexJobs {
+    next_id: u64,
+    jobs: VecDeque<ReindexJob>,
+}
+
+impl ReindexJobs {
+    pub fn running(&self) -> Option<&ReindexJob> {
+        self.jobs
+            .iter()
+            .find(|job| job.status == JobStatus::Running)
+    }
+
+    pub fn get(&self, job_id: u64) -> Option<&ReindexJob> {
+        self.jobs.iter().find(|job| job.job_id == job_id)
+    }
+
+    /// Records a new running job and returns it.
+    pub fn start(&mut self) -> ReindexJob {
+        self.next_id += 1;
+        let job = ReindexJob {
+            job_id: self.next_id,
+            status: JobStatus::Running,
+            started_at: now(),
+            finished_at: None,
+            error: None,
+        };
+        while self.jobs.len() >= FINISHED_JOBS_KEPT {
+            self.jobs.pop_front();
+        }
+        self.jobs.push_back(job.clone());
+        job
+    }
+
+    pub fn finish(&mut self, job_id: u64, result: &anyhow::Result<()>) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.job_id == job_id) {
+            job.finished_at = Some(now());
+            match result {
+                Ok(()) => job.status = JobStatus::Succeeded,
+                Err(err) => {
+                    job.status = JobStatus::Failed;
+                    job.error = Some(format!("{err:#}"));
+                }
+            }
+        }
+    }
+}
+
+fn now() -> String {
+    Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn start_keeps_at_most_the_configured_number_of_jobs() {
+        let mut jobs = ReindexJobs::default();
+        for _ in 0..FINISHED_JOBS_KEPT + 3 {
+            let job = jobs.start();
+            jobs.finish(job.job_id, &Ok(()));
+        }
+        assert_eq!(jobs.jobs.len(), FINISHED_JOBS_KEPT);
+        assert!(jobs.get(3).is_none());
+        assert!(jobs.get(4).is_some());
+    }
+}
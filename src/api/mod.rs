@@ -1,8 +1,20 @@
+mod cache;
+mod filters;
+mod genres;
 mod handlers;
+mod jobs;
+pub mod openapi;
 mod scoring;
 mod state;
+mod suggest;
+mod title_types;
 pub mod types;
 mod utils;
+mod validation;
 
-pub use scoring::compute_title_relevance_score;
+pub use scoring::{
+    ColdStartTier, DefaultScorer, RECENT_WINDOW_BOOST, ScoringConfig, TitleScorer,
+    compute_title_relevance_score, genre_match_multiplier, normalize_scores,
+    recent_window_multiplier, score_breakdown, score_title, title_popularity,
+};
 pub use state::{AppState, router};
@@ -0,0 +1,301 @@
+use axum::Json;
+use axum::response::Html;
+use utoipa::openapi::schema::{
+    ArrayBuilder, KnownFormat, ObjectBuilder, OneOfBuilder, Schema, SchemaFormat, SchemaType, Type,
+};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{Ref, RefOr};
+use utoipa::{Modify, OpenApi, PartialSchema, ToSchema};
+
+use crate::indexer::{BuildMeta, BuildSettings, BuildSummary, PopularityTier};
+
+use super::handlers;
+use super::types::{FilterMode, Format, GroupBy, MatchMode, NameSortMode, Operator, SortMode};
+
+/// OpenAPI description of the HTTP API, served at `/openapi.json`.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "imdb-rs", description = "Search IMDb titles and people."),
+    paths(
+        handlers::healthz,
+        handlers::list_filters,
+        handlers::list_title_types,
+        handlers::stats,
+        handlers::build_meta,
+        handlers::search_all,
+        handlers::search_titles,
+        handlers::random_title,
+        handlers::get_title_by_id,
+        handlers::get_title_cast,
+        handlers::get_title_episodes,
+        handlers::similar_titles,
+        handlers::related_genres,
+        handlers::search_names,
+        handlers::get_name_by_id,
+        handlers::admin_segments,
+        handlers::admin_reindex,
+        handlers::admin_reindex_status,
+    ),
+    // Enums used only by query parameters are not collected from the paths.
+    components(schemas(
+        SortMode,
+        FilterMode,
+        MatchMode,
+        NameSortMode,
+        Operator,
+        PopularityTier,
+        GroupBy,
+        Format
+    )),
+    modifiers(&AdminToken)
+)]
+pub struct ApiDoc;
+
+/// Schema of an enum declared with `named_enum!`, serialized as one of `names`.
+fn named_enum_schema(description: &str, names: &[&'static str]) -> RefOr<Schema> {
+    ObjectBuilder::new()
+        .schema_type(Type::String)
+        .description(Some(description))
+        .enum_values(Some(names.iter().copied()))
+        .into()
+}
+
+impl PartialSchema for SortMode {
+    fn schema() -> RefOr<Schema> {
+        named_enum_schema("Order of title search results.", &SortMode::NAMES)
+    }
+}
+
+impl ToSchema for SortMode {}
+
+impl PartialSchema for PopularityTier {
+    fn schema() -> RefOr<Schema> {
+        named_enum_schema(
+            "Vote-count bucket of a rated title: blockbuster (1,000,000+ votes), popular \
+             (100,000+), niche (10,000+) or indie.",
+            &PopularityTier::NAMES,
+        )
+    }
+}
+
+impl ToSchema for PopularityTier {}
+
+// The indexer's build records are served as they are stored; their schemas are described here
+// so the data layer does not depend on utoipa.
+
+/// Unsigned integer property stored as `format`.
+fn unsigned(format: KnownFormat, description: Option<&str>) -> ObjectBuilder {
+    ObjectBuilder::new()
+        .schema_type(Type::Integer)
+        .format(Some(SchemaFormat::KnownFormat(format)))
+        .minimum(Some(0))
+        .description(description)
+}
+
+/// String property, also allowing `null` when `nullable` is set.
+fn string(description: &str, nullable: bool) -> ObjectBuilder {
+    let schema_type = if nullable {
+        SchemaType::from_iter([Type::String, Type::Null])
+    } else {
+        Type::String.into()
+    };
+    ObjectBuilder::new()
+        .schema_type(schema_type)
+        .description(Some(description))
+}
+
+/// Boolean property that may be `null`.
+fn optional_bool(description: &str) -> ObjectBuilder {
+    ObjectBuilder::new()
+        .schema_type(SchemaType::from_iter([Type::Boolean, Type::Null]))
+        .description(Some(description))
+}
+
+/// Object property keyed by dataset file name with string values.
+fn per_file(description: &str) -> ObjectBuilder {
+    ObjectBuilder::new()
+        .schema_type(Type::Object)
+        .property_names(Some(ObjectBuilder::new().schema_type(Type::String).build()))
+        .additional_properties(Some(ObjectBuilder::new().schema_type(Type::String)))
+        .description(Some(description))
+}
+
+const SOURCES_DESCRIPTION: &str = "Last-modified time (RFC 3339) of each dataset file the index \
+                                   was built from, keyed by file name.";
+const BUILT_AT_DESCRIPTION: &str = "When the build finished, RFC 3339.";
+const UPDATED_AT_DESCRIPTION: &str =
+    "When titles were last re-indexed in place by the `update` subcommand, RFC 3339.";
+
+impl PartialSchema for BuildSummary {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .description(Some(
+                "Outcome and provenance of the last build of an index, kept with the index so \
+                 merges carry it along.",
+            ))
+            .property(
+                "schema_version",
+                unsigned(
+                    KnownFormat::Int32,
+                    Some(
+                        "Schema version the index was built with; 0 for indexes built before it \
+                         was recorded.",
+                    ),
+                ),
+            )
+            .property(
+                "processed",
+                unsigned(KnownFormat::Int64, Some("Rows written to the index.")),
+            )
+            .required("processed")
+            .property(
+                "skipped",
+                unsigned(KnownFormat::Int64, Some("Rows dropped as malformed.")),
+            )
+            .required("skipped")
+            .property("elapsed_ms", unsigned(KnownFormat::Int64, None))
+            .required("elapsed_ms")
+            .property("sources", per_file(SOURCES_DESCRIPTION))
+            .property(
+                "fingerprints",
+                per_file(
+                    "Fingerprint of the archive behind each dataset file, keyed by file name. An \
+                     index is rebuilt on startup once a dataset it was built from has a \
+                     different fingerprint on disk.",
+                ),
+            )
+            .property("built_at", string(BUILT_AT_DESCRIPTION, false))
+            .property("updated_at", string(UPDATED_AT_DESCRIPTION, true))
+            .property(
+                "settings",
+                OneOfBuilder::new()
+                    .item(ObjectBuilder::new().schema_type(Type::Null))
+                    .item(Ref::from_schema_name(BuildSettings::name()))
+                    .description(Some(
+                        "Options the index was built with; `null` for indexes built before they \
+                         were recorded.",
+                    )),
+            )
+            .into()
+    }
+}
+
+impl ToSchema for BuildSummary {
+    fn schemas(schemas: &mut Vec<(String, RefOr<Schema>)>) {
+        schemas.push((BuildSettings::name().into(), BuildSettings::schema()));
+    }
+}
+
+impl PartialSchema for BuildSettings {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .description(Some(
+                "Index-time options that change what an index contains. An index whose recorded \
+                 settings differ from the configured ones is rebuilt on startup.",
+            ))
+            .property(
+                "infix_ngram",
+                ArrayBuilder::new()
+                    .schema_type(SchemaType::from_iter([Type::Array, Type::Null]))
+                    .items(
+                        ObjectBuilder::new()
+                            .schema_type(Type::Integer)
+                            .minimum(Some(0)),
+                    )
+                    .description(Some(
+                        "`[min, max]` gram sizes of the infix n-gram field; `null` when it is \
+                         disabled.",
+                    )),
+            )
+            .property(
+                "docstore_compression",
+                string(
+                    "Docstore compression, as accepted by `IMDB_DOCSTORE_COMPRESSION`.",
+                    false,
+                ),
+            )
+            .required("docstore_compression")
+            .property(
+                "principals",
+                optional_bool("Whether title.principals was loaded; `null` for the name index."),
+            )
+            .property(
+                "episodes",
+                optional_bool("Whether title.episode was loaded; `null` for the name index."),
+            )
+            .property(
+                "overview_column",
+                string("title.basics column indexed as the overview, if any.", true),
+            )
+            .into()
+    }
+}
+
+impl ToSchema for BuildSettings {}
+
+impl PartialSchema for BuildMeta {
+    fn schema() -> RefOr<Schema> {
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .description(Some(
+                "Provenance of an index, as written to `build_meta.json` in the index directory.",
+            ))
+            .property("schema_version", unsigned(KnownFormat::Int32, None))
+            .required("schema_version")
+            .property(
+                "documents",
+                unsigned(KnownFormat::Int64, Some("Documents written by the build.")),
+            )
+            .required("documents")
+            .property("sources", per_file(SOURCES_DESCRIPTION))
+            .required("sources")
+            .property("built_at", string(BUILT_AT_DESCRIPTION, false))
+            .required("built_at")
+            .property("updated_at", string(UPDATED_AT_DESCRIPTION, true))
+            .into()
+    }
+}
+
+impl ToSchema for BuildMeta {}
+
+/// Declares the bearer token checked by the `/admin/*` endpoints.
+struct AdminToken;
+
+impl Modify for AdminToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "admin_token",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI for `/openapi.json`, loaded from a CDN so the binary carries no assets. The
+/// version is pinned so the page does not change under us when a new release is published.
+pub async fn docs() -> Html<&'static str> {
+    Html(
+        r##"<!doctype html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>imdb-rs API</title>
+  <link rel="stylesheet" href="https://unpkg.com/swagger-ui-dist@5.17.14/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="https://unpkg.com/swagger-ui-dist@5.17.14/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({ url: "/openapi.json", dom_id: "#swagger-ui" });
+  </script>
+</body>
+</html>
+"##,
+    )
+}
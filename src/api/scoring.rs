@@ -1,13 +1,191 @@
 use chrono::{Datelike, Utc};
 use tantivy::Score;
 
-use crate::api::types::TitleSearchResult;
+use crate::api::types::{ScoreBreakdown, TitleSearchResult};
 
+/// Multiplier applied to titles inside the `boost_recent_years` freshness window.
+pub const RECENT_WINDOW_BOOST: f64 = 1.5;
+
+/// Ranks title search candidates in relevance mode.
+///
+/// `base` is the raw tantivy score and `query` the lowercased query text, if any. Per-request
+/// adjustments (the `boost_recent_years` window and genre match boost) are applied on top of
+/// the returned score by the handler.
+pub trait TitleScorer: Send + Sync {
+    fn score(&self, base: Score, result: &TitleSearchResult, query: Option<&str>) -> f32;
+
+    /// Components behind [`TitleScorer::score`] for `debug_score=true`; scorers without a
+    /// breakdown return `None`.
+    fn breakdown(
+        &self,
+        _base: Score,
+        _result: &TitleSearchResult,
+        _query: Option<&str>,
+    ) -> Option<ScoreBreakdown> {
+        None
+    }
+}
+
+/// Corpus-dependent constants of the default relevance formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoringConfig {
+    /// Mean rating titles are shrunk towards (`C` in the Bayesian weighted rating).
+    pub global_avg: f64,
+    /// Votes needed before a title's own rating outweighs `global_avg` (`m`).
+    pub m_prior: f64,
+    /// Vote count treated as maximal popularity when normalizing.
+    pub vmax: f64,
+    /// Multipliers for titles with few votes, checked in order; the first tier whose
+    /// `below_votes` exceeds the title's votes applies.
+    pub cold_start: Vec<ColdStartTier>,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            global_avg: 6.7,
+            m_prior: 12_000.0,
+            vmax: 2_000_000.0,
+            cold_start: vec![
+                ColdStartTier::new(50.0, 0.20),
+                ColdStartTier::new(500.0, 0.50),
+                ColdStartTier::new(2_000.0, 0.80),
+            ],
+        }
+    }
+}
+
+impl ScoringConfig {
+    /// Bayesian weighted rating: `(v/(v+m))*R + (m/(v+m))*C`.
+    pub fn weighted_rating(&self, rating: f64, votes: f64) -> f64 {
+        if votes > 0.0 {
+            (votes / (votes + self.m_prior)) * rating
+                + (self.m_prior / (votes + self.m_prior)) * self.global_avg
+        } else {
+            self.global_avg
+        }
+    }
+
+    fn cold_start_multiplier(&self, votes: f64) -> f64 {
+        self.cold_start
+            .iter()
+            .find(|tier| votes < tier.below_votes)
+            .map_or(1.0, |tier| tier.multiplier)
+    }
+}
+
+/// Dampens titles with fewer than `below_votes` votes by `multiplier`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ColdStartTier {
+    pub below_votes: f64,
+    pub multiplier: f64,
+}
+
+impl ColdStartTier {
+    pub fn new(below_votes: f64, multiplier: f64) -> Self {
+        Self {
+            below_votes,
+            multiplier,
+        }
+    }
+}
+
+/// Parses `votes:multiplier`, e.g. `50:0.2`.
+impl std::str::FromStr for ColdStartTier {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (votes, multiplier) = value
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("expected `votes:multiplier`, got `{value}`"))?;
+        Ok(Self::new(votes.trim().parse()?, multiplier.trim().parse()?))
+    }
+}
+
+/// The built-in scorer: [`score_title`] without a freshness window.
+#[derive(Debug, Clone, Default)]
+pub struct DefaultScorer {
+    pub scoring: ScoringConfig,
+}
+
+impl DefaultScorer {
+    pub fn new(scoring: ScoringConfig) -> Self {
+        Self { scoring }
+    }
+}
+
+impl TitleScorer for DefaultScorer {
+    fn score(&self, base: Score, result: &TitleSearchResult, query: Option<&str>) -> f32 {
+        score_title(&self.scoring, base, result, query, None)
+    }
+
+    fn breakdown(
+        &self,
+        base: Score,
+        result: &TitleSearchResult,
+        query: Option<&str>,
+    ) -> Option<ScoreBreakdown> {
+        Some(score_breakdown(&self.scoring, base, result, query))
+    }
+}
+
+/// [`RECENT_WINDOW_BOOST`] for titles that started within the last `boost_recent_years` years
+/// (counting the current year), `1.0` otherwise.
+pub fn recent_window_multiplier(
+    result: &TitleSearchResult,
+    boost_recent_years: Option<u32>,
+) -> f32 {
+    let current_year = Utc::now().year();
+    match (boost_recent_years, result.start_year) {
+        (Some(years), Some(start_year))
+            if years > 0 && start_year > i64::from(current_year) - i64::from(years) =>
+        {
+            RECENT_WINDOW_BOOST as f32
+        }
+        _ => 1.0,
+    }
+}
+
+/// Scores a title with the default [`ScoringConfig`]; `boost_recent_years` multiplies titles
+/// that started within that many years (counting the current year) by [`RECENT_WINDOW_BOOST`],
+/// on top of the recency tilt.
 pub fn compute_title_relevance_score(
     base_score: Score,
     result: &TitleSearchResult,
     query_lower: Option<&str>,
+    boost_recent_years: Option<u32>,
 ) -> f32 {
+    score_title(
+        &ScoringConfig::default(),
+        base_score,
+        result,
+        query_lower,
+        boost_recent_years,
+    )
+}
+
+/// [`compute_title_relevance_score`] with the corpus constants taken from `scoring`.
+pub fn score_title(
+    scoring: &ScoringConfig,
+    base_score: Score,
+    result: &TitleSearchResult,
+    query_lower: Option<&str>,
+    boost_recent_years: Option<u32>,
+) -> f32 {
+    let mut breakdown = score_breakdown(scoring, base_score, result, query_lower);
+    breakdown.recent_window_multiplier =
+        f64::from(recent_window_multiplier(result, boost_recent_years));
+    breakdown.total()
+}
+
+/// Computes the components of a title's relevance score; [`ScoreBreakdown::total`] combines
+/// them. The per-request multipliers are left at `1.0`.
+pub fn score_breakdown(
+    scoring: &ScoringConfig,
+    base_score: Score,
+    result: &TitleSearchResult,
+    query_lower: Option<&str>,
+) -> ScoreBreakdown {
     // ---- 1) Base signal: compress to avoid TF-IDF blowups
     let mut base = ((base_score as f64).max(0.0) + 1.0).ln(); // ~0..~something manageable
 
@@ -52,28 +230,9 @@ pub fn compute_title_relevance_score(
     }
 
     // ---- 3) Quality / popularity with proper Bayesian shrinkage
-    // Bayesian weighted rating: wr = (v/(v+m))*R + (m/(v+m))*C
-    let rating = result.average_rating.unwrap_or(5.0) as f64;
+    let (rating_component, popularity_component) = quality_components(scoring, result);
     let votes = result.num_votes.unwrap_or(0) as f64;
 
-    const GLOBAL_AVG: f64 = 6.7; // adjust if your corpus differs
-    const M_PRIOR: f64 = 12_000.0; // realistic IMDB-ish prior
-    let wr = if votes > 0.0 {
-        (votes / (votes + M_PRIOR)) * rating + (M_PRIOR / (votes + M_PRIOR)) * GLOBAL_AVG
-    } else {
-        GLOBAL_AVG
-    };
-    // Map to ~[0..3]
-    let rating_component = (wr / 10.0) * 3.0;
-
-    // Popularity: log-normalized and softly weighted to avoid swamping
-    const VMAX: f64 = 2_000_000.0; // rough upper bound for normalization
-    let popularity_component = if votes > 0.0 {
-        (votes.ln_1p() / VMAX.ln_1p()) * 2.2 // ~[0..2.2]
-    } else {
-        0.0
-    };
-
     // ---- 4) Recency (small)
     let current_year = Utc::now().year();
     let recency_year = if matches!(
@@ -96,22 +255,77 @@ pub fn compute_title_relevance_score(
         ((recency_year as f64 - 2012.0) / 90.0).clamp(-0.10, 0.15)
     };
 
-    // ---- 5) Combine
-    let mut combined = 1.0 + rating_component + popularity_component + year_component + title_bonus;
+    ScoreBreakdown {
+        base,
+        rating_component,
+        popularity_component,
+        year_component,
+        title_bonus,
+        // Cold-start dampening: smoothly punish low vote counts
+        cold_start_multiplier: scoring.cold_start_multiplier(votes),
+        recent_window_multiplier: 1.0,
+        genre_multiplier: 1.0,
+    }
+}
+
+/// Query-independent rating and popularity components of the default score.
+fn quality_components(scoring: &ScoringConfig, result: &TitleSearchResult) -> (f64, f64) {
+    // Bayesian weighted rating: wr = (v/(v+m))*R + (m/(v+m))*C
+    let rating = result.average_rating.unwrap_or(5.0);
+    let votes = result.num_votes.unwrap_or(0) as f64;
+
+    let wr = scoring.weighted_rating(rating, votes);
+    // Map to ~[0..3]
+    let rating_component = (wr / 10.0) * 3.0;
 
-    // Cold-start dampening: smoothly punish low vote counts
-    combined *= if votes < 50.0 {
-        0.20
-    } else if votes < 500.0 {
-        0.50
-    } else if votes < 2_000.0 {
-        0.80
+    // Popularity: log-normalized and softly weighted to avoid swamping
+    let popularity_component = if votes > 0.0 {
+        (votes.ln_1p() / scoring.vmax.ln_1p()) * 2.2 // ~[0..2.2]
     } else {
-        1.00
+        0.0
     };
+    (rating_component, popularity_component)
+}
+
+/// Query-independent popularity (~0–5.2): the weighted rating and log-votes components of
+/// the default score. `None` for titles without ratings data.
+pub fn title_popularity(scoring: &ScoringConfig, result: &TitleSearchResult) -> Option<f64> {
+    result.num_votes?;
+    let (rating_component, popularity_component) = quality_components(scoring, result);
+    Some(rating_component + popularity_component)
+}
 
-    // Keep it positive
-    combined = combined.max(0.05);
+/// Multiplier rewarding results that carry more of the requested genres, scaling linearly from
+/// `1.0` (none matched) to `1.0 + boost` (all matched).
+pub fn genre_match_multiplier(result: &TitleSearchResult, requested: &[String], boost: f32) -> f32 {
+    if requested.is_empty() || boost == 0.0 {
+        return 1.0;
+    }
+    let genres = result.genres.as_deref().unwrap_or_default();
+    let matched = requested
+        .iter()
+        .filter(|wanted| {
+            genres
+                .iter()
+                .any(|genre| genre.eq_ignore_ascii_case(wanted))
+        })
+        .count();
+    1.0 + boost * matched as f32 / requested.len() as f32
+}
 
-    (base * combined) as f32
+/// Rescales scores to 0–1 relative to the highest one, so the best hit scores exactly 1.0.
+///
+/// Scores are left untouched when there is no positive maximum to divide by.
+pub fn normalize_scores<'a>(scores: impl IntoIterator<Item = &'a mut Option<f32>>) {
+    let mut scores: Vec<&mut f32> = scores.into_iter().flatten().collect();
+    let max = scores
+        .iter()
+        .map(|score| **score)
+        .fold(f32::NEG_INFINITY, f32::max);
+    if !max.is_finite() || max <= 0.0 {
+        return;
+    }
+    for score in &mut scores {
+        **score = (**score / max).max(0.0);
+    }
 }
@@ -1,34 +1,121 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
+use arc_swap::ArcSwap;
 use axum::Router;
-use axum::routing::get;
+use axum::routing::{get, post};
+use tower_http::compression::CompressionLayer;
 
+use crate::config::AppConfig;
 use crate::indexer::{NameIndex, PreparedIndexes, TitleIndex};
 
-use super::handlers::{get_name_by_id, get_title_by_id, healthz, search_names, search_titles};
+use super::cache::SearchCache;
+use super::filters::describe_filters;
+use super::handlers::{
+    TitleSearchKey, TitleSearchOutcome, admin_reindex, admin_reindex_status, admin_segments,
+    build_meta, get_name_by_id, get_title_by_id, get_title_cast, get_title_episodes, healthz,
+    list_filters, list_title_types, random_title, related_genres, search_all, search_names,
+    search_titles, similar_titles, stats,
+};
+use super::jobs::ReindexJobs;
+use super::openapi::{docs, openapi_json};
+use super::scoring::{DefaultScorer, TitleScorer};
+use super::title_types::count_title_types;
+use super::types::{FiltersResponse, RelatedGenresResponse, TitleTypesResponse};
 
 #[derive(Clone)]
 pub struct AppState {
-    pub(crate) title_index: Arc<TitleIndex>,
-    pub(crate) name_index: Arc<NameIndex>,
+    /// Swapped wholesale by `/admin/reindex`; requests keep the index they loaded until they finish.
+    pub(crate) title_index: Arc<ArcSwap<TitleIndex>>,
+    pub(crate) name_index: Arc<ArcSwap<NameIndex>>,
+    pub(crate) config: Arc<AppConfig>,
+    pub(crate) filters: Arc<ArcSwap<FiltersResponse>>,
+    pub(crate) title_types: Arc<ArcSwap<TitleTypesResponse>>,
+    /// Relevance scorer for title search and similar titles.
+    pub(crate) scorer: Arc<dyn TitleScorer>,
+    /// `/genres/{genre}/related` results keyed by canonical genre, each with the index
+    /// generation it was computed from; entries from an older generation are recomputed.
+    pub(crate) related_genres: Arc<Mutex<RelatedGenresCache>>,
+    pub(crate) reindex_jobs: Arc<Mutex<ReindexJobs>>,
+    /// `/titles/search` outcomes keyed by normalized parameters; see [`SearchCache`].
+    pub(crate) search_cache: Arc<Mutex<SearchCache<TitleSearchKey, Arc<TitleSearchOutcome>>>>,
 }
 
+/// Related genres by canonical genre, with the index generation each was computed from.
+type RelatedGenresCache = HashMap<&'static str, (String, Arc<RelatedGenresResponse>)>;
+
 impl AppState {
-    pub fn new(indexes: PreparedIndexes) -> Self {
+    pub fn new(indexes: PreparedIndexes, config: AppConfig) -> Self {
+        let filters = describe_filters(&indexes.titles, &indexes.names, &config);
+        let title_types = count_title_types(&indexes.titles);
+        let scorer = DefaultScorer::new(config.scoring.clone());
+        let search_cache = SearchCache::new(
+            config.search_cache_capacity,
+            Duration::from_secs(config.search_cache_ttl_secs),
+        );
         Self {
-            title_index: Arc::new(indexes.titles),
-            name_index: Arc::new(indexes.names),
+            title_index: Arc::new(ArcSwap::from_pointee(indexes.titles)),
+            name_index: Arc::new(ArcSwap::from_pointee(indexes.names)),
+            config: Arc::new(config),
+            filters: Arc::new(ArcSwap::from_pointee(filters)),
+            title_types: Arc::new(ArcSwap::from_pointee(title_types)),
+            scorer: Arc::new(scorer),
+            related_genres: Arc::default(),
+            reindex_jobs: Arc::default(),
+            search_cache: Arc::new(Mutex::new(search_cache)),
         }
     }
+
+    /// Serves subsequent requests from `indexes`, dropping everything derived from the old ones.
+    pub fn swap_indexes(&self, indexes: PreparedIndexes) {
+        let filters = describe_filters(&indexes.titles, &indexes.names, &self.config);
+        let title_types = count_title_types(&indexes.titles);
+        self.title_index.store(Arc::new(indexes.titles));
+        self.name_index.store(Arc::new(indexes.names));
+        self.filters.store(Arc::new(filters));
+        self.title_types.store(Arc::new(title_types));
+        self.related_genres
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+        self.search_cache
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+
+    /// Replaces the [`DefaultScorer`] used to rank relevance-sorted titles.
+    pub fn with_scorer(mut self, scorer: impl TitleScorer + 'static) -> Self {
+        self.scorer = Arc::new(scorer);
+        self
+    }
 }
 
 pub fn router(state: AppState) -> Router {
     Router::new()
-        .route("/healthz", get(healthz))
-        .route("/search", get(search_titles))
+        .route("/filters", get(list_filters))
+        .route("/title-types", get(list_title_types))
+        .route("/stats", get(stats))
+        .route("/meta", get(build_meta))
+        .route("/openapi.json", get(openapi_json))
+        .route("/docs", get(docs))
+        .route("/search", get(search_all))
         .route("/titles/search", get(search_titles))
         .route("/names/search", get(search_names))
+        .route("/titles/random", get(random_title))
+        .route("/genres/{genre}/related", get(related_genres))
         .route("/titles/{tconst}", get(get_title_by_id))
+        .route("/titles/{tconst}/cast", get(get_title_cast))
+        .route("/titles/{tconst}/episodes", get(get_title_episodes))
+        .route("/titles/{tconst}/similar", get(similar_titles))
         .route("/names/{nconst}", get(get_name_by_id))
+        .route("/admin/segments", get(admin_segments))
+        .route("/admin/reindex", post(admin_reindex))
+        .route("/admin/reindex/{job_id}", get(admin_reindex_status))
+        // gzip or brotli, as negotiated by `Accept-Encoding`; tiny bodies are sent as they are.
+        .layer(CompressionLayer::new())
+        // Added after the layer so health checks skip it.
+        .route("/healthz", get(healthz))
         .with_state(state)
 }
@@ -0,0 +1,110 @@
+use tantivy::collector::TopDocs;
+use tantivy::query::FuzzyTermQuery;
+use tantivy::schema::TantivyDocument;
+use tantivy::{Order, Searcher, Term};
+
+use crate::indexer::TitleIndex;
+
+use super::utils::get_first_text;
+
+/// Edit distance searched for replacements, the most a fuzzy term query supports. Automatic
+/// fuzzy matching already reaches it for words of eight or more characters, so suggestions
+/// mostly help with shorter words and with searches that use a smaller fixed distance.
+const SUGGEST_MAX_DISTANCE: u8 = 2;
+
+/// Most-voted titles whose words are considered as replacements for one misspelled word.
+const SUGGEST_CANDIDATES: usize = 50;
+
+/// Rewrites the words of `text` that appear in no primary title to the closest word of a
+/// popular title within [`SUGGEST_MAX_DISTANCE`] edits. Returns `None` when every word is
+/// already known or nothing close enough exists.
+pub(super) fn suggest_title_query(
+    title_index: &TitleIndex,
+    searcher: &Searcher,
+    text: &str,
+) -> tantivy::Result<Option<String>> {
+    let Some(mut analyzer) = title_index.tokenizers.get("default") else {
+        return Ok(None);
+    };
+    let mut words = Vec::new();
+    analyzer.token_stream(text).process(&mut |token| {
+        words.push(token.text.clone());
+    });
+
+    let field = title_index.fields.primary_title;
+    let votes = title_index
+        .schema
+        .get_field_entry(title_index.fields.num_votes)
+        .name()
+        .to_string();
+    let mut changed = false;
+    for word in &mut words {
+        let term = Term::from_field_text(field, word);
+        if searcher.doc_freq(&term)? > 0 {
+            continue;
+        }
+        let query = FuzzyTermQuery::new(term, SUGGEST_MAX_DISTANCE, true);
+        let collector = TopDocs::with_limit(SUGGEST_CANDIDATES)
+            .order_by_fast_field::<i64>(votes.as_str(), Order::Desc);
+
+        // Closest candidate first, then the one used by the most titles.
+        let mut best: Option<(usize, u64, String)> = None;
+        for (_, address) in searcher.search(&query, &collector)? {
+            let doc = searcher.doc::<TantivyDocument>(address)?;
+            let Some(title) = get_first_text(&doc, field) else {
+                continue;
+            };
+            let mut candidates = Vec::new();
+            analyzer.token_stream(&title).process(&mut |token| {
+                candidates.push(token.text.clone());
+            });
+            for candidate in candidates {
+                let distance = edit_distance(word, &candidate);
+                if distance > usize::from(SUGGEST_MAX_DISTANCE) {
+                    continue;
+                }
+                let freq = searcher.doc_freq(&Term::from_field_text(field, &candidate))?;
+                let better = best.as_ref().is_none_or(|(best_distance, best_freq, _)| {
+                    (distance, std::cmp::Reverse(freq))
+                        < (*best_distance, std::cmp::Reverse(*best_freq))
+                });
+                if better {
+                    best = Some((distance, freq, candidate));
+                }
+            }
+        }
+        if let Some((_, _, replacement)) = best {
+            *word = replacement;
+            changed = true;
+        }
+    }
+
+    Ok(changed.then(|| words.join(" ")))
+}
+
+/// Optimal string alignment distance: Levenshtein plus adjacent transpositions, matching the
+/// fuzzy queries built with `transposition_cost_one`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = value;
+        }
+    }
+    rows[a.len()][b.len()]
+}
@@ -0,0 +1,45 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+
+use tracing::warn;
+
+use crate::indexer::TitleIndex;
+
+use super::types::{TitleTypeCount, TitleTypesResponse};
+
+/// Counts titles per distinct `titleType` by walking the field's term dictionaries.
+///
+/// Only dictionary metadata is read, so this is cheap enough to run once at startup; the result
+/// is cached in [`super::AppState`]. Documents deleted but not yet merged away are still counted.
+pub fn count_title_types(titles: &TitleIndex) -> TitleTypesResponse {
+    let searcher = titles.reader.searcher();
+    let mut counts: BTreeMap<String, u64> = BTreeMap::new();
+    for segment_reader in searcher.segment_readers() {
+        let inverted = match segment_reader.inverted_index(titles.fields.title_type) {
+            Ok(inverted) => inverted,
+            Err(err) => {
+                warn!(error = %err, "failed to read title type terms");
+                continue;
+            }
+        };
+        let mut stream = match inverted.terms().stream() {
+            Ok(stream) => stream,
+            Err(err) => {
+                warn!(error = %err, "failed to read title type terms");
+                continue;
+            }
+        };
+        while stream.advance() {
+            let title_type = String::from_utf8_lossy(stream.key()).into_owned();
+            *counts.entry(title_type).or_default() += u64::from(stream.value().doc_freq);
+        }
+    }
+
+    let mut title_types: Vec<TitleTypeCount> = counts
+        .into_iter()
+        .map(|(title_type, count)| TitleTypeCount { title_type, count })
+        .collect();
+    // Most common first; the map already ordered equal counts by name.
+    title_types.sort_by_key(|entry| Reverse(entry.count));
+    TitleTypesResponse { title_types }
+}
@@ -1,62 +1,382 @@
+use std::collections::BTreeMap;
+
 use axum::{Json, http::StatusCode};
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::indexer::{BuildMeta, BuildSummary, PopularityTier};
+pub use crate::search::{SortMode, TieBreak};
 
 use super::utils::deserialize_one_or_many;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct TitleSearchParams {
+    /// Search expression over primary, original and alternate titles.
     #[serde(default)]
     pub query: Option<String>,
+    /// Maximum results (default 10), clamped to `IMDB_MAX_LIMIT` or `IMDB_MAX_EXPORT_LIMIT` for CSV.
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Results to skip for pagination; `offset + limit` may not exceed 10,000.
+    #[serde(default)]
+    pub offset: Option<usize>,
+    /// Exact title type, e.g. `movie` or `tvSeries`.
     #[serde(default)]
     pub title_type: Option<String>,
+    /// Inclusive lower bound on the start year.
     #[serde(default)]
     pub start_year_min: Option<i64>,
+    /// Inclusive upper bound on the start year.
     #[serde(default)]
     pub start_year_max: Option<i64>,
+    /// First year of a decade (e.g. `1990`) limiting start years to `1990..=1999`.
+    #[serde(default)]
+    pub decade: Option<i64>,
+    /// Exact start year, shorthand for equal `start_year_min` and `start_year_max`.
+    #[serde(default)]
+    pub year: Option<i64>,
+    /// Inclusive lower bound on the series end year.
     #[serde(default)]
     pub end_year_min: Option<i64>,
+    /// Inclusive upper bound on the series end year.
     #[serde(default)]
     pub end_year_max: Option<i64>,
+    /// Inclusive lower bound on the average rating (0–10).
     #[serde(default)]
     pub min_rating: Option<f64>,
+    /// Inclusive upper bound on the average rating (0–10).
     #[serde(default)]
     pub max_rating: Option<f64>,
+    /// Inclusive lower bound on the vote count.
     #[serde(default)]
     pub min_votes: Option<i64>,
+    /// Inclusive upper bound on the vote count.
     #[serde(default)]
     pub max_votes: Option<i64>,
+    /// Vote-count bucket; unrated titles belong to none.
+    #[serde(default)]
+    pub tier: Option<PopularityTier>,
+    /// Inclusive lower bound on the runtime in minutes.
+    #[serde(default)]
+    pub min_runtime: Option<i64>,
+    /// Inclusive upper bound on the runtime in minutes.
+    #[serde(default)]
+    pub max_runtime: Option<i64>,
+    /// Required genres; repeat the parameter for several.
     #[serde(default, deserialize_with = "deserialize_one_or_many")]
     pub genres: Vec<String>,
+    /// Whether titles need all (default) or any of `genres`.
+    #[serde(default)]
+    pub genre_mode: Option<FilterMode>,
+    /// Genres a title must not have, whatever `genre_mode` is.
+    #[serde(default, deserialize_with = "deserialize_one_or_many")]
+    pub exclude_genres: Vec<String>,
+    /// Result order (default `relevance`).
     #[serde(default)]
     pub sort: Option<SortMode>,
+    /// Also match inside words of the primary title; requires `IMDB_INFIX_NGRAM`.
+    #[serde(default)]
+    pub infix: Option<bool>,
+    /// Language or region code adding a `localized_title`, e.g. `de`.
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Same as `lang`, which wins when both are given.
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Only titles with an AKA released in this region, e.g. `DE`.
+    #[serde(default)]
+    pub region: Option<String>,
+    /// Only titles with an AKA in this language, e.g. `ja`.
+    #[serde(default)]
+    pub aka_language: Option<String>,
+    /// Bucket results by this field; `limit` applies per bucket.
+    #[serde(default)]
+    pub group_by: Option<GroupBy>,
+    /// Include titles flagged as adult (default `false`).
+    #[serde(default)]
+    pub include_adult: Option<bool>,
+    /// Rescale scores to 0–1 relative to the best candidate.
+    #[serde(default)]
+    pub normalize_scores: Option<bool>,
+    /// Typo-tolerant matching (default `true`).
+    #[serde(default)]
+    pub fuzzy: Option<bool>,
+    /// Boost titles that started within the last N years.
+    #[serde(default)]
+    pub boost_recent_years: Option<u32>,
+    /// `any` word (default) or the words as a `phrase`.
+    #[serde(default, rename = "match")]
+    pub match_mode: Option<MatchMode>,
+    /// Whether plain query words are OR-ed (default) or AND-ed.
+    #[serde(default)]
+    pub default_operator: Option<Operator>,
+    /// Add the assembled query as `query_debug`.
+    #[serde(default)]
+    pub debug: Option<bool>,
+    /// Add `highlighted_title` with matched words wrapped in `<em>`.
+    #[serde(default)]
+    pub highlight: Option<bool>,
+    /// Add per-genre `facet_counts`.
+    #[serde(default)]
+    pub facets: Option<bool>,
+    /// Explain empty results with per-filter `diagnostics`.
+    #[serde(default)]
+    pub diagnose: Option<bool>,
+    /// Add a `score_breakdown` to relevance-ranked results.
+    #[serde(default)]
+    pub debug_score: Option<bool>,
+    /// nconst of a person who must be credited on the title (principal cast/crew, director or writer).
+    #[serde(default)]
+    pub person: Option<String>,
+    /// Ignore a leading "The", "A" or "An" when sorting by title.
+    #[serde(default)]
+    pub strip_articles: Option<bool>,
+    /// Series tconst whose episodes are searched, e.g. `tt0903747`.
+    #[serde(default)]
+    pub parent: Option<String>,
+    /// Season number of the episodes, usually together with `parent`.
+    #[serde(default)]
+    pub season: Option<i64>,
+    /// Suggest a respelled query as `did_you_mean` when nothing matches.
+    #[serde(default)]
+    pub suggest: Option<bool>,
+    /// Response body format (default `json`).
+    #[serde(default)]
+    pub format: Option<Format>,
+    /// Skip the search result cache for this request; the fresh results are not cached either.
+    #[serde(default)]
+    pub no_cache: Option<bool>,
 }
 
-#[derive(Debug, Clone, Copy, Deserialize, Default)]
+impl TitleSearchParams {
+    /// Whether any explicit filter narrows the search, independent of `query`.
+    ///
+    /// Only decides whether a query shorter than `min_query_len` is accepted; every
+    /// filter parameter counts, including `decade`, `year`, `region`, `aka_language`,
+    /// `parent` and `season`. Sorting, paging and display options (`lang`) do not.
+    pub fn has_filters(&self) -> bool {
+        self.title_type.is_some()
+            || self.start_year_min.is_some()
+            || self.start_year_max.is_some()
+            || self.decade.is_some()
+            || self.year.is_some()
+            || self.end_year_min.is_some()
+            || self.end_year_max.is_some()
+            || self.min_rating.is_some()
+            || self.max_rating.is_some()
+            || self.min_votes.is_some()
+            || self.max_votes.is_some()
+            || self.min_runtime.is_some()
+            || self.max_runtime.is_some()
+            || !self.genres.is_empty()
+            || !self.exclude_genres.is_empty()
+            || self.person.is_some()
+            || self.region.is_some()
+            || self.parent.is_some()
+            || self.season.is_some()
+            || self.aka_language.is_some()
+    }
+
+    /// Code used to pick `localized_title`: `lang` or `language`, else the `region` or
+    /// `aka_language` filter.
+    pub fn localization(&self) -> Option<&str> {
+        self.lang
+            .as_deref()
+            .or(self.language.as_deref())
+            .or(self.region.as_deref())
+            .or(self.aka_language.as_deref())
+    }
+
+    /// Requested start-year bounds, with `decade` expanded to its ten years and `year` to
+    /// itself.
+    pub fn start_year_bounds(&self) -> (Option<i64>, Option<i64>) {
+        match (self.decade, self.year) {
+            (Some(decade), _) => (Some(decade), Some(decade.saturating_add(9))),
+            (None, Some(year)) => (Some(year), Some(year)),
+            (None, None) => (self.start_year_min, self.start_year_max),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TitleLookupParams {
+    /// Language or region code adding a `localized_title`, e.g. `de`.
+    #[serde(default)]
+    pub lang: Option<String>,
+    /// Same as `lang`, which wins when both are given.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+impl TitleLookupParams {
+    /// Code used to pick `localized_title`.
+    pub fn localization(&self) -> Option<&str> {
+        self.lang.as_deref().or(self.language.as_deref())
+    }
+}
+
+/// How the values of a repeated filter such as `genres` or `primary_profession` combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterMode {
+    /// Results must have every requested value.
+    #[default]
+    All,
+    /// Results must have at least one requested value.
+    Any,
+}
+
+/// How the words of `query` are matched and ranked against titles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchMode {
+    /// Any word may match (the query parser's default OR semantics).
+    #[default]
+    Any,
+    /// Like `Any`, with titles holding the words together, in order, ranked first.
+    Phrase,
+}
+
+/// How query words without an explicit `AND`/`OR`/`+`/`-` combine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Operator {
+    /// Any word may match; matching more words ranks higher.
+    #[default]
+    Or,
+    /// Every word must match.
+    And,
+}
+
+/// Body format of `/titles/search`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Format {
+    #[default]
+    Json,
+    /// `text/csv` with a header row and one line per result.
+    Csv,
+}
+
+/// Field used to bucket title search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GroupBy {
+    TitleType,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SimilarTitlesParams {
+    /// Maximum results (default 10), clamped to `IMDB_MAX_LIMIT`.
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct TitleEpisodesParams {
+    /// Maximum episodes (default and cap `IMDB_MAX_LIMIT`).
+    #[serde(default)]
+    pub limit: Option<usize>,
+    /// Episodes to skip, in episode order (default 0).
+    #[serde(default)]
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RandomTitleParams {
+    /// Makes the pick reproducible; echoed in `X-Random-Seed`.
+    #[serde(default)]
+    pub seed: Option<u64>,
+}
+
+/// Order of name search results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Default, ToSchema)]
 #[serde(rename_all = "snake_case")]
-pub enum SortMode {
+pub enum NameSortMode {
     #[default]
     Relevance,
-    RatingDesc,
-    RatingAsc,
-    VotesDesc,
-    VotesAsc,
+    /// Youngest first; names without a birth year last.
+    BirthYearDesc,
+    /// Oldest first; names without a birth year last.
+    BirthYearAsc,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct TitleSearchResponse {
+    /// Number of documents matching the query and filters, ignoring `limit`/`offset`.
+    pub total: usize,
     pub results: Vec<TitleSearchResult>,
+    /// Matching titles per genre, ignoring the `genres` filter; only present with `facets=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facet_counts: Option<BTreeMap<String, u64>>,
+    /// Matches left when each filter is dropped; only present for empty results with
+    /// `diagnose=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<Vec<FilterDiagnostic>>,
+    /// `{:?}` of the assembled Tantivy query; only present with `debug=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_debug: Option<String>,
+    /// Respelled query; only present for empty results with `suggest=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did_you_mean: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// Result count of a search with one filter removed.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FilterDiagnostic {
+    /// Request parameter behind the filter, e.g. `start_year` or `genres`.
+    pub filter: String,
+    pub results_without: usize,
+}
+
+/// Title search results bucketed by `group_by`; each bucket keeps the requested sort order.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct GroupedTitleSearchResponse {
+    /// Number of documents matching the query and filters across all groups.
+    pub total: usize,
+    pub groups: BTreeMap<String, Vec<TitleSearchResult>>,
+    /// Matching titles per genre, ignoring the `genres` filter; only present with `facets=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub facet_counts: Option<BTreeMap<String, u64>>,
+    /// Matches left when each filter is dropped; only present for empty results with
+    /// `diagnose=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diagnostics: Option<Vec<FilterDiagnostic>>,
+    /// `{:?}` of the assembled Tantivy query; only present with `debug=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_debug: Option<String>,
+    /// Respelled query; only present for empty results with `suggest=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub did_you_mean: Option<String>,
+}
+
+/// Body of `/titles/search`, flat unless `group_by` is requested.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum TitleSearchOutput {
+    Flat(TitleSearchResponse),
+    Grouped(GroupedTitleSearchResponse),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct TitleSearchResult {
     pub tconst: String,
+    /// The title's page on imdb.com.
+    #[serde(default)]
+    pub imdb_url: String,
     pub primary_title: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub original_title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title_type: Option<String>,
+    /// `true` for `tvSeries` and `tvMiniSeries`; episodes are not series.
+    #[serde(default)]
+    pub is_series: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_year: Option<i64>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -67,34 +387,143 @@ pub struct TitleSearchResult {
     pub average_rating: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub num_votes: Option<i64>,
+    /// Query-independent popularity from rating and votes, for re-sorting client-side.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub popularity: Option<f64>,
+    /// Vote-count bucket assigned at index time; absent for unrated titles.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub popularity_tier: Option<PopularityTier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub runtime_minutes: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overview: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub localized_title: Option<String>,
+    /// `primaryTitle` with matched query terms wrapped in `<em>`; only set with `highlight=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub highlighted_title: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub score: Option<f32>,
+    /// Components behind `score`; only set with `debug_score=true` and the default scorer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_breakdown: Option<ScoreBreakdown>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sort_value: Option<f64>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Components of the default relevance score.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ScoreBreakdown {
+    /// Log-compressed tantivy score, raised for exact title matches.
+    pub base: f64,
+    pub rating_component: f64,
+    pub popularity_component: f64,
+    pub year_component: f64,
+    pub title_bonus: f64,
+    pub cold_start_multiplier: f64,
+    /// `boost_recent_years` multiplier, `1.0` outside the window.
+    pub recent_window_multiplier: f64,
+    /// Requested-genre multiplier, `1.0` without `genres`.
+    pub genre_multiplier: f64,
+}
+
+impl ScoreBreakdown {
+    /// `base × max((1 + components) × cold_start_multiplier, 0.05) × the per-request multipliers`.
+    pub fn total(&self) -> f32 {
+        let components = 1.0
+            + self.rating_component
+            + self.popularity_component
+            + self.year_component
+            + self.title_bonus;
+        let combined = (components * self.cold_start_multiplier).max(0.05)
+            * self.recent_window_multiplier
+            * self.genre_multiplier;
+        (self.base * combined) as f32
+    }
+}
+
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct NameSearchParams {
+    /// Text searched across primary names and professions.
     #[serde(default)]
     pub query: String,
+    /// Maximum results (default 10), clamped to `IMDB_MAX_LIMIT`.
     #[serde(default)]
     pub limit: Option<usize>,
+    /// Inclusive lower bound on the birth year.
     #[serde(default)]
     pub birth_year_min: Option<i64>,
+    /// Inclusive upper bound on the birth year.
     #[serde(default)]
     pub birth_year_max: Option<i64>,
+    /// Required professions; repeat the parameter for several.
     #[serde(default, deserialize_with = "deserialize_one_or_many")]
     pub primary_profession: Vec<String>,
+    /// Whether every (default) or any requested profession is required.
+    #[serde(default)]
+    pub profession_mode: Option<FilterMode>,
+    /// `true` keeps only people without a death year, `false` only those with one.
+    #[serde(default)]
+    pub alive: Option<bool>,
+    /// Rescale scores to 0–1 relative to the best result on the page.
+    #[serde(default)]
+    pub normalize_scores: Option<bool>,
+    /// Typo-tolerant matching (default `true`).
+    #[serde(default)]
+    pub fuzzy: Option<bool>,
+    /// Whether plain query words are OR-ed (default) or AND-ed.
+    #[serde(default)]
+    pub default_operator: Option<Operator>,
+    /// Also match names that sound like the query (Soundex).
+    #[serde(default)]
+    pub phonetic: Option<bool>,
+    /// Result order (default `relevance`).
+    #[serde(default)]
+    pub sort: Option<NameSortMode>,
+    /// Add `known_for` with the title and start year of each known-for title.
+    #[serde(default)]
+    pub resolve_known_for: Option<bool>,
+    /// Add the assembled query as `query_debug`.
+    #[serde(default)]
+    pub debug: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct NameSearchResponse {
+    /// Number of documents matching the query and filters, ignoring `limit`/`offset`.
+    pub total: usize,
     pub results: Vec<NameSearchResult>,
+    /// `{:?}` of the assembled Tantivy query; only present with `debug=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_debug: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CombinedSearchParams {
+    /// Text searched across titles and names.
+    pub query: String,
+    /// Results per section (default 5, at most `IMDB_MAX_LIMIT`).
+    pub limit: Option<usize>,
+    /// Typo-tolerant matching (default `true`).
+    #[serde(default)]
+    pub fuzzy: Option<bool>,
+}
+
+/// Body of `/search`: the best matching titles and names for one query.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CombinedSearchResponse {
+    pub titles: Vec<TitleSearchResult>,
+    pub names: Vec<NameSearchResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct NameSearchResult {
     pub nconst: String,
+    /// The person's page on imdb.com.
+    #[serde(default)]
+    pub imdb_url: String,
     pub primary_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub birth_year: Option<i64>,
@@ -104,10 +533,215 @@ pub struct NameSearchResult {
     pub primary_profession: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub known_for_titles: Option<Vec<String>>,
+    /// `known_for_titles` found in the title index, in the same order; only present with
+    /// `resolve_known_for=true`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub known_for: Option<Vec<KnownForTitle>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub score: Option<f32>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FiltersResponse {
+    pub titles: Vec<FilterDescriptor>,
+    pub names: Vec<FilterDescriptor>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TitleTypesResponse {
+    /// Distinct title types, most common first.
+    pub title_types: Vec<TitleTypeCount>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct TitleTypeCount {
+    pub title_type: String,
+    /// Number of indexed titles of this type.
+    pub count: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FilterDescriptor {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: FilterKind,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub repeatable: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterKind {
+    Text,
+    Integer,
+    Float,
+    Boolean,
+    Enum,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StatsResponse {
+    pub titles: IndexStats,
+    pub names: IndexStats,
+    pub search_cache: SearchCacheStats,
+}
+
+/// Usage of the `/titles/search` result cache since startup.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchCacheStats {
+    /// Configured `IMDB_SEARCH_CACHE_CAPACITY`; `0` when the cache is disabled.
+    pub capacity: usize,
+    pub entries: usize,
+    pub hits: u64,
+    pub misses: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IndexStats {
+    pub num_docs: u64,
+    /// Counts and duration recorded by the build that produced the index; absent for indexes
+    /// built before summaries were recorded.
+    pub last_build: Option<BuildSummary>,
+}
+
+/// `build_meta.json` of each index; `null` for indexes built before it was recorded.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MetaResponse {
+    pub titles: Option<BuildMeta>,
+    pub names: Option<BuildMeta>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SegmentsResponse {
+    pub titles: IndexSegments,
+    pub names: IndexSegments,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct IndexSegments {
+    pub segment_count: usize,
+    pub num_docs: u64,
+    pub num_deleted_docs: u64,
+    pub size_bytes: u64,
+    pub segments: Vec<SegmentInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SegmentInfo {
+    pub segment_id: String,
+    pub num_docs: u32,
+    pub num_deleted_docs: u32,
+    pub max_doc: u32,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Running,
+    Succeeded,
+    Failed,
+}
+
+/// A background `/admin/reindex` run.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReindexJob {
+    pub job_id: u64,
+    pub status: JobStatus,
+    /// RFC 3339 timestamp.
+    pub started_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<String>,
+    /// Why the job failed; the previous indexes keep serving in that case.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SimilarTitlesResponse {
+    pub tconst: String,
+    /// Most relevant titles first; never includes the source title.
+    pub results: Vec<TitleSearchResult>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TitleCastResponse {
+    pub tconst: String,
+    /// Principals in IMDb billing order.
+    pub cast: Vec<CastMember>,
+}
+
+/// Episodes of a series, by season and then episode number.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TitleEpisodesResponse {
+    pub tconst: String,
+    /// Episodes of the series, including those outside the requested page.
+    pub total: usize,
+    pub episodes: Vec<EpisodeSummary>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NameLookupParams {
+    /// Add `known_for` with the title and start year of each known-for title.
+    #[serde(default)]
+    pub resolve_known_for: Option<bool>,
+}
+
+/// A known-for title looked up in the title index.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KnownForTitle {
+    pub tconst: String,
+    pub primary_title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_year: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct EpisodeSummary {
+    pub tconst: String,
+    pub primary_title: String,
+    /// Absent when IMDb does not record the season (`\N`); such episodes sort last.
+    pub season_number: Option<i64>,
+    pub episode_number: Option<i64>,
+    pub average_rating: Option<f64>,
+    pub num_votes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CastMember {
+    pub nconst: String,
+    pub primary_name: String,
+    /// Role category such as `actor`, `director` or `writer`.
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub characters: Vec<String>,
+    pub ordering: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RelatedGenresResponse {
+    pub genre: String,
+    /// Number of titles carrying `genre`.
+    pub titles: usize,
+    pub related: Vec<RelatedGenre>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RelatedGenre {
+    pub genre: String,
+    /// Number of titles carrying both this genre and the requested one.
+    pub count: usize,
+}
+
 #[derive(Debug)]
 pub struct ApiError {
     pub status: StatusCode,
@@ -132,6 +766,14 @@ impl ApiError {
         }
     }
 
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::UNAUTHORIZED,
+            message: message.into(),
+            detail: None,
+        }
+    }
+
     pub fn not_found(message: impl Into<String>) -> Self {
         Self {
             status: StatusCode::NOT_FOUND,
@@ -139,9 +781,17 @@ impl ApiError {
             detail: None,
         }
     }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::CONFLICT,
+            message: message.into(),
+            detail: None,
+        }
+    }
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct ErrorBody {
     pub message: String,
 }
@@ -1,9 +1,17 @@
+use std::collections::HashSet;
+
+use axum::http::HeaderMap;
+use axum::http::header::IF_NONE_MATCH;
 use serde::Deserializer;
+use tantivy::query::{PhraseQuery, Query as TantivyQuery, TermQuery};
 use tantivy::schema::{Field, OwnedValue, TantivyDocument};
+use tantivy::tokenizer::TextAnalyzer;
+use tantivy::{Searcher, Term};
 
-use crate::indexer::{NameFields, TitleFields};
+use crate::indexer::{AkaTitle, NameFields, Principal, TitleFields};
 
-use super::types::{NameSearchResult, TitleSearchResult};
+use super::scoring::{ScoringConfig, title_popularity};
+use super::types::{CastMember, NameSearchResult, TitleSearchResult};
 
 pub fn deserialize_one_or_many<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
 where
@@ -47,26 +55,187 @@ where
     deserializer.deserialize_any(Visitor)
 }
 
+/// Header carrying the generation of the index a search response was served from.
+pub const INDEX_GENERATION_HEADER: &str = "x-index-generation";
+
+/// 64-bit FNV-1a over explicitly fed bytes. Unlike `DefaultHasher`, whose algorithm may change
+/// between Rust releases, the result is fixed, so generations and ETags survive upgrades.
+struct Fnv1a(u64);
+
+impl Fnv1a {
+    fn new() -> Self {
+        Self(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+    }
+}
+
+/// Fingerprints the searcher's committed segments (and their delete opstamps) so the value
+/// changes on every commit or reindex but stays the same across restarts and releases.
+pub fn index_generation(searcher: &Searcher) -> String {
+    let mut hasher = Fnv1a::new();
+    for (segment_id, delete_opstamp) in searcher.generation().segments() {
+        hasher.write(segment_id.uuid_string().as_bytes());
+        match delete_opstamp {
+            Some(opstamp) => {
+                hasher.write(&[1]);
+                hasher.write(&opstamp.to_le_bytes());
+            }
+            None => hasher.write(&[0]),
+        }
+    }
+    format!("{:016x}", hasher.0)
+}
+
+/// Weak ETag of a single-document response: the document id and any response variant (such as
+/// `lang`), scoped to the index generation so it changes with every rebuild.
+pub fn document_etag(searcher: &Searcher, id: &str, variant: Option<&str>) -> String {
+    let mut hasher = Fnv1a::new();
+    hasher.write(id.as_bytes());
+    if let Some(variant) = variant {
+        // Ids never contain NUL, so `id` and `variant` cannot run together ambiguously.
+        hasher.write(&[0]);
+        hasher.write(variant.as_bytes());
+    }
+    format!(
+        "W/\"{id}-{}-{:08x}\"",
+        index_generation(searcher),
+        hasher.0 as u32
+    )
+}
+
+/// Whether the request's `If-None-Match` lists `etag` (or `*`), using weak comparison.
+pub fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    headers
+        .get_all(IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+/// Public IMDb website that `imdb_url` fields link to.
+const IMDB_SITE_URL: &str = "https://www.imdb.com";
+
+/// Title types that are series in their own right, as opposed to movies or episodes.
+pub const SERIES_TITLE_TYPES: [&str; 2] = ["tvSeries", "tvMiniSeries"];
+
 pub fn document_to_title_result(
     doc: &TantivyDocument,
     fields: &TitleFields,
+    lang: Option<&str>,
+    scoring: &ScoringConfig,
 ) -> Result<TitleSearchResult, anyhow::Error> {
     let primary_title = get_first_text(doc, fields.primary_title)
         .ok_or_else(|| anyhow::anyhow!("document missing primaryTitle"))?;
 
-    Ok(TitleSearchResult {
-        tconst: get_first_text(doc, fields.tconst).unwrap_or_default(),
+    let tconst = get_first_text(doc, fields.tconst).unwrap_or_default();
+    let title_type = get_first_text(doc, fields.title_type);
+
+    let mut result = TitleSearchResult {
+        imdb_url: format!("{IMDB_SITE_URL}/title/{tconst}/"),
+        tconst,
         primary_title,
         original_title: get_first_text(doc, fields.original_title),
-        title_type: get_first_text(doc, fields.title_type),
+        is_series: title_type
+            .as_deref()
+            .is_some_and(|title_type| SERIES_TITLE_TYPES.contains(&title_type)),
+        title_type,
         start_year: get_first_i64(doc, fields.start_year),
         end_year: get_first_i64(doc, fields.end_year),
         genres: get_all_text(doc, fields.genres),
         average_rating: get_first_f64(doc, fields.average_rating),
         num_votes: get_first_i64(doc, fields.num_votes),
+        popularity: None,
+        popularity_tier: get_first_text(doc, fields.popularity_tier)
+            .and_then(|tier| tier.parse().ok()),
+        runtime_minutes: get_first_i64(doc, fields.runtime_minutes),
+        overview: fields.overview.and_then(|field| get_first_text(doc, field)),
+        localized_title: lang.and_then(|lang| localized_title(doc, fields, lang)),
+        highlighted_title: None,
+        score_breakdown: None,
         score: None,
         sort_value: None,
-    })
+    };
+    result.popularity = title_popularity(scoring, &result);
+    Ok(result)
+}
+
+/// Columns written by [`title_results_csv`].
+const TITLE_CSV_HEADER: [&str; 7] = [
+    "tconst",
+    "primary_title",
+    "title_type",
+    "start_year",
+    "average_rating",
+    "num_votes",
+    "genres",
+];
+
+/// Serializes title results as CSV with a header row; genres are joined with `|`.
+pub fn title_results_csv(results: &[TitleSearchResult]) -> Result<Vec<u8>, csv::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(TITLE_CSV_HEADER)?;
+    let optional = |value: Option<String>| value.unwrap_or_default();
+    for result in results {
+        writer.write_record([
+            result.tconst.clone(),
+            result.primary_title.clone(),
+            optional(result.title_type.clone()),
+            optional(result.start_year.map(|year| year.to_string())),
+            optional(result.average_rating.map(|rating| rating.to_string())),
+            optional(result.num_votes.map(|votes| votes.to_string())),
+            optional(result.genres.as_ref().map(|genres| genres.join("|"))),
+        ])?;
+    }
+    writer
+        .into_inner()
+        .map_err(|err| csv::Error::from(err.into_error()))
+}
+
+/// Picks the AKA title for `lang`, preferring an explicit language match over a region match
+/// (IMDb often records only the region, e.g. `DE`).
+pub fn localized_title(doc: &TantivyDocument, fields: &TitleFields, lang: &str) -> Option<String> {
+    let akas: Vec<AkaTitle> = get_all_text(doc, fields.localized_titles)?
+        .iter()
+        .filter_map(|value| AkaTitle::from_stored(value))
+        .collect();
+    let matches = |value: &Option<String>| {
+        value
+            .as_deref()
+            .is_some_and(|value| value.eq_ignore_ascii_case(lang))
+    };
+    akas.iter()
+        .find(|aka| matches(&aka.language))
+        .or_else(|| akas.iter().find(|aka| matches(&aka.region)))
+        .map(|aka| aka.title.clone())
+}
+
+/// Decodes the stored principals of a title document, in billing order.
+pub fn document_to_cast(doc: &TantivyDocument, fields: &TitleFields) -> Vec<CastMember> {
+    let mut principals: Vec<Principal> = get_all_text(doc, fields.principals)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|value| Principal::from_stored(value))
+        .collect();
+    principals.sort_by_key(|principal| principal.ordering);
+    principals
+        .into_iter()
+        .map(|principal| CastMember {
+            nconst: principal.nconst,
+            primary_name: principal.primary_name,
+            category: principal.category,
+            job: principal.job,
+            characters: principal.characters,
+            ordering: principal.ordering,
+        })
+        .collect()
 }
 
 pub fn document_to_name_result(
@@ -89,31 +258,122 @@ pub fn document_to_name_result(
             })
             .collect::<Vec<String>>()
     });
-    let known_for = get_all_text(doc, fields.known_for_titles).map(|values| {
-        values
-            .into_iter()
-            .flat_map(|entry| {
-                entry
-                    .split(',')
-                    .map(|s| s.trim())
-                    .filter(|value| !value.is_empty())
-                    .map(String::from)
-                    .collect::<Vec<String>>()
-            })
-            .collect::<Vec<String>>()
-    });
+    let known_for = resolve_known_for(doc, fields);
+    let nconst = get_first_text(doc, fields.nconst).unwrap_or_default();
 
     Ok(NameSearchResult {
-        nconst: get_first_text(doc, fields.nconst).unwrap_or_default(),
+        imdb_url: format!("{IMDB_SITE_URL}/name/{nconst}/"),
+        nconst,
         primary_name,
         birth_year: get_first_i64(doc, fields.birth_year),
         death_year: get_first_i64(doc, fields.death_year),
         primary_profession: professions,
         known_for_titles: known_for,
+        known_for: None,
         score: None,
     })
 }
 
+/// Returns the known-for tconsts in IMDb's original order (most significant first).
+///
+/// The stored value is the raw comma-separated column, so its order is authoritative; the
+/// indexed tokens are only used for matching and must never drive display order.
+pub fn resolve_known_for(doc: &TantivyDocument, fields: &NameFields) -> Option<Vec<String>> {
+    let values = get_all_text(doc, fields.known_for_titles)?;
+    let mut seen = HashSet::new();
+    Some(
+        values
+            .iter()
+            .flat_map(|entry| entry.split(','))
+            .map(str::trim)
+            .filter(|value| !value.is_empty() && *value != "\\N")
+            .filter(|value| seen.insert(*value))
+            .map(String::from)
+            .collect(),
+    )
+}
+
+/// Splits IMDb ids with `prefix` (e.g. `tt0133093`) out of a free-text query, returning the
+/// lowercased ids and the remaining words.
+///
+/// The `AND`/`OR` joining a removed id to its neighbour is dropped with it, so the remaining
+/// words still parse. Ids inside a quoted phrase or negated with `NOT` stay in the text.
+pub fn split_embedded_ids(query: &str, prefix: &str) -> (Vec<String>, String) {
+    let is_binary_operator = |word: &str| matches!(word, "AND" | "OR" | "&&" | "||");
+    let mut ids = Vec::new();
+    let mut words: Vec<&str> = Vec::new();
+    let mut in_phrase = false;
+    let mut drop_operator = false;
+    for token in query.split_whitespace() {
+        let quoted = in_phrase || token.contains('"');
+        in_phrase ^= token.matches('"').count() % 2 == 1;
+        if std::mem::take(&mut drop_operator) && !quoted && is_binary_operator(token) {
+            continue;
+        }
+        let lower = token.to_ascii_lowercase();
+        let is_id = lower
+            .strip_prefix(prefix)
+            .is_some_and(|digits| digits.len() >= 7 && digits.bytes().all(|b| b.is_ascii_digit()));
+        if !is_id || quoted || words.last() == Some(&"NOT") {
+            words.push(token);
+            continue;
+        }
+        ids.push(lower);
+        if words.last().is_some_and(|word| is_binary_operator(word)) {
+            words.pop();
+        } else {
+            drop_operator = true;
+        }
+    }
+    (ids, words.join(" "))
+}
+
+/// Compares `provided` with the secret `expected` in time that depends only on the length of
+/// `expected`, so response timing does not reveal how much of a guessed token was right.
+pub fn constant_time_eq(provided: &[u8], expected: &[u8]) -> bool {
+    let diff = expected
+        .iter()
+        .enumerate()
+        .fold(provided.len() ^ expected.len(), |diff, (index, byte)| {
+            diff | usize::from(provided.get(index).copied().unwrap_or(0) ^ byte)
+        });
+    std::hint::black_box(diff) == 0
+}
+
+/// Normalizes an IMDb id from a request path: `133093`, `0133093` and `TT0133093` all become
+/// `tt0133093`. Digits are padded to the conventional seven; anything that is not a number
+/// (after the optional `prefix`) is returned unchanged and simply won't match.
+pub fn normalize_imdb_id(raw: &str, prefix: &str) -> String {
+    let trimmed = raw.trim();
+    let lower = trimmed.to_ascii_lowercase();
+    let digits = lower.strip_prefix(prefix).unwrap_or(&lower);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return trimmed.to_string();
+    }
+    format!("{prefix}{:0>7}", digits.trim_start_matches('0'))
+}
+
+/// Tokenizes `text` with `analyzer` and matches the tokens as a phrase on `field`, falling back
+/// to a term query for a single token. Returns `None` when `text` yields no tokens.
+pub fn phrase_or_term_query(
+    analyzer: &mut TextAnalyzer,
+    field: Field,
+    text: &str,
+) -> Option<Box<dyn TantivyQuery>> {
+    let mut terms = Vec::new();
+    analyzer.token_stream(text).process(&mut |token| {
+        terms.push((token.position, Term::from_field_text(field, &token.text)));
+    });
+    match terms.len() {
+        0 => None,
+        1 => {
+            let (_, term) = terms.pop()?;
+            Some(Box::new(TermQuery::new(term, Default::default())))
+        }
+        _ => Some(Box::new(PhraseQuery::new_with_offset(terms))),
+    }
+}
+
 pub fn get_first_text(doc: &TantivyDocument, field: Field) -> Option<String> {
     doc.get_first(field)
         .and_then(|value| match OwnedValue::from(value) {
@@ -159,3 +419,20 @@ pub fn get_first_f64(doc: &TantivyDocument, field: Field) -> Option<f64> {
             _ => None,
         })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_matches_the_reference_vectors() {
+        let hash = |bytes: &[u8]| {
+            let mut hasher = Fnv1a::new();
+            hasher.write(bytes);
+            hasher.0
+        };
+        assert_eq!(hash(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(hash(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(hash(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+}
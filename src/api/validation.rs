@@ -0,0 +1,147 @@
+use axum::extract::FromRequestParts;
+use axum::http::request::Parts;
+use serde::de::DeserializeOwned;
+
+use super::types::{
+    ApiError, CombinedSearchParams, Format, NameSearchParams, SimilarTitlesParams,
+    TitleEpisodesParams, TitleSearchParams,
+};
+
+/// Deepest result (`offset + limit`) a title search may page to.
+pub const MAX_RESULT_WINDOW: usize = 10_000;
+
+/// Semantic checks run on query parameters after they deserialize successfully.
+pub trait Validate {
+    fn validate(&self) -> Result<(), ApiError>;
+}
+
+/// Query extractor that rejects type/enum errors and failed [`Validate`] checks with the
+/// standard JSON [`ApiError`] body instead of axum's plain-text rejection.
+///
+/// Parsing goes through `serde_html_form` so repeated keys (`genres=a&genres=b`) collect into
+/// lists, which axum's `Query` rejects as duplicate fields.
+pub struct ValidatedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + Validate,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query().unwrap_or_default();
+        let value: T = serde_html_form::from_str(query).map_err(|err| {
+            ApiError::bad_request(format!("failed to deserialize query string: {err}"))
+        })?;
+        value.validate()?;
+        Ok(Self(value))
+    }
+}
+
+fn check_range<T: PartialOrd + std::fmt::Display>(
+    name: &str,
+    min: Option<T>,
+    max: Option<T>,
+) -> Result<(), ApiError> {
+    if let (Some(min), Some(max)) = (min, max)
+        && min > max
+    {
+        return Err(ApiError::bad_request(format!(
+            "invalid {name} range: minimum {min} is greater than maximum {max}"
+        )));
+    }
+    Ok(())
+}
+
+fn check_bounds<T: PartialOrd + std::fmt::Display + Copy>(
+    param: &str,
+    value: Option<T>,
+    lower: T,
+    upper: T,
+) -> Result<(), ApiError> {
+    if let Some(value) = value
+        && (value < lower || value > upper)
+    {
+        return Err(ApiError::bad_request(format!(
+            "{param} must be between {lower} and {upper}, got {value}"
+        )));
+    }
+    Ok(())
+}
+
+impl Validate for TitleSearchParams {
+    fn validate(&self) -> Result<(), ApiError> {
+        if self.group_by.is_some() && self.format == Some(Format::Csv) {
+            return Err(ApiError::bad_request(
+                "format=csv is not supported together with group_by",
+            ));
+        }
+        if self.group_by.is_some() && self.offset.is_some_and(|offset| offset > 0) {
+            return Err(ApiError::bad_request(
+                "offset is not supported together with group_by",
+            ));
+        }
+        if let Some(decade) = self.decade {
+            if self.start_year_min.is_some() || self.start_year_max.is_some() {
+                return Err(ApiError::bad_request(
+                    "decade cannot be combined with start_year_min or start_year_max",
+                ));
+            }
+            if decade % 10 != 0 {
+                return Err(ApiError::bad_request(format!(
+                    "decade must be a multiple of 10, got {decade}"
+                )));
+            }
+            check_bounds("decade", Some(decade), 1800, 2200)?;
+        }
+        if self.year.is_some()
+            && (self.decade.is_some()
+                || self.start_year_min.is_some()
+                || self.start_year_max.is_some())
+        {
+            return Err(ApiError::bad_request(
+                "year cannot be combined with decade, start_year_min or start_year_max",
+            ));
+        }
+        check_range("start_year", self.start_year_min, self.start_year_max)?;
+        check_range("end_year", self.end_year_min, self.end_year_max)?;
+        check_bounds("min_rating", self.min_rating, 0.0, 10.0)?;
+        check_bounds("max_rating", self.max_rating, 0.0, 10.0)?;
+        check_range("rating", self.min_rating, self.max_rating)?;
+        check_bounds("min_votes", self.min_votes, 0, i64::MAX)?;
+        check_bounds("max_votes", self.max_votes, 0, i64::MAX)?;
+        check_range("votes", self.min_votes, self.max_votes)?;
+        check_bounds("min_runtime", self.min_runtime, 0, i64::MAX)?;
+        check_bounds("max_runtime", self.max_runtime, 0, i64::MAX)?;
+        check_range("runtime", self.min_runtime, self.max_runtime)
+    }
+}
+
+impl Validate for NameSearchParams {
+    fn validate(&self) -> Result<(), ApiError> {
+        check_range("birth_year", self.birth_year_min, self.birth_year_max)
+    }
+}
+
+/// `limit` is clamped rather than rejected, so only deserialization can fail.
+impl Validate for TitleEpisodesParams {
+    fn validate(&self) -> Result<(), ApiError> {
+        check_bounds("offset", self.offset, 0, MAX_RESULT_WINDOW)
+    }
+}
+
+impl Validate for SimilarTitlesParams {
+    fn validate(&self) -> Result<(), ApiError> {
+        Ok(())
+    }
+}
+
+impl Validate for CombinedSearchParams {
+    fn validate(&self) -> Result<(), ApiError> {
+        if self.query.trim().is_empty() {
+            return Err(ApiError::bad_request("query must not be empty"));
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,22 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashSet};
 use std::env;
+use std::fs;
 use std::net::SocketAddr;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::Context;
+
+use crate::api::ScoringConfig;
+use crate::datasets::{IMDB_BASE_URL, RefreshPolicy};
+use crate::indexer::{
+    DEFAULT_GENRES_BOOST, DEFAULT_NAME_BOOST, DEFAULT_NAME_WRITER_MB, DEFAULT_ORIGINAL_TITLE_BOOST,
+    DEFAULT_OVERVIEW_BOOST, DEFAULT_PRIMARY_TITLE_BOOST, DEFAULT_SUPPORTING_PRINCIPALS_BOOST,
+    DEFAULT_TITLE_WRITER_MB, DocstoreCompression, FuzzyDistance, MIN_WRITER_MB, MalformedRowPolicy,
+    default_index_workers,
+};
+use crate::search::{SortMode, TieBreak};
 
 /// Application configuration driven by environment variables.
 #[derive(Debug, Clone)]
@@ -8,32 +24,536 @@ pub struct AppConfig {
     pub data_dir: PathBuf,
     pub index_dir: PathBuf,
     pub bind_addr: SocketAddr,
+    /// Base URL the dataset archives are downloaded from.
+    pub base_url: String,
+    /// Download every archive in [`crate::datasets::DATASET_FILES`] instead of only those the indexer reads.
+    pub download_all_datasets: bool,
+    /// When datasets already on disk are checked for a newer upstream version.
+    pub refresh: RefreshPolicy,
+    /// Inflate archives to `.tsv` files; when off the indexer reads the `.gz` archives directly.
+    pub decompress_datasets: bool,
+    /// Dataset archives downloaded at the same time.
+    pub download_concurrency: usize,
+    /// Attempts per archive before a transient download failure aborts startup.
+    pub download_attempts: usize,
+    /// Delay before the first download retry; later retries double it (plus jitter).
+    pub download_retry_delay_ms: u64,
+    /// Bearer token required by `/admin/*` endpoints; admin routes are disabled when unset.
+    pub admin_token: Option<String>,
+    /// Earliest plausible birth/death year kept when indexing names.
+    pub name_min_year: Option<i64>,
+    /// Latest plausible birth/death year kept when indexing names.
+    pub name_max_year: Option<i64>,
+    /// Sort modes accepted by `/titles/search`; every mode is allowed when unset.
+    pub allowed_sorts: Option<Vec<SortMode>>,
+    /// Secondary ordering for title results that tie on the requested sort.
+    pub tiebreak: TieBreak,
+    /// Edits per term tolerated by fuzzy title and name queries.
+    pub fuzzy_distance: FuzzyDistance,
+    /// Index an ngram copy of `primaryTitle` so `infix=true` searches can match inside words.
+    pub infix_ngram: bool,
+    pub infix_min_gram: usize,
+    pub infix_max_gram: usize,
+    /// Largest `limit` honoured by the search endpoints; bigger requests are clamped.
+    pub max_limit: usize,
+    /// Largest `limit` for `format=csv` title searches.
+    pub max_export_limit: usize,
+    /// Shortest non-empty title query accepted without any filter.
+    pub min_query_len: usize,
+    /// Default `min_rating` applied to title searches without query text.
+    pub browse_min_rating: Option<f64>,
+    /// Searches slower than this many milliseconds are logged at `warn`.
+    pub slow_query_ms: Option<u64>,
+    /// Title searches kept in the in-memory result cache; `0` disables it.
+    pub search_cache_capacity: usize,
+    /// Seconds a cached title search is served before it is recomputed.
+    pub search_cache_ttl_secs: u64,
+    /// How dataset rows with missing columns are handled while indexing.
+    pub malformed_rows: MalformedRowPolicy,
+    /// Corpus constants of the default relevance scorer.
+    pub scoring: ScoringConfig,
+    /// Relevance multiplier added when a title has all requested genres (scaled by the fraction matched).
+    pub genre_match_boost: f32,
+    /// Title types searched when a request has no `title_type`.
+    pub default_title_types: Vec<String>,
+    /// Skip the browse defaults (title types, 1980 floor, browse rating) so searches only apply requested filters.
+    pub disable_implicit_defaults: bool,
+    /// Extra title.basics column (by header) indexed and returned as `overview` when present.
+    pub overview_column: Option<String>,
+    /// Query parser boost for the `overview` field.
+    pub overview_boost: f32,
+    /// Query parser boost for `primary_title` matches.
+    pub primary_title_boost: f32,
+    /// Query parser boost for `original_title` matches.
+    pub original_title_boost: f32,
+    /// Query parser boost for genre matches.
+    pub genres_boost: f32,
+    /// Query parser boost for names of principals billed below the top three.
+    pub supporting_principals_boost: f32,
+    /// Query parser boost for person name matches.
+    pub name_boost: f32,
+    /// Docstore compression used when building indexes; a change rebuilds existing indexes.
+    pub docstore_compression: DocstoreCompression,
+    /// Load title.principals at index time; disable to save memory on small machines.
+    pub index_principals: bool,
+    /// Load title.episode at index time so episodes can be listed per series.
+    pub index_episodes: bool,
+    /// Threads building title documents while indexing; defaults to the available cores.
+    pub index_workers: usize,
+    /// Title index writer heap in MiB; larger budgets flush fewer, bigger segments.
+    pub title_writer_mb: usize,
+    /// Name index writer heap in MiB.
+    pub name_writer_mb: usize,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        let data_dir = PathBuf::from("data");
+        Self {
+            index_dir: data_dir.join("tantivy_index"),
+            data_dir,
+            bind_addr: SocketAddr::from(([127, 0, 0, 1], 3000)),
+            base_url: IMDB_BASE_URL.to_string(),
+            download_all_datasets: false,
+            refresh: RefreshPolicy::default(),
+            decompress_datasets: true,
+            download_concurrency: 3,
+            download_attempts: 3,
+            download_retry_delay_ms: 1_000,
+            admin_token: None,
+            name_min_year: None,
+            name_max_year: None,
+            allowed_sorts: None,
+            tiebreak: TieBreak::default(),
+            fuzzy_distance: FuzzyDistance::default(),
+            infix_ngram: false,
+            infix_min_gram: 3,
+            infix_max_gram: 3,
+            max_limit: 50,
+            max_export_limit: 1_000,
+            min_query_len: 2,
+            browse_min_rating: None,
+            slow_query_ms: None,
+            search_cache_capacity: 1_000,
+            search_cache_ttl_secs: 300,
+            malformed_rows: MalformedRowPolicy::default(),
+            scoring: ScoringConfig::default(),
+            genre_match_boost: 0.5,
+            default_title_types: default_title_types(),
+            disable_implicit_defaults: false,
+            overview_column: None,
+            overview_boost: DEFAULT_OVERVIEW_BOOST,
+            primary_title_boost: DEFAULT_PRIMARY_TITLE_BOOST,
+            original_title_boost: DEFAULT_ORIGINAL_TITLE_BOOST,
+            genres_boost: DEFAULT_GENRES_BOOST,
+            supporting_principals_boost: DEFAULT_SUPPORTING_PRINCIPALS_BOOST,
+            name_boost: DEFAULT_NAME_BOOST,
+            docstore_compression: DocstoreCompression::default(),
+            index_principals: true,
+            index_episodes: true,
+            index_workers: default_index_workers(),
+            title_writer_mb: DEFAULT_TITLE_WRITER_MB,
+            name_writer_mb: DEFAULT_NAME_WRITER_MB,
+        }
+    }
 }
 
 impl AppConfig {
+    /// Reads the configuration from `IMDB_*` environment variables, falling back to the TOML
+    /// file named by `IMDB_CONFIG` for variables that are not set.
     pub fn from_env() -> anyhow::Result<Self> {
-        let data_dir = env::var("IMDB_DATA_DIR")
+        match env::var("IMDB_CONFIG") {
+            Ok(path) if !path.trim().is_empty() => Self::from_env_with_file(Path::new(path.trim())),
+            _ => Self::from_settings(&Settings::new(BTreeMap::new(), true)),
+        }
+    }
+
+    /// Like [`from_env`](Self::from_env), reading `path` instead of the file named by
+    /// `IMDB_CONFIG`.
+    pub fn from_env_with_file(path: &Path) -> anyhow::Result<Self> {
+        Self::from_settings(&Settings::new(read_config_file(path)?, true))
+    }
+
+    /// Reads the configuration from a TOML file alone, ignoring the environment.
+    ///
+    /// Keys are the environment variable names without the `IMDB_` prefix, in lowercase
+    /// (`data_dir`, `title_writer_mb`, ...); nested tables join their keys with `_`, so
+    /// `[scoring] m_prior = 500` sets `IMDB_SCORING_M_PRIOR`. Lists may be TOML arrays.
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Self::from_settings(&Settings::new(read_config_file(path.as_ref())?, false))
+    }
+
+    fn from_settings(settings: &Settings) -> anyhow::Result<Self> {
+        let data_dir = settings
+            .var("IMDB_DATA_DIR")
             .map(PathBuf::from)
-            .unwrap_or_else(|_| PathBuf::from("data"));
+            .unwrap_or_else(|| PathBuf::from("data"));
 
-        let index_dir = env::var("IMDB_INDEX_DIR")
+        let index_dir = settings
+            .var("IMDB_INDEX_DIR")
             .map(PathBuf::from)
-            .unwrap_or_else(|_| data_dir.join("tantivy_index"));
+            .unwrap_or_else(|| data_dir.join("tantivy_index"));
+
+        let bind_addr: SocketAddr = settings
+            .var("IMDB_BIND_ADDR")
+            .unwrap_or_else(|| "127.0.0.1:3000".to_string())
+            .parse()
+            .context("parsing IMDB_BIND_ADDR")?;
 
-        let bind_addr: SocketAddr = env::var("IMDB_BIND_ADDR")
-            .unwrap_or_else(|_| "127.0.0.1:3000".to_string())
-            .parse()?;
+        let base_url = settings
+            .var("IMDB_BASE_URL")
+            .map(|value| value.trim().to_string())
+            .unwrap_or_else(|| IMDB_BASE_URL.to_string());
+        let download_all_datasets = settings
+            .flag("IMDB_DOWNLOAD_ALL_DATASETS")?
+            .unwrap_or(false);
+        let refresh = settings.parse("IMDB_REFRESH")?.unwrap_or_default();
+        let decompress_datasets = settings.flag("IMDB_DECOMPRESS_DATASETS")?.unwrap_or(true);
+        let download_concurrency = settings
+            .parse("IMDB_DOWNLOAD_CONCURRENCY")?
+            .unwrap_or(3)
+            .max(1);
 
-        Ok(Self {
+        let download_attempts = settings
+            .parse("IMDB_DOWNLOAD_ATTEMPTS")?
+            .unwrap_or(3)
+            .max(1);
+        let download_retry_delay_ms = settings
+            .parse("IMDB_DOWNLOAD_RETRY_DELAY_MS")?
+            .unwrap_or(1_000);
+
+        let admin_token = settings.var("IMDB_ADMIN_TOKEN");
+
+        let name_min_year = settings.parse("IMDB_NAME_MIN_YEAR")?;
+        let name_max_year = settings.parse("IMDB_NAME_MAX_YEAR")?;
+        let allowed_sorts = settings.list("IMDB_ALLOWED_SORTS")?;
+        let tiebreak = settings.parse("IMDB_TIEBREAK")?.unwrap_or_default();
+        let fuzzy_distance = settings.parse("IMDB_FUZZY_DISTANCE")?.unwrap_or_default();
+        let infix_ngram = settings.parse("IMDB_INFIX_NGRAM")?.unwrap_or(false);
+        let infix_min_gram = settings.parse("IMDB_INFIX_MIN_GRAM")?.unwrap_or(3);
+        let infix_max_gram = settings
+            .parse("IMDB_INFIX_MAX_GRAM")?
+            .unwrap_or(infix_min_gram);
+        let max_limit = settings.parse("IMDB_MAX_LIMIT")?.unwrap_or(50).max(1);
+        let max_export_limit = settings
+            .parse("IMDB_MAX_EXPORT_LIMIT")?
+            .unwrap_or(1_000)
+            .max(max_limit);
+        let min_query_len = settings.parse("IMDB_MIN_QUERY_LEN")?.unwrap_or(2);
+        let browse_min_rating = settings.parse("IMDB_BROWSE_MIN_RATING")?;
+        let slow_query_ms = settings.parse("IMDB_SLOW_QUERY_MS")?;
+        let search_cache_capacity = settings
+            .parse("IMDB_SEARCH_CACHE_CAPACITY")?
+            .unwrap_or(1_000);
+        let search_cache_ttl_secs = settings.parse("IMDB_SEARCH_CACHE_TTL_SECS")?.unwrap_or(300);
+        let malformed_rows = settings.parse("IMDB_MALFORMED_ROWS")?.unwrap_or_default();
+        let scoring = scoring_from_settings(settings)?;
+        let genre_match_boost = settings.parse("IMDB_GENRE_MATCH_BOOST")?.unwrap_or(0.5);
+        let default_title_types = settings
+            .list("IMDB_DEFAULT_TITLE_TYPES")?
+            .unwrap_or_else(default_title_types);
+        let disable_implicit_defaults = settings
+            .flag("IMDB_DISABLE_IMPLICIT_DEFAULTS")?
+            .unwrap_or(false);
+        let overview_column = settings
+            .var("IMDB_OVERVIEW_COLUMN")
+            .map(|value| value.trim().to_string());
+        let overview_boost = parse_boost(settings, "IMDB_OVERVIEW_BOOST", DEFAULT_OVERVIEW_BOOST)?;
+        let primary_title_boost = parse_boost(
+            settings,
+            "IMDB_PRIMARY_TITLE_BOOST",
+            DEFAULT_PRIMARY_TITLE_BOOST,
+        )?;
+        let original_title_boost = parse_boost(
+            settings,
+            "IMDB_ORIGINAL_TITLE_BOOST",
+            DEFAULT_ORIGINAL_TITLE_BOOST,
+        )?;
+        let genres_boost = parse_boost(settings, "IMDB_GENRES_BOOST", DEFAULT_GENRES_BOOST)?;
+        let supporting_principals_boost = parse_boost(
+            settings,
+            "IMDB_SUPPORTING_PRINCIPALS_BOOST",
+            DEFAULT_SUPPORTING_PRINCIPALS_BOOST,
+        )?;
+        let name_boost = parse_boost(settings, "IMDB_NAME_BOOST", DEFAULT_NAME_BOOST)?;
+        let docstore_compression = settings
+            .parse("IMDB_DOCSTORE_COMPRESSION")?
+            .unwrap_or_default();
+        let index_principals = settings.flag("IMDB_INDEX_PRINCIPALS")?.unwrap_or(true);
+        let index_episodes = settings.flag("IMDB_INDEX_EPISODES")?.unwrap_or(true);
+        let index_workers = settings
+            .parse("IMDB_INDEX_WORKERS")?
+            .unwrap_or_else(default_index_workers)
+            .max(1);
+        let title_writer_mb =
+            parse_writer_mb(settings, "IMDB_TITLE_WRITER_MB", DEFAULT_TITLE_WRITER_MB)?;
+        let name_writer_mb =
+            parse_writer_mb(settings, "IMDB_NAME_WRITER_MB", DEFAULT_NAME_WRITER_MB)?;
+
+        let config = Self {
             data_dir,
             index_dir,
             bind_addr,
-        })
+            base_url,
+            download_all_datasets,
+            refresh,
+            decompress_datasets,
+            download_concurrency,
+            download_attempts,
+            download_retry_delay_ms,
+            admin_token,
+            name_min_year,
+            name_max_year,
+            allowed_sorts,
+            tiebreak,
+            fuzzy_distance,
+            infix_ngram,
+            infix_min_gram,
+            infix_max_gram,
+            max_limit,
+            max_export_limit,
+            min_query_len,
+            browse_min_rating,
+            slow_query_ms,
+            search_cache_capacity,
+            search_cache_ttl_secs,
+            malformed_rows,
+            scoring,
+            genre_match_boost,
+            default_title_types,
+            disable_implicit_defaults,
+            overview_column,
+            overview_boost,
+            primary_title_boost,
+            original_title_boost,
+            genres_boost,
+            supporting_principals_boost,
+            name_boost,
+            docstore_compression,
+            index_principals,
+            index_episodes,
+            index_workers,
+            title_writer_mb,
+            name_writer_mb,
+        };
+        settings.reject_unknown_keys()?;
+        Ok(config)
+    }
+}
+
+/// Default [`ScoringConfig`] with any `IMDB_SCORING_*` overrides applied.
+///
+/// `IMDB_SCORING_VMAX` must exceed 1, since popularity is normalized by `ln(1 + vmax)`, and the
+/// cold-start tiers must be listed by ascending vote threshold because the first match wins.
+fn scoring_from_settings(settings: &Settings) -> anyhow::Result<ScoringConfig> {
+    let defaults = ScoringConfig::default();
+    let scoring = ScoringConfig {
+        global_avg: settings
+            .parse("IMDB_SCORING_GLOBAL_AVG")?
+            .unwrap_or(defaults.global_avg),
+        m_prior: settings
+            .parse("IMDB_SCORING_M_PRIOR")?
+            .unwrap_or(defaults.m_prior),
+        vmax: settings
+            .parse("IMDB_SCORING_VMAX")?
+            .unwrap_or(defaults.vmax),
+        cold_start: settings
+            .list("IMDB_SCORING_COLD_START")?
+            .unwrap_or(defaults.cold_start),
+    };
+    anyhow::ensure!(
+        scoring.vmax > 1.0,
+        "IMDB_SCORING_VMAX must be greater than 1, got {}",
+        scoring.vmax
+    );
+    anyhow::ensure!(
+        scoring
+            .cold_start
+            .windows(2)
+            .all(|pair| pair[0].below_votes < pair[1].below_votes),
+        "IMDB_SCORING_COLD_START tiers must be listed by ascending vote count"
+    );
+    Ok(scoring)
+}
+
+/// Parses an index writer heap in MiB, rejecting budgets tantivy cannot open a writer with.
+fn parse_writer_mb(settings: &Settings, key: &str, default: usize) -> anyhow::Result<usize> {
+    let megabytes = settings.parse(key)?.unwrap_or(default);
+    anyhow::ensure!(
+        megabytes >= MIN_WRITER_MB,
+        "{key} is {megabytes} MiB, but the index writer needs at least {MIN_WRITER_MB} MiB"
+    );
+    Ok(megabytes)
+}
+
+fn parse_boost(settings: &Settings, key: &str, default: f32) -> anyhow::Result<f32> {
+    let boost = settings.parse(key)?.unwrap_or(default);
+    anyhow::ensure!(
+        boost >= 0.0,
+        "{key} is {boost}, but query boosts must be zero or positive"
+    );
+    Ok(boost)
+}
+
+fn default_title_types() -> Vec<String> {
+    vec!["movie".to_string(), "tvSeries".to_string()]
+}
+
+/// Reads a TOML config file into values keyed by the environment variable they stand for.
+fn read_config_file(path: &Path) -> anyhow::Result<BTreeMap<String, String>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("reading config file {}", path.display()))?;
+    let table: toml::Table = toml::from_str(&contents)
+        .with_context(|| format!("parsing config file {}", path.display()))?;
+    let mut values = BTreeMap::new();
+    flatten_table("IMDB", &table, &mut values)
+        .with_context(|| format!("reading config file {}", path.display()))?;
+    Ok(values)
+}
+
+fn flatten_table(
+    prefix: &str,
+    table: &toml::Table,
+    values: &mut BTreeMap<String, String>,
+) -> anyhow::Result<()> {
+    for (key, value) in table {
+        let name = format!("{prefix}_{}", key.to_ascii_uppercase());
+        match value {
+            toml::Value::Table(table) => flatten_table(&name, table, values)?,
+            toml::Value::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(scalar_setting)
+                    .collect::<Option<Vec<_>>>()
+                    .with_context(|| format!("`{key}` must be a list of plain values"))?;
+                values.insert(name, items.join(","));
+            }
+            value => {
+                values.insert(
+                    name,
+                    scalar_setting(value).expect("tables and arrays handled"),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn scalar_setting(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(value) => Some(value.clone()),
+        toml::Value::Integer(value) => Some(value.to_string()),
+        toml::Value::Float(value) => Some(value.to_string()),
+        toml::Value::Boolean(value) => Some(value.to_string()),
+        toml::Value::Datetime(value) => Some(value.to_string()),
+        toml::Value::Array(_) | toml::Value::Table(_) => None,
+    }
+}
+
+/// Raw setting values by environment variable name: the process environment (when enabled)
+/// wins over the config file.
+struct Settings {
+    file: BTreeMap<String, String>,
+    env: bool,
+    /// Variables looked up so far, to catch misspelt keys in the config file.
+    read: RefCell<HashSet<String>>,
+}
+
+impl Settings {
+    fn new(file: BTreeMap<String, String>, env: bool) -> Self {
+        Self {
+            file,
+            env,
+            read: RefCell::default(),
+        }
+    }
+
+    /// Blank values count as unset, so an empty `IMDB_*` variable falls back to the config file
+    /// and then to the default.
+    fn var(&self, key: &str) -> Option<String> {
+        self.read.borrow_mut().insert(key.to_string());
+        let non_blank = |value: &String| !value.trim().is_empty();
+        self.env
+            .then(|| env::var(key).ok())
+            .flatten()
+            .filter(non_blank)
+            .or_else(|| self.file.get(key).cloned())
+            .filter(non_blank)
+    }
+
+    fn parse<T>(&self, key: &str) -> anyhow::Result<Option<T>>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        match self.var(key) {
+            Some(value) => value
+                .trim()
+                .parse()
+                .map(Some)
+                .map_err(Into::into)
+                .with_context(|| format!("parsing {key}")),
+            None => Ok(None),
+        }
+    }
+
+    /// Parses a boolean variable, accepting `1`/`0` alongside `true`/`false`.
+    fn flag(&self, key: &str) -> anyhow::Result<Option<bool>> {
+        match self.var(key) {
+            Some(value) => match value.trim().to_ascii_lowercase().as_str() {
+                "1" | "true" | "yes" | "on" => Ok(Some(true)),
+                "0" | "false" | "no" | "off" => Ok(Some(false)),
+                other => anyhow::bail!("parsing {key}: expected a boolean, got `{other}`"),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Parses a comma-separated variable, ignoring blank entries.
+    fn list<T>(&self, key: &str) -> anyhow::Result<Option<Vec<T>>>
+    where
+        T: FromStr,
+        T::Err: Into<anyhow::Error>,
+    {
+        let Some(value) = self.var(key) else {
+            return Ok(None);
+        };
+        let values = value
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                entry
+                    .parse()
+                    .map_err(Into::into)
+                    .with_context(|| format!("parsing {key}"))
+            })
+            .collect::<anyhow::Result<Vec<T>>>()?;
+        Ok((!values.is_empty()).then_some(values))
+    }
+
+    fn reject_unknown_keys(&self) -> anyhow::Result<()> {
+        let read = self.read.borrow();
+        let unknown: Vec<_> = self
+            .file
+            .keys()
+            .filter(|key| !read.contains(*key))
+            .map(|key| key["IMDB_".len()..].to_ascii_lowercase())
+            .collect();
+        anyhow::ensure!(
+            unknown.is_empty(),
+            "unknown config file keys: {}",
+            unknown.join(", ")
+        );
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
+    // Tests touching the process environment need `unsafe`: Rust 2024 marks `set_var` and
+    // `remove_var` unsafe because the environment is global state shared by every thread.
+
     use super::*;
 
     #[test]
@@ -42,7 +562,6 @@ mod tests {
         let prev_index = env::var("IMDB_INDEX_DIR").ok();
         let prev_bind = env::var("IMDB_BIND_ADDR").ok();
 
-        // Mutating process environment is unsafe in Rust 2024 because it affects global state.
         unsafe {
             env::remove_var("IMDB_DATA_DIR");
             env::remove_var("IMDB_INDEX_DIR");
@@ -53,6 +572,7 @@ mod tests {
         assert_eq!(config.data_dir, PathBuf::from("data"));
         assert_eq!(config.index_dir, PathBuf::from("data/tantivy_index"));
         assert_eq!(config.bind_addr, "127.0.0.1:3000".parse().unwrap());
+        assert_eq!(config.default_title_types, ["movie", "tvSeries"]);
 
         // Restore any previous environment to avoid leaking state across tests.
         unsafe {
@@ -73,4 +593,115 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn writer_budgets_below_the_tantivy_minimum_are_rejected() {
+        let settings = |value: &str| {
+            let file = BTreeMap::from([("IMDB_TITLE_WRITER_MB".to_string(), value.to_string())]);
+            Settings::new(file, false)
+        };
+        let err = parse_writer_mb(&settings("8"), "IMDB_TITLE_WRITER_MB", 256).unwrap_err();
+        assert!(err.to_string().contains("at least 15 MiB"), "{err}");
+        assert_eq!(
+            parse_writer_mb(&settings("64"), "IMDB_TITLE_WRITER_MB", 256).unwrap(),
+            64
+        );
+        assert_eq!(
+            parse_writer_mb(&settings("64"), "IMDB_NAME_WRITER_MB", 128).unwrap(),
+            128
+        );
+    }
+
+    #[test]
+    fn scoring_rejects_a_flat_vmax_and_unsorted_cold_start_tiers() {
+        let settings = |key: &str, value: &str| {
+            let file = BTreeMap::from([(key.to_string(), value.to_string())]);
+            Settings::new(file, false)
+        };
+        for vmax in ["1", "0", "-5", "NaN"] {
+            let err = scoring_from_settings(&settings("IMDB_SCORING_VMAX", vmax)).unwrap_err();
+            assert!(err.to_string().contains("IMDB_SCORING_VMAX"), "{err}");
+        }
+        let err = scoring_from_settings(&settings("IMDB_SCORING_COLD_START", "500:0.5,50:0.2"))
+            .unwrap_err();
+        assert!(err.to_string().contains("ascending"), "{err}");
+
+        let scoring =
+            scoring_from_settings(&settings("IMDB_SCORING_COLD_START", "10:0.1,100:0.6")).unwrap();
+        assert_eq!(scoring.cold_start.len(), 2);
+    }
+
+    #[test]
+    fn boosts_reject_negative_and_nan_values() {
+        let settings = |value: &str| {
+            let file = BTreeMap::from([("IMDB_NAME_BOOST".to_string(), value.to_string())]);
+            Settings::new(file, false)
+        };
+        for boost in ["-0.5", "NaN"] {
+            let err = parse_boost(&settings(boost), "IMDB_NAME_BOOST", 1.5).unwrap_err();
+            assert!(err.to_string().contains("IMDB_NAME_BOOST"), "{err}");
+        }
+        assert_eq!(
+            parse_boost(&settings("0"), "IMDB_NAME_BOOST", 1.5).unwrap(),
+            0.0
+        );
+    }
+
+    #[test]
+    fn config_files_use_env_names_and_reject_unknown_keys() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(
+            &path,
+            r#"
+data_dir = "/srv/imdb"
+bind_addr = "0.0.0.0:8080"
+infix_ngram = true
+title_writer_mb = 512
+allowed_sorts = ["relevance", "rating_desc"]
+default_title_types = ["movie"]
+
+[scoring]
+m_prior = 500
+"#,
+        )
+        .unwrap();
+
+        let config = AppConfig::from_file(&path).unwrap();
+        assert_eq!(config.data_dir, PathBuf::from("/srv/imdb"));
+        assert_eq!(config.index_dir, PathBuf::from("/srv/imdb/tantivy_index"));
+        assert_eq!(config.bind_addr, "0.0.0.0:8080".parse().unwrap());
+        assert!(config.infix_ngram);
+        assert_eq!(config.title_writer_mb, 512);
+        assert_eq!(config.allowed_sorts.unwrap().len(), 2);
+        assert_eq!(config.default_title_types, ["movie"]);
+        assert_eq!(config.scoring.m_prior, 500.0);
+
+        std::fs::write(&path, "data_dri = \"/srv/imdb\"\n").unwrap();
+        let err = AppConfig::from_file(&path).unwrap_err();
+        assert!(err.to_string().contains("data_dri"), "{err}");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn environment_variables_win_over_the_config_file() {
+        let file = BTreeMap::from([("IMDB_TEST_PRECEDENCE".to_string(), "file".to_string())]);
+        let settings = Settings::new(file, true);
+        assert_eq!(
+            settings.var("IMDB_TEST_PRECEDENCE").as_deref(),
+            Some("file")
+        );
+
+        unsafe { env::set_var("IMDB_TEST_PRECEDENCE", "env") };
+        assert_eq!(settings.var("IMDB_TEST_PRECEDENCE").as_deref(), Some("env"));
+        // An empty variable counts as unset rather than overriding the file.
+        unsafe { env::set_var("IMDB_TEST_PRECEDENCE", "") };
+        assert_eq!(
+            settings.var("IMDB_TEST_PRECEDENCE").as_deref(),
+            Some("file")
+        );
+        unsafe { env::remove_var("IMDB_TEST_PRECEDENCE") };
+    }
 }
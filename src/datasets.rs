@@ -1,10 +1,14 @@
+use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Write};
+use std::io::{BufReader, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
 use anyhow::{Context, Result};
+use flate2::Crc;
 use flate2::read::GzDecoder;
-use futures_util::TryStreamExt;
+use futures_util::{StreamExt, TryStreamExt, future, stream};
+use rand::Rng;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use tokio::task;
@@ -23,13 +27,65 @@ pub const DATASET_FILES: &[&str] = &[
     "title.ratings.tsv.gz",
 ];
 
-const IMDB_BASE_URL: &str = "https://datasets.imdbws.com";
+/// Official location of the dataset archives; `IMDB_BASE_URL` can point at a mirror instead.
+pub const IMDB_BASE_URL: &str = "https://datasets.imdbws.com";
+
+/// Upper bound on the backoff exponent, so late retries wait at most 32× the base delay.
+const MAX_BACKOFF_DOUBLINGS: u32 = 5;
+
+/// Age after which [`RefreshPolicy::Daily`] checks an archive for a newer upstream version.
+const DAILY_REFRESH_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Response headers that may carry the archive's SHA-256, checked in order.
+const SHA256_HEADERS: &[&str] = &["x-amz-checksum-sha256", "x-amz-meta-sha256"];
+
+/// When datasets already on disk are checked for a newer upstream version.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RefreshPolicy {
+    /// Keep whatever is on disk, so indexes stay reproducible.
+    #[default]
+    Never,
+    /// Check datasets whose local copy is more than a day old.
+    Daily,
+    /// Check every dataset on each startup.
+    Always,
+}
+
+impl RefreshPolicy {
+    /// Whether a local copy last modified `age` ago is due for a check.
+    fn is_due(self, age: Duration) -> bool {
+        match self {
+            RefreshPolicy::Never => false,
+            RefreshPolicy::Daily => age >= DAILY_REFRESH_AGE,
+            RefreshPolicy::Always => true,
+        }
+    }
+}
+
+impl std::str::FromStr for RefreshPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "never" => Ok(RefreshPolicy::Never),
+            "daily" => Ok(RefreshPolicy::Daily),
+            "always" => Ok(RefreshPolicy::Always),
+            other => Err(anyhow::anyhow!("unknown refresh policy `{other}`")),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct DatasetFile {
     pub name: &'static str,
     pub gz_path: PathBuf,
     pub tsv_path: PathBuf,
+    /// ETag reported by the server for the downloaded archive.
+    pub etag_path: PathBuf,
+    /// SHA-256 the server advertised for the downloaded archive, when it sent one.
+    pub sha256_path: PathBuf,
+    /// Fingerprint of the archive the decompressed TSV was produced from.
+    pub source_path: PathBuf,
 }
 
 impl DatasetFile {
@@ -39,77 +95,482 @@ impl DatasetFile {
         let tsv_path = data_dir.join(tsv_name);
         Self {
             name,
+            etag_path: data_dir.join(format!("{name}.etag")),
+            sha256_path: data_dir.join(format!("{name}.sha256")),
+            source_path: data_dir.join(format!("{tsv_name}.source")),
             gz_path,
             tsv_path,
         }
     }
+
+    /// File the indexer reads: the archive while it is kept (it is newer than any leftover TSV,
+    /// since decompression removes it), otherwise the decompressed TSV.
+    pub fn path(&self) -> &Path {
+        if self.gz_path.exists() {
+            &self.gz_path
+        } else {
+            &self.tsv_path
+        }
+    }
+
+    /// Opens [`DatasetFile::path`] for reading, decompressing on the fly when it is the archive.
+    pub fn open(&self) -> Result<Box<dyn Read + Send>> {
+        open_dataset(self.path())
+    }
+}
+
+/// Fingerprint of the archive behind `path`, a dataset file as returned by
+/// [`DatasetFile::path`]: the one recorded when a TSV was decompressed, or the ETag of a kept
+/// archive. `None` when neither was recorded.
+pub fn recorded_fingerprint(path: &Path) -> Option<String> {
+    let sidecar = if path.extension().is_some_and(|extension| extension == "gz") {
+        "etag"
+    } else {
+        "source"
+    };
+    let mut sidecar_path = path.as_os_str().to_owned();
+    sidecar_path.push(format!(".{sidecar}"));
+    let fingerprint = std::fs::read_to_string(sidecar_path).ok()?;
+    let fingerprint = fingerprint.trim();
+    (!fingerprint.is_empty()).then(|| fingerprint.to_string())
+}
+
+/// Opens a dataset file, streaming it through a gzip decoder when it has a `.gz` extension.
+pub fn open_dataset(path: &Path) -> Result<Box<dyn Read + Send>> {
+    let input =
+        BufReader::new(File::open(path).with_context(|| format!("opening {}", path.display()))?);
+    if path.extension().is_some_and(|extension| extension == "gz") {
+        Ok(Box::new(GzDecoder::new(input)))
+    } else {
+        Ok(Box::new(input))
+    }
+}
+
+/// Archives to prepare under `config`: every one in [`DATASET_FILES`] with
+/// `IMDB_DOWNLOAD_ALL_DATASETS`, otherwise only those the indexer reads.
+pub fn configured_datasets(config: &AppConfig) -> Vec<&'static str> {
+    if config.download_all_datasets {
+        DATASET_FILES.to_vec()
+    } else {
+        crate::indexer::required_datasets(config)
+    }
 }
 
-/// Downloads and decompresses all IMDb datasets, returning the local file mapping.
-pub async fn prepare_datasets(config: &AppConfig) -> Result<Vec<DatasetFile>> {
+/// Downloads the named IMDb datasets (see [`DATASET_FILES`]) and, unless
+/// `IMDB_DECOMPRESS_DATASETS` is off, decompresses them, returning the local file mapping.
+pub async fn prepare_datasets(
+    config: &AppConfig,
+    names: &[&'static str],
+) -> Result<Vec<DatasetFile>> {
     fs::create_dir_all(&config.data_dir)
         .await
         .with_context(|| format!("creating data directory at {}", config.data_dir.display()))?;
 
-    let mut files: Vec<DatasetFile> = Vec::new();
-    for name in DATASET_FILES {
-        files.push(DatasetFile::new(&config.data_dir, name));
-    }
+    let files: Vec<DatasetFile> = names
+        .iter()
+        .map(|name| DatasetFile::new(&config.data_dir, name))
+        .collect();
 
-    download_missing_files(&files).await?;
-    decompress_archives(&files).await?;
+    let refreshed = download_missing_files(&files, config).await?;
+    if !refreshed.is_empty() {
+        info!(datasets = ?refreshed, "downloaded newer dataset versions");
+    }
+    if config.decompress_datasets {
+        decompress_archives(&files).await?;
+    } else {
+        debug!("keeping dataset archives compressed");
+    }
 
     Ok(files)
 }
 
-async fn download_missing_files(files: &[DatasetFile]) -> Result<()> {
+/// How failed downloads are retried.
+#[derive(Debug, Clone, Copy)]
+struct RetryPolicy {
+    attempts: usize,
+    base_delay: Duration,
+}
+
+impl RetryPolicy {
+    fn from_config(config: &AppConfig) -> Self {
+        Self {
+            attempts: config.download_attempts.max(1),
+            base_delay: Duration::from_millis(config.download_retry_delay_ms),
+        }
+    }
+
+    /// Exponential backoff with up to 50% jitter before retry number `retry` (starting at 1).
+    fn delay(&self, retry: usize) -> Duration {
+        let doublings = (retry.saturating_sub(1) as u32).min(MAX_BACKOFF_DOUBLINGS);
+        let backoff = self.base_delay * 2u32.pow(doublings);
+        backoff + backoff.mul_f64(rand::thread_rng().gen_range(0.0..0.5))
+    }
+}
+
+/// A download failure that retrying cannot fix, such as a `404`.
+#[derive(Debug)]
+struct PermanentDownloadError(String);
+
+impl fmt::Display for PermanentDownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for PermanentDownloadError {}
+
+/// Downloads the archives that are neither downloaded nor decompressed yet, and re-downloads
+/// those the server reports as changed when a check is due under `IMDB_REFRESH`. At most
+/// `IMDB_DOWNLOAD_CONCURRENCY` downloads run at a time. The first failure is returned and cancels
+/// the other downloads; archives already moved into place are kept.
+///
+/// Returns the names of the archives that replaced an existing local copy.
+async fn download_missing_files(
+    files: &[DatasetFile],
+    config: &AppConfig,
+) -> Result<Vec<&'static str>> {
     let client = reqwest::Client::new();
-    for file in files {
-        if file.gz_path.exists() {
-            debug!(path = %file.gz_path.display(), "dataset already downloaded");
-            continue;
+    let retry = RetryPolicy::from_config(config);
+    // Built up front so the stream does not hold a closure, which keeps this future `Send`
+    // for callers that spawn it.
+    let syncs: Vec<_> = files
+        .iter()
+        .map(|file| sync_file(&client, config, file, retry))
+        .collect();
+    stream::iter(syncs)
+        .buffer_unordered(config.download_concurrency.max(1))
+        .try_filter_map(future::ok)
+        .try_collect()
+        .await
+}
+
+/// Downloads one archive unless an up-to-date local copy exists, returning its name when an
+/// existing copy was replaced.
+async fn sync_file(
+    client: &reqwest::Client,
+    config: &AppConfig,
+    file: &DatasetFile,
+    retry: RetryPolicy,
+) -> Result<Option<&'static str>> {
+    let local = if file.gz_path.exists() {
+        Some(&file.gz_path)
+    } else if file.tsv_path.exists() {
+        Some(&file.tsv_path)
+    } else {
+        None
+    };
+    let Some(local) = local else {
+        download_file(client, &config.base_url, file, retry).await?;
+        return Ok(None);
+    };
+
+    let age = fs::metadata(local)
+        .await
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .unwrap_or_default();
+    if !config.refresh.is_due(age) {
+        debug!(path = %local.display(), "dataset already downloaded");
+        return Ok(None);
+    }
+    match remote_is_newer(client, &config.base_url, file, local).await {
+        Ok(false) => {
+            debug!(path = %local.display(), "dataset up to date");
+            Ok(None)
         }
+        Ok(true) => {
+            info!(path = %local.display(), "newer dataset published; downloading again");
+            download_file(client, &config.base_url, file, retry).await?;
+            Ok(Some(file.name))
+        }
+        Err(err) => {
+            warn!(
+                path = %local.display(),
+                error = %format!("{err:#}"),
+                "failed to check for a newer dataset; keeping the local copy"
+            );
+            Ok(None)
+        }
+    }
+}
 
-        if file.tsv_path.exists() {
-            debug!(path = %file.tsv_path.display(), "dataset already prepared");
-            continue;
+/// Asks the server whether the archive changed since the local copy, using a conditional `HEAD`
+/// request on the recorded ETag and the local modification time.
+async fn remote_is_newer(
+    client: &reqwest::Client,
+    base_url: &str,
+    file: &DatasetFile,
+    local: &Path,
+) -> Result<bool> {
+    let url = dataset_url(base_url, file.name);
+    let etag = recorded_etag(file).await;
+    let mut request = client.head(&url);
+    if let Some(etag) = &etag {
+        request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+    }
+    if let Ok(modified) = fs::metadata(local)
+        .await
+        .and_then(|metadata| metadata.modified())
+    {
+        request = request.header(reqwest::header::IF_MODIFIED_SINCE, http_date(modified));
+    }
+
+    let resp = request
+        .send()
+        .await
+        .with_context(|| format!("requesting {url}"))?;
+    let status = resp.status();
+    if status == reqwest::StatusCode::NOT_MODIFIED {
+        return Ok(false);
+    }
+    if !status.is_success() {
+        anyhow::bail!("checking {url} for updates: status {status}");
+    }
+    // Servers that ignore conditional headers still reveal an unchanged archive by its ETag.
+    let remote_etag = resp
+        .headers()
+        .get(reqwest::header::ETAG)
+        .and_then(|value| value.to_str().ok());
+    Ok(remote_etag.is_none() || remote_etag != etag.as_deref())
+}
+
+/// ETag of the local copy: the sidecar of a kept archive, or the fingerprint recorded when it
+/// was decompressed.
+async fn recorded_etag(file: &DatasetFile) -> Option<String> {
+    for path in [&file.etag_path, &file.source_path] {
+        if let Ok(value) = fs::read_to_string(path).await {
+            let value = value.trim();
+            let etag = value.strip_prefix("etag:").unwrap_or(value);
+            if !etag.is_empty() && !etag.starts_with("crc32:") {
+                return Some(etag.to_string());
+            }
         }
+    }
+    None
+}
+
+/// Formats a timestamp as an HTTP date, e.g. `Sun, 06 Nov 1994 08:49:37 GMT`.
+fn http_date(time: SystemTime) -> String {
+    chrono::DateTime::<chrono::Utc>::from(time)
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
 
-        let url = format!("{}/{}", IMDB_BASE_URL, file.name);
+fn dataset_url(base_url: &str, name: &str) -> String {
+    format!("{}/{}", base_url.trim_end_matches('/'), name)
+}
+
+/// Downloads one archive, retrying network errors, `5xx` responses and failed verification
+/// with exponential backoff. Permanent failures such as a `404` are returned immediately.
+async fn download_file(
+    client: &reqwest::Client,
+    base_url: &str,
+    file: &DatasetFile,
+    retry: RetryPolicy,
+) -> Result<()> {
+    let mut attempt = 1;
+    loop {
+        match fetch_archive(client, base_url, file).await {
+            Ok(()) => return Ok(()),
+            Err(err)
+                if attempt < retry.attempts
+                    && err.downcast_ref::<PermanentDownloadError>().is_none() =>
+            {
+                let delay = retry.delay(attempt);
+                warn!(
+                    path = %file.gz_path.display(),
+                    error = %format!("{err:#}"),
+                    attempt,
+                    delay_ms = delay.as_millis() as u64,
+                    "dataset download failed; retrying"
+                );
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Streams one archive into a temporary file and renames it into place once it has been
+/// verified with [`verify_archive`].
+///
+/// A temporary file left by an interrupted download is resumed with a `Range` request guarded
+/// by `If-Range` on the ETag recorded when it started. Servers that answer with the full body
+/// instead of `206 Partial Content` restart the file from scratch.
+async fn fetch_archive(client: &reqwest::Client, base_url: &str, file: &DatasetFile) -> Result<()> {
+    let url = dataset_url(base_url, file.name);
+    let mut tmp_path = file.gz_path.clone();
+    tmp_path.set_extension("tmp-download");
+
+    let partial = partial_download(&tmp_path, &file.etag_path).await;
+    let mut request = client.get(&url);
+    if let Some((offset, etag)) = &partial {
+        info!(%url, path = %file.gz_path.display(), offset, "resuming dataset download");
+        request = request
+            .header(reqwest::header::RANGE, format!("bytes={offset}-"))
+            .header(reqwest::header::IF_RANGE, etag);
+    } else {
         info!(%url, path = %file.gz_path.display(), "downloading dataset");
+    }
 
-        let resp = client
-            .get(&url)
-            .send()
-            .await
-            .with_context(|| format!("requesting {}", url))?;
+    let resp = request
+        .send()
+        .await
+        .with_context(|| format!("requesting {}", url))?;
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::RANGE_NOT_SATISFIABLE {
+        let _ = fs::remove_file(&tmp_path).await;
+        anyhow::bail!("server rejected resuming {url}; discarded the partial download");
+    }
+    let retryable = status.is_server_error()
+        || matches!(
+            status,
+            reqwest::StatusCode::REQUEST_TIMEOUT | reqwest::StatusCode::TOO_MANY_REQUESTS
+        );
+    if !status.is_success() && !retryable {
+        return Err(
+            PermanentDownloadError(format!("failed to download {url}: status {status}")).into(),
+        );
+    }
+    if !status.is_success() {
+        anyhow::bail!("failed to download {}: status {}", url, status);
+    }
+
+    let header = |name: &str| {
+        resp.headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let etag = header(reqwest::header::ETAG.as_str());
+    let sha256 = SHA256_HEADERS.iter().find_map(|name| header(name));
 
-        if !resp.status().is_success() {
-            anyhow::bail!("failed to download {}: status {}", url, resp.status());
+    let resumed_from = match (&partial, status) {
+        (Some((offset, _)), reqwest::StatusCode::PARTIAL_CONTENT) => {
+            let start = header(reqwest::header::CONTENT_RANGE.as_str())
+                .as_deref()
+                .and_then(content_range_start);
+            if start != Some(*offset) {
+                let _ = fs::remove_file(&tmp_path).await;
+                anyhow::bail!(
+                    "unexpected Content-Range resuming {url}; discarded the partial download"
+                );
+            }
+            Some(*offset)
         }
+        (Some(_), _) => {
+            info!(%url, "server sent the whole archive; restarting download");
+            None
+        }
+        (None, _) => None,
+    };
+    let expected_len = resp
+        .content_length()
+        .map(|len| len + resumed_from.unwrap_or(0));
 
-        let mut stream = resp.bytes_stream();
-        let mut tmp_path = file.gz_path.clone();
-        tmp_path.set_extension("tmp-download");
-        let mut dest = fs::File::create(&tmp_path)
+    let mut dest = if resumed_from.is_some() {
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&tmp_path)
             .await
-            .with_context(|| format!("creating {}", tmp_path.display()))?;
+            .with_context(|| format!("opening {}", tmp_path.display()))?
+    } else {
+        // Recorded up front so an interrupted download can later be resumed against it.
+        write_sidecar(&file.etag_path, etag.clone()).await?;
+        fs::File::create(&tmp_path)
+            .await
+            .with_context(|| format!("creating {}", tmp_path.display()))?
+    };
 
+    let mut stream = resp.bytes_stream();
+    let written: Result<()> = async {
         while let Some(chunk) = stream.try_next().await? {
             dest.write_all(&chunk).await?;
         }
         dest.flush().await?;
-        drop(dest);
+        Ok(())
+    }
+    .await;
+    drop(dest);
+    // Keep what arrived so the next attempt can resume from it.
+    written.with_context(|| format!("downloading {url} (partial data kept for resuming)"))?;
+
+    let path = tmp_path.clone();
+    let verified = task::spawn_blocking(move || verify_archive(&path, expected_len))
+        .await
+        .context("joining verification task")?;
+    if let Err(err) = verified {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(err.context(format!("downloading {url}")));
+    }
+
+    fs::rename(&tmp_path, &file.gz_path)
+        .await
+        .with_context(|| format!("moving download into place for {}", file.gz_path.display()))?;
+
+    write_sidecar(&file.etag_path, etag).await?;
+    write_sidecar(&file.sha256_path, sha256).await?;
+    Ok(())
+}
+
+/// Length of a resumable temporary download and the ETag it was started against.
+async fn partial_download(tmp_path: &Path, etag_path: &Path) -> Option<(u64, String)> {
+    let len = fs::metadata(tmp_path).await.ok()?.len();
+    let etag = fs::read_to_string(etag_path).await.ok()?;
+    let etag = etag.trim();
+    (len > 0 && !etag.is_empty()).then(|| (len, etag.to_string()))
+}
+
+/// First byte position of a `Content-Range: bytes <start>-<end>/<total>` header.
+fn content_range_start(value: &str) -> Option<u64> {
+    let range = value.trim().strip_prefix("bytes ")?;
+    let (start, _) = range.split_once('-')?;
+    start.trim().parse().ok()
+}
 
-        fs::rename(&tmp_path, &file.gz_path)
+/// Writes `value` to a sidecar file, or removes a stale one when there is no value.
+async fn write_sidecar(path: &Path, value: Option<String>) -> Result<()> {
+    match value {
+        Some(value) => fs::write(path, value)
             .await
-            .with_context(|| {
-                format!("moving download into place for {}", file.gz_path.display())
-            })?;
+            .with_context(|| format!("writing {}", path.display()))?,
+        None => {
+            if let Err(err) = fs::remove_file(path).await
+                && err.kind() != std::io::ErrorKind::NotFound
+            {
+                warn!(path = %path.display(), error = %err, "failed to clear stale sidecar");
+            }
+        }
     }
     Ok(())
 }
 
+/// Checks that an archive has the advertised length and decompresses through to its gzip
+/// trailer (whose CRC the decoder verifies), so truncated or corrupted downloads never reach
+/// the indexer.
+fn verify_archive(path: &Path, expected_len: Option<u64>) -> Result<()> {
+    let len = std::fs::metadata(path)
+        .with_context(|| format!("reading metadata of {}", path.display()))?
+        .len();
+    if let Some(expected) = expected_len
+        && len != expected
+    {
+        anyhow::bail!(
+            "{} is {len} bytes but the server announced {expected}",
+            path.display()
+        );
+    }
+
+    let input = File::open(path).with_context(|| format!("opening {}", path.display()))?;
+    let mut decoder = GzDecoder::new(BufReader::new(input));
+    std::io::copy(&mut decoder, &mut std::io::sink())
+        .with_context(|| format!("{} is not a complete gzip archive", path.display()))?;
+    Ok(())
+}
+
 async fn decompress_archives(files: &[DatasetFile]) -> Result<()> {
     for file in files {
         if !file.gz_path.exists() {
@@ -129,36 +590,31 @@ async fn decompress_archives(files: &[DatasetFile]) -> Result<()> {
             continue;
         }
 
-        if file.tsv_path.exists() {
-            let gz_meta = fs::metadata(&file.gz_path).await.ok();
-            let tsv_meta = fs::metadata(&file.tsv_path).await.ok();
-            if let (Some(gz), Some(tsv)) = (gz_meta, tsv_meta)
-                && let (Ok(gz_time), Ok(tsv_time)) = (gz.modified(), tsv.modified())
-                && gz_time <= tsv_time
-            {
-                debug!(path = %file.tsv_path.display(), "decompression up to date");
-                if let Err(err) = fs::remove_file(&file.gz_path).await {
-                    warn!(
-                        path = %file.gz_path.display(),
-                        error = %err,
-                        "failed to remove compressed archive"
-                    );
-                }
-                continue;
-            }
-        }
+        let owned = file.clone();
+        let fingerprint = task::spawn_blocking(move || archive_fingerprint(&owned))
+            .await
+            .context("joining fingerprint task")??;
 
-        let gz_path = file.gz_path.clone();
-        let tsv_path = file.tsv_path.clone();
-        info!(
-            gz = %gz_path.display(),
-            tsv = %tsv_path.display(),
-            "decompressing dataset"
-        );
+        let recorded = fs::read_to_string(&file.source_path).await.ok();
+        if file.tsv_path.exists() && recorded.as_deref().map(str::trim) == Some(&fingerprint) {
+            debug!(path = %file.tsv_path.display(), "decompression up to date");
+        } else {
+            let gz_path = file.gz_path.clone();
+            let tsv_path = file.tsv_path.clone();
+            info!(
+                gz = %gz_path.display(),
+                tsv = %tsv_path.display(),
+                "decompressing dataset"
+            );
 
-        task::spawn_blocking(move || decompress_sync(&gz_path, &tsv_path))
-            .await
-            .context("joining decompression task")??;
+            task::spawn_blocking(move || decompress_sync(&gz_path, &tsv_path))
+                .await
+                .context("joining decompression task")??;
+
+            fs::write(&file.source_path, &fingerprint)
+                .await
+                .with_context(|| format!("writing {}", file.source_path.display()))?;
+        }
 
         if let Err(err) = fs::remove_file(&file.gz_path).await {
             warn!(
@@ -169,10 +625,42 @@ async fn decompress_archives(files: &[DatasetFile]) -> Result<()> {
         } else {
             debug!(path = %file.gz_path.display(), "removed compressed archive");
         }
+        let _ = fs::remove_file(&file.etag_path).await;
+        let _ = fs::remove_file(&file.sha256_path).await;
     }
     Ok(())
 }
 
+/// Identifies an archive by its download ETag, falling back to a CRC32 of its contents, so
+/// freshness checks survive copies and snapshots that reset modification times.
+fn archive_fingerprint(file: &DatasetFile) -> Result<String> {
+    if let Ok(etag) = std::fs::read_to_string(&file.etag_path) {
+        let etag = etag.trim();
+        if !etag.is_empty() {
+            return Ok(format!("etag:{etag}"));
+        }
+    }
+
+    let mut input = BufReader::new(
+        File::open(&file.gz_path)
+            .with_context(|| format!("opening archive {}", file.gz_path.display()))?,
+    );
+    let mut crc = Crc::new();
+    let mut len = 0u64;
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let read = input
+            .read(&mut buf)
+            .with_context(|| format!("hashing {}", file.gz_path.display()))?;
+        if read == 0 {
+            break;
+        }
+        crc.update(&buf[..read]);
+        len += read as u64;
+    }
+    Ok(format!("crc32:{:08x}:{len}", crc.sum()))
+}
+
 fn decompress_sync(gz_path: &Path, tsv_path: &Path) -> Result<()> {
     let input =
         File::open(gz_path).with_context(|| format!("opening archive {}", gz_path.display()))?;
@@ -188,3 +676,343 @@ fn decompress_sync(gz_path: &Path, tsv_path: &Path) -> Result<()> {
     writer.flush()?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::extract::Path as AxumPath;
+    use axum::http::{HeaderMap, StatusCode, header};
+    use axum::response::IntoResponse;
+    use flate2::Compression;
+    use flate2::write::GzEncoder;
+
+    use super::*;
+
+    fn write_archive(path: &Path, contents: &str) {
+        let mut encoder = GzEncoder::new(File::create(path).unwrap(), Compression::fast());
+        encoder.write_all(contents.as_bytes()).unwrap();
+        encoder.finish().unwrap();
+    }
+
+    fn set_mtime(path: &Path, time: SystemTime) {
+        File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(time)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn freshness_ignores_reset_modification_times() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-datasets-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = DatasetFile::new(&dir, "title.ratings.tsv.gz");
+        let past = SystemTime::now() - Duration::from_secs(3600);
+
+        write_archive(&file.gz_path, "v1");
+        decompress_archives(std::slice::from_ref(&file))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file.tsv_path).unwrap(), "v1");
+
+        // Same archive restored with a newer mtime: no spurious re-decompression.
+        std::fs::write(&file.tsv_path, "kept").unwrap();
+        write_archive(&file.gz_path, "v1");
+        set_mtime(&file.tsv_path, past);
+        decompress_archives(std::slice::from_ref(&file))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file.tsv_path).unwrap(), "kept");
+
+        // New archive with an older mtime than the TSV must still be decompressed.
+        write_archive(&file.gz_path, "v2");
+        set_mtime(&file.gz_path, past);
+        decompress_archives(std::slice::from_ref(&file))
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file.tsv_path).unwrap(), "v2");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn datasets_are_read_from_the_archive_or_the_tsv() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-open-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = DatasetFile::new(&dir, "title.ratings.tsv.gz");
+        let read = |file: &DatasetFile| {
+            let mut contents = String::new();
+            file.open().unwrap().read_to_string(&mut contents).unwrap();
+            contents
+        };
+
+        std::fs::write(&file.tsv_path, "stale").unwrap();
+        write_archive(&file.gz_path, "tconst\taverageRating\tnumVotes\n");
+        assert_eq!(file.path(), file.gz_path);
+        assert_eq!(read(&file), "tconst\taverageRating\tnumVotes\n");
+
+        std::fs::remove_file(&file.gz_path).unwrap();
+        assert_eq!(file.path(), file.tsv_path);
+        assert_eq!(read(&file), "stale");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    const FIXTURE_ETAG: &str = "\"v1\"";
+
+    /// Serves `archive` as `/title.ratings.tsv.gz` (anything else is a 404), honouring `Range`
+    /// requests whose `If-Range` matches [`FIXTURE_ETAG`]. Returns the base URL and the `Range`
+    /// header of every request received.
+    async fn serve_archive(archive: Vec<u8>) -> (String, Arc<Mutex<Vec<Option<String>>>>) {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        let app = axum::Router::new().route(
+            "/{name}",
+            axum::routing::get(
+                move |AxumPath(name): AxumPath<String>, headers: HeaderMap| {
+                    let archive = archive.clone();
+                    let log = log.clone();
+                    async move {
+                        let range = headers
+                            .get(header::RANGE)
+                            .and_then(|value| value.to_str().ok())
+                            .map(str::to_string);
+                        log.lock().unwrap().push(range.clone());
+                        if name != "title.ratings.tsv.gz" {
+                            return StatusCode::NOT_FOUND.into_response();
+                        }
+                        if headers
+                            .get(header::IF_NONE_MATCH)
+                            .is_some_and(|v| v == FIXTURE_ETAG)
+                        {
+                            return StatusCode::NOT_MODIFIED.into_response();
+                        }
+                        let start = range
+                            .as_deref()
+                            .and_then(|value| value.strip_prefix("bytes="))
+                            .and_then(|value| value.strip_suffix('-'))
+                            .and_then(|value| value.parse::<usize>().ok())
+                            .filter(|_| {
+                                headers
+                                    .get(header::IF_RANGE)
+                                    .is_some_and(|v| v == FIXTURE_ETAG)
+                            });
+                        match start {
+                            Some(start) => (
+                                StatusCode::PARTIAL_CONTENT,
+                                [
+                                    (header::ETAG, FIXTURE_ETAG.to_string()),
+                                    (
+                                        header::CONTENT_RANGE,
+                                        format!(
+                                            "bytes {start}-{}/{}",
+                                            archive.len() - 1,
+                                            archive.len()
+                                        ),
+                                    ),
+                                ],
+                                archive[start..].to_vec(),
+                            )
+                                .into_response(),
+                            None => (
+                                StatusCode::OK,
+                                [(header::ETAG, FIXTURE_ETAG.to_string())],
+                                archive,
+                            )
+                                .into_response(),
+                        }
+                    }
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}/"), requests)
+    }
+
+    fn fixture_config(dir: &Path, base_url: String) -> AppConfig {
+        AppConfig {
+            data_dir: dir.to_path_buf(),
+            base_url,
+            download_retry_delay_ms: 1,
+            ..AppConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn downloads_resume_from_the_configured_mirror() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-mirror-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.gz");
+        write_archive(&source, &"tt0000001\t5.7\t2000\n".repeat(500));
+        let archive = std::fs::read(&source).unwrap();
+        let (base_url, requests) = serve_archive(archive.clone()).await;
+        let config = fixture_config(&dir, base_url);
+        let file = DatasetFile::new(&dir, "title.ratings.tsv.gz");
+
+        download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&file.gz_path).unwrap(), archive);
+        assert_eq!(
+            std::fs::read_to_string(&file.etag_path).unwrap(),
+            FIXTURE_ETAG
+        );
+
+        // An interrupted download picks up where the temporary file ends.
+        std::fs::remove_file(&file.gz_path).unwrap();
+        let half = archive.len() / 2;
+        let mut tmp_path = file.gz_path.clone();
+        tmp_path.set_extension("tmp-download");
+        std::fs::write(&tmp_path, &archive[..half]).unwrap();
+        download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read(&file.gz_path).unwrap(), archive);
+        assert!(!tmp_path.exists());
+        assert_eq!(
+            *requests.lock().unwrap(),
+            [None, Some(format!("bytes={half}-"))]
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn refresh_downloads_archives_the_server_reports_as_changed() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-refresh-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("source.gz");
+        write_archive(&source, "tt0000001\t5.7\t2000\n");
+        let (base_url, requests) = serve_archive(std::fs::read(&source).unwrap()).await;
+        let mut config = fixture_config(&dir, base_url);
+        let file = DatasetFile::new(&dir, "title.ratings.tsv.gz");
+
+        // Decompressed from the archive the server still serves.
+        std::fs::write(&file.tsv_path, "v1").unwrap();
+        std::fs::write(&file.source_path, format!("etag:{FIXTURE_ETAG}")).unwrap();
+        let refreshed = download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap();
+        assert!(refreshed.is_empty());
+        assert!(requests.lock().unwrap().is_empty(), "no check by default");
+
+        config.refresh = RefreshPolicy::Daily;
+        let refreshed = download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap();
+        assert!(refreshed.is_empty());
+        assert!(
+            requests.lock().unwrap().is_empty(),
+            "copy is less than a day old"
+        );
+
+        config.refresh = RefreshPolicy::Always;
+        let refreshed = download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap();
+        assert!(refreshed.is_empty());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(!file.gz_path.exists());
+
+        // Decompressed from an older archive: the server's ETag no longer matches.
+        std::fs::write(&file.source_path, "etag:\"v0\"").unwrap();
+        let refreshed = download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap();
+        assert_eq!(refreshed, ["title.ratings.tsv.gz"]);
+        assert_eq!(
+            std::fs::read(&file.gz_path).unwrap(),
+            std::fs::read(&source).unwrap()
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn refresh_policies_parse_and_schedule_checks() {
+        assert_eq!(
+            "daily".parse::<RefreshPolicy>().unwrap(),
+            RefreshPolicy::Daily
+        );
+        assert!("hourly".parse::<RefreshPolicy>().is_err());
+        assert!(!RefreshPolicy::Never.is_due(Duration::from_secs(365 * 24 * 3600)));
+        assert!(!RefreshPolicy::Daily.is_due(Duration::from_secs(3600)));
+        assert!(RefreshPolicy::Daily.is_due(DAILY_REFRESH_AGE));
+        assert!(RefreshPolicy::Always.is_due(Duration::ZERO));
+        assert_eq!(
+            http_date(SystemTime::UNIX_EPOCH + Duration::from_secs(784_111_777)),
+            "Sun, 06 Nov 1994 08:49:37 GMT"
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_archives_fail_without_retrying() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-missing-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let (base_url, requests) = serve_archive(Vec::new()).await;
+        let config = fixture_config(&dir, base_url);
+        let file = DatasetFile::new(&dir, "title.crew.tsv.gz");
+
+        let err = download_missing_files(std::slice::from_ref(&file), &config)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("404"), "{err:#}");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+        assert!(!file.gz_path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn retry_delays_double_with_bounded_jitter() {
+        let retry = RetryPolicy {
+            attempts: 5,
+            base_delay: Duration::from_millis(100),
+        };
+        for (attempt, backoff) in [(1, 100), (2, 200), (3, 400), (10, 3_200)] {
+            let delay = retry.delay(attempt);
+            let backoff = Duration::from_millis(backoff);
+            assert!(
+                delay >= backoff && delay < backoff.mul_f64(1.5),
+                "{delay:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn content_range_start_reads_the_first_byte() {
+        assert_eq!(content_range_start("bytes 1024-2047/2048"), Some(1024));
+        assert_eq!(content_range_start("bytes 0-99/*"), Some(0));
+        assert_eq!(content_range_start("bytes */2048"), None);
+        assert_eq!(content_range_start("items 1-2/3"), None);
+    }
+
+    #[test]
+    fn truncated_archives_fail_verification() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-verify-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("title.ratings.tsv.gz");
+
+        write_archive(&path, &"tt0000001\t5.7\t2000\n".repeat(500));
+        let len = std::fs::metadata(&path).unwrap().len();
+        verify_archive(&path, Some(len)).unwrap();
+        verify_archive(&path, None).unwrap();
+        assert!(verify_archive(&path, Some(len + 1)).is_err());
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::write(&path, &bytes[..bytes.len() - 10]).unwrap();
+        assert!(verify_archive(&path, None).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,29 +1,415 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::io::Read;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result, anyhow};
-use csv::ReaderBuilder;
-use tantivy::query::QueryParser;
+use chrono::{DateTime, SecondsFormat, Utc};
+use csv::{Reader, ReaderBuilder, StringRecord};
+use serde::{Deserialize, Serialize};
+use tantivy::collector::Count;
+use tantivy::query::{QueryParser, TermQuery};
 use tantivy::schema::{
-    Field, IndexRecordOption, NumericOptions, STORED, STRING, Schema, TEXT, TantivyDocument,
+    FAST, Field, IndexRecordOption, NumericOptions, STORED, STRING, Schema, TEXT, TantivyDocument,
     TextFieldIndexing, TextOptions,
 };
-use tantivy::{Index, IndexReader, ReloadPolicy};
+use tantivy::store::{Compressor, ZstdCompressor};
+use tantivy::tokenizer::{LowerCaser, NgramTokenizer, TextAnalyzer, TokenizerManager};
+use tantivy::{Index, IndexReader, IndexSettings, IndexWriter, ReloadPolicy, Term};
 use tokio::fs;
 use tokio::task;
-use tracing::info;
+use tracing::{debug, info, warn};
 
 use crate::config::AppConfig;
-use crate::datasets::DatasetFile;
+use crate::datasets::{DatasetFile, open_dataset, recorded_fingerprint};
 
 const TITLE_INDEX_SUBDIR: &str = "titles";
 const NAME_INDEX_SUBDIR: &str = "names";
 
+/// Leading columns of each dataset, in the order the loaders read them by position. Files may
+/// carry extra columns after these.
+const TITLE_BASICS_HEADER: &[&str] = &[
+    "tconst",
+    "titleType",
+    "primaryTitle",
+    "originalTitle",
+    "isAdult",
+    "startYear",
+    "endYear",
+    "runtimeMinutes",
+    "genres",
+];
+const NAME_BASICS_HEADER: &[&str] = &[
+    "nconst",
+    "primaryName",
+    "birthYear",
+    "deathYear",
+    "primaryProfession",
+    "knownForTitles",
+];
+const TITLE_RATINGS_HEADER: &[&str] = &["tconst", "averageRating", "numVotes"];
+const TITLE_AKAS_HEADER: &[&str] = &["titleId", "ordering", "title", "region", "language"];
+const TITLE_EPISODE_HEADER: &[&str] = &["tconst", "parentTconst", "seasonNumber", "episodeNumber"];
+const TITLE_CREW_HEADER: &[&str] = &["tconst", "directors", "writers"];
+const TITLE_PRINCIPALS_HEADER: &[&str] = &[
+    "tconst",
+    "ordering",
+    "nconst",
+    "category",
+    "job",
+    "characters",
+];
+
+/// Minimum columns in a well-formed title.basics row.
+const TITLE_BASICS_COLUMNS: usize = TITLE_BASICS_HEADER.len();
+/// Minimum columns in a well-formed name.basics row.
+const NAME_BASICS_COLUMNS: usize = NAME_BASICS_HEADER.len();
+/// title.basics rows handed to an indexing worker at a time.
+const TITLE_BATCH_SIZE: usize = 1_024;
+
+/// Bumped whenever the title or name schema changes in a way existing indexes cannot serve.
+pub const TITLE_SCHEMA_VERSION: u32 = 6;
+pub const NAME_SCHEMA_VERSION: u32 = 3;
+
+/// File written next to the tantivy files of each index, describing the build.
+const BUILD_META_FILE: &str = "build_meta.json";
+
+/// Tokenizer backing the optional `primaryTitleNgram` infix field.
+pub const TITLE_NGRAM_TOKENIZER: &str = "title_ngram";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NgramOptions {
+    pub min_gram: usize,
+    pub max_gram: usize,
+}
+
+/// What to do with dataset rows that have fewer columns than expected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MalformedRowPolicy {
+    /// Skip the row and report the number skipped once indexing finishes.
+    #[default]
+    Skip,
+    /// Abort indexing on the first malformed row.
+    Fail,
+}
+
+impl std::str::FromStr for MalformedRowPolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "skip" => Ok(MalformedRowPolicy::Skip),
+            "fail" => Ok(MalformedRowPolicy::Fail),
+            other => Err(anyhow!("unknown malformed row policy `{other}`")),
+        }
+    }
+}
+
+/// Largest edit distance a fuzzy query may use.
+pub const MAX_FUZZY_DISTANCE: u8 = 2;
+
+/// Edits per term tolerated by typo-tolerant title and name queries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FuzzyDistance {
+    /// Scaled to each query word: exact up to 3 characters, one edit up to 7 and two beyond, so
+    /// short words stop matching unrelated short words.
+    #[default]
+    Auto,
+    /// The same distance for every query, at most [`MAX_FUZZY_DISTANCE`].
+    Fixed(u8),
+}
+
+impl FuzzyDistance {
+    /// Distance to use for one query term. Terms the tokenizer splits further, such as
+    /// `Sci-Fi`, are scaled to their longest word.
+    pub fn for_term(self, text: &str) -> u8 {
+        match self {
+            FuzzyDistance::Fixed(distance) => distance,
+            FuzzyDistance::Auto => {
+                let longest = text
+                    .split(|c: char| !c.is_alphanumeric())
+                    .map(|word| word.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                match longest {
+                    0..=3 => 0,
+                    4..=7 => 1,
+                    _ => 2,
+                }
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for FuzzyDistance {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "auto" {
+            return Ok(FuzzyDistance::Auto);
+        }
+        match value.parse::<u8>() {
+            Ok(distance) if distance <= MAX_FUZZY_DISTANCE => Ok(FuzzyDistance::Fixed(distance)),
+            _ => Err(anyhow!(
+                "unknown fuzzy distance `{value}`; expected `auto` or 0–{MAX_FUZZY_DISTANCE}"
+            )),
+        }
+    }
+}
+
+named_enum! {
+    /// Vote-count bucket stored with each rated title, so tier filters are single term lookups.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum PopularityTier("popularity tier") {
+        /// At least 1,000,000 votes.
+        Blockbuster = "blockbuster",
+        /// 100,000 to 999,999 votes.
+        Popular = "popular",
+        /// 10,000 to 99,999 votes.
+        Niche = "niche",
+        /// Fewer than 10,000 votes.
+        Indie = "indie",
+    }
+}
+
+impl PopularityTier {
+    pub fn for_votes(votes: i64) -> Self {
+        match votes {
+            1_000_000.. => PopularityTier::Blockbuster,
+            100_000..=999_999 => PopularityTier::Popular,
+            10_000..=99_999 => PopularityTier::Niche,
+            _ => PopularityTier::Indie,
+        }
+    }
+}
+
+/// Compression applied to the stored-field docstore when an index is created.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocstoreCompression {
+    None,
+    /// Tantivy's default: fast to read, moderate ratio.
+    #[default]
+    Lz4,
+    /// Smaller on disk but slower to read back; `None` uses zstd's default level.
+    Zstd(Option<i32>),
+}
+
+impl DocstoreCompression {
+    fn compressor(self) -> Compressor {
+        match self {
+            DocstoreCompression::None => Compressor::None,
+            DocstoreCompression::Lz4 => Compressor::Lz4,
+            DocstoreCompression::Zstd(compression_level) => {
+                Compressor::Zstd(ZstdCompressor { compression_level })
+            }
+        }
+    }
+
+    fn index_settings(self) -> IndexSettings {
+        IndexSettings {
+            docstore_compression: self.compressor(),
+            ..IndexSettings::default()
+        }
+    }
+}
+
+impl std::fmt::Display for DocstoreCompression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DocstoreCompression::None => f.write_str("none"),
+            DocstoreCompression::Lz4 => f.write_str("lz4"),
+            DocstoreCompression::Zstd(None) => f.write_str("zstd"),
+            DocstoreCompression::Zstd(Some(level)) => write!(f, "zstd:{level}"),
+        }
+    }
+}
+
+impl std::str::FromStr for DocstoreCompression {
+    type Err = anyhow::Error;
+
+    /// Accepts `none`, `lz4`, `zstd` or `zstd:<level>`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            None if value == "none" => Ok(DocstoreCompression::None),
+            None if value == "lz4" => Ok(DocstoreCompression::Lz4),
+            None if value == "zstd" => Ok(DocstoreCompression::Zstd(None)),
+            Some(("zstd", level)) => {
+                let level = level
+                    .parse()
+                    .with_context(|| format!("invalid zstd level `{level}`"))?;
+                Ok(DocstoreCompression::Zstd(Some(level)))
+            }
+            _ => Err(anyhow!("unknown docstore compression `{value}`")),
+        }
+    }
+}
+
+/// Smallest index writer heap tantivy accepts, in MiB.
+pub const MIN_WRITER_MB: usize = 15;
+pub const DEFAULT_TITLE_WRITER_MB: usize = 256;
+pub const DEFAULT_NAME_WRITER_MB: usize = 128;
+
+/// Default query parser boost for the optional `overview` field.
+pub const DEFAULT_OVERVIEW_BOOST: f32 = 0.5;
+/// Default query parser boosts for the always-present title fields.
+pub const DEFAULT_PRIMARY_TITLE_BOOST: f32 = 2.0;
+pub const DEFAULT_ORIGINAL_TITLE_BOOST: f32 = 1.2;
+pub const DEFAULT_GENRES_BOOST: f32 = 0.3;
+/// Default query parser boost for principals billed below [`TOP_BILLED_PRINCIPALS`].
+pub const DEFAULT_SUPPORTING_PRINCIPALS_BOOST: f32 = 0.4;
+/// Default query parser boost for the searchable person name.
+pub const DEFAULT_NAME_BOOST: f32 = 1.5;
+
+/// Settings for building and querying the title index.
+#[derive(Debug, Clone)]
+pub struct TitleIndexOptions {
+    pub infix_ngram: Option<NgramOptions>,
+    pub malformed_rows: MalformedRowPolicy,
+    /// Header of an extra title.basics column indexed as `overview` when present.
+    pub overview_column: Option<String>,
+    /// Query-time boosts: only the parsers built when the index is opened read them, so
+    /// changing one needs no rebuild.
+    pub overview_boost: f32,
+    pub primary_title_boost: f32,
+    pub original_title_boost: f32,
+    pub genres_boost: f32,
+    pub supporting_principals_boost: f32,
+    pub docstore_compression: DocstoreCompression,
+    /// Load title.principals to enrich search and `/titles/{tconst}/cast`; this holds every
+    /// principal and person name in memory while indexing.
+    pub principals: bool,
+    /// Join title.episode so episodes carry their series, season and episode number.
+    pub episodes: bool,
+    /// Threads turning title.basics rows into documents during a build. They add documents as
+    /// they finish them, so document order is not stable across builds.
+    pub workers: usize,
+    /// Index writer heap used for builds and updates, in MiB.
+    pub writer_mb: usize,
+}
+
+impl Default for TitleIndexOptions {
+    fn default() -> Self {
+        Self {
+            infix_ngram: None,
+            malformed_rows: MalformedRowPolicy::default(),
+            overview_column: None,
+            overview_boost: DEFAULT_OVERVIEW_BOOST,
+            primary_title_boost: DEFAULT_PRIMARY_TITLE_BOOST,
+            original_title_boost: DEFAULT_ORIGINAL_TITLE_BOOST,
+            genres_boost: DEFAULT_GENRES_BOOST,
+            supporting_principals_boost: DEFAULT_SUPPORTING_PRINCIPALS_BOOST,
+            docstore_compression: DocstoreCompression::default(),
+            principals: true,
+            episodes: true,
+            workers: default_index_workers(),
+            writer_mb: DEFAULT_TITLE_WRITER_MB,
+        }
+    }
+}
+
+impl TitleIndexOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            infix_ngram: config.infix_ngram.then_some(NgramOptions {
+                min_gram: config.infix_min_gram,
+                max_gram: config.infix_max_gram,
+            }),
+            malformed_rows: config.malformed_rows,
+            overview_column: config.overview_column.clone(),
+            overview_boost: config.overview_boost,
+            primary_title_boost: config.primary_title_boost,
+            original_title_boost: config.original_title_boost,
+            genres_boost: config.genres_boost,
+            supporting_principals_boost: config.supporting_principals_boost,
+            docstore_compression: config.docstore_compression,
+            principals: config.index_principals,
+            episodes: config.index_episodes,
+            workers: config.index_workers.max(1),
+            writer_mb: config.title_writer_mb,
+        }
+    }
+}
+
+impl TitleIndexOptions {
+    /// The options recorded in the build summary and compared when the index is reopened.
+    fn build_settings(&self) -> BuildSettings {
+        BuildSettings {
+            infix_ngram: self
+                .infix_ngram
+                .map(|ngram| [ngram.min_gram, ngram.max_gram]),
+            docstore_compression: self.docstore_compression.to_string(),
+            principals: Some(self.principals),
+            episodes: Some(self.episodes),
+            overview_column: self.overview_column.clone(),
+        }
+    }
+}
+
+/// One indexing worker per available core.
+pub fn default_index_workers() -> usize {
+    thread::available_parallelism().map_or(1, usize::from)
+}
+
+/// Index-time settings for the name index.
+#[derive(Debug, Clone, Copy)]
+pub struct NameIndexOptions {
+    pub year_bounds: YearBounds,
+    pub malformed_rows: MalformedRowPolicy,
+    pub docstore_compression: DocstoreCompression,
+    /// Index writer heap, in MiB.
+    pub writer_mb: usize,
+    /// Query parser boost for name matches. Applied when the index is opened, so changing it
+    /// needs no rebuild; professions are searched in their own field at the default weight.
+    pub name_boost: f32,
+}
+
+impl Default for NameIndexOptions {
+    fn default() -> Self {
+        Self {
+            year_bounds: YearBounds::default(),
+            malformed_rows: MalformedRowPolicy::default(),
+            docstore_compression: DocstoreCompression::default(),
+            writer_mb: DEFAULT_NAME_WRITER_MB,
+            name_boost: DEFAULT_NAME_BOOST,
+        }
+    }
+}
+
+impl NameIndexOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            year_bounds: YearBounds {
+                min: config.name_min_year,
+                max: config.name_max_year,
+            },
+            malformed_rows: config.malformed_rows,
+            docstore_compression: config.docstore_compression,
+            writer_mb: config.name_writer_mb,
+            name_boost: config.name_boost,
+        }
+    }
+
+    /// The options recorded in the build summary and compared when the index is reopened.
+    fn build_settings(&self) -> BuildSettings {
+        BuildSettings {
+            infix_ngram: None,
+            docstore_compression: self.docstore_compression.to_string(),
+            principals: None,
+            episodes: None,
+            overview_column: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TitleFields {
     pub tconst: Field,
     pub primary_title: Field,
+    pub primary_title_sort: Field,
+    pub primary_title_sort_stripped: Field,
     pub primary_title_exact: Option<Field>,
     pub original_title: Field,
     pub title_type: Field,
@@ -32,11 +418,30 @@ pub struct TitleFields {
     pub genres: Field,
     pub average_rating: Field,
     pub num_votes: Field,
+    /// [`PopularityTier`] of rated titles, derived from `numVotes` at index time.
+    pub popularity_tier: Field,
+    pub runtime_minutes: Field,
+    pub is_adult: Field,
     pub search_titles: Field,
+    /// Names of principals billed below [`TOP_BILLED_PRINCIPALS`], searched with a lower boost.
+    pub supporting_principals: Field,
+    pub primary_title_ngram: Option<Field>,
+    pub localized_titles: Field,
+    /// Lowercased regions of the title's AKAs, for `region` filters.
+    pub aka_regions: Field,
+    /// Lowercased languages of the title's AKAs, for `language` filters.
+    pub aka_languages: Field,
+    pub principals: Field,
+    pub people_ids: Field,
+    pub overview: Option<Field>,
+    /// Series tconst of an episode, from title.episode.
+    pub parent_tconst: Field,
+    pub season_number: Field,
+    pub episode_number: Field,
 }
 
 impl TitleFields {
-    fn new(schema: &Schema) -> Result<Self> {
+    pub fn new(schema: &Schema) -> Result<Self> {
         Ok(Self {
             tconst: schema
                 .get_field("tconst")
@@ -44,6 +449,12 @@ impl TitleFields {
             primary_title: schema
                 .get_field("primaryTitle")
                 .map_err(|_| anyhow!("missing field primaryTitle"))?,
+            primary_title_sort: schema
+                .get_field("primaryTitleSort")
+                .map_err(|_| anyhow!("missing field primaryTitleSort"))?,
+            primary_title_sort_stripped: schema
+                .get_field("primaryTitleSortStripped")
+                .map_err(|_| anyhow!("missing field primaryTitleSortStripped"))?,
             primary_title_exact: schema.get_field("primary_title_exact").ok(),
             original_title: schema
                 .get_field("originalTitle")
@@ -66,9 +477,47 @@ impl TitleFields {
             num_votes: schema
                 .get_field("numVotes")
                 .map_err(|_| anyhow!("missing field numVotes"))?,
+            popularity_tier: schema
+                .get_field("popularityTier")
+                .map_err(|_| anyhow!("missing field popularityTier"))?,
+            runtime_minutes: schema
+                .get_field("runtimeMinutes")
+                .map_err(|_| anyhow!("missing field runtimeMinutes"))?,
+            is_adult: schema
+                .get_field("isAdult")
+                .map_err(|_| anyhow!("missing field isAdult"))?,
             search_titles: schema
                 .get_field("searchTitles")
                 .map_err(|_| anyhow!("missing field searchTitles"))?,
+            supporting_principals: schema
+                .get_field("supportingPrincipals")
+                .map_err(|_| anyhow!("missing field supportingPrincipals"))?,
+            primary_title_ngram: schema.get_field("primaryTitleNgram").ok(),
+            localized_titles: schema
+                .get_field("localizedTitles")
+                .map_err(|_| anyhow!("missing field localizedTitles"))?,
+            aka_regions: schema
+                .get_field("akaRegions")
+                .map_err(|_| anyhow!("missing field akaRegions"))?,
+            aka_languages: schema
+                .get_field("akaLanguages")
+                .map_err(|_| anyhow!("missing field akaLanguages"))?,
+            principals: schema
+                .get_field("principals")
+                .map_err(|_| anyhow!("missing field principals"))?,
+            people_ids: schema
+                .get_field("peopleIds")
+                .map_err(|_| anyhow!("missing field peopleIds"))?,
+            overview: schema.get_field("overview").ok(),
+            parent_tconst: schema
+                .get_field("parentTconst")
+                .map_err(|_| anyhow!("missing field parentTconst"))?,
+            season_number: schema
+                .get_field("seasonNumber")
+                .map_err(|_| anyhow!("missing field seasonNumber"))?,
+            episode_number: schema
+                .get_field("episodeNumber")
+                .map_err(|_| anyhow!("missing field episodeNumber"))?,
         })
     }
 }
@@ -78,6 +527,8 @@ pub struct NameFields {
     pub nconst: Field,
     pub primary_name: Field,
     pub primary_name_search: Field,
+    /// Soundex codes of the words of `primaryName`, see [`phonetic_codes`].
+    pub primary_name_phonetic: Field,
     pub birth_year: Field,
     pub death_year: Field,
     pub primary_profession: Field,
@@ -85,7 +536,7 @@ pub struct NameFields {
 }
 
 impl NameFields {
-    fn new(schema: &Schema) -> Result<Self> {
+    pub fn new(schema: &Schema) -> Result<Self> {
         Ok(Self {
             nconst: schema
                 .get_field("nconst")
@@ -96,6 +547,9 @@ impl NameFields {
             primary_name_search: schema
                 .get_field("primaryNameSearch")
                 .map_err(|_| anyhow!("missing field primaryNameSearch"))?,
+            primary_name_phonetic: schema
+                .get_field("primaryNamePhonetic")
+                .map_err(|_| anyhow!("missing field primaryNamePhonetic"))?,
             birth_year: schema
                 .get_field("birthYear")
                 .map_err(|_| anyhow!("missing field birthYear"))?,
@@ -112,19 +566,408 @@ impl NameFields {
     }
 }
 
+/// A title.akas entry, kept with its region/language so localized titles can be returned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AkaTitle {
+    pub title: String,
+    pub region: Option<String>,
+    pub language: Option<String>,
+}
+
+impl AkaTitle {
+    /// Encodes the entry for the stored `localizedTitles` field as `language\tregion\ttitle`.
+    pub fn to_stored(&self) -> String {
+        format!(
+            "{}\t{}\t{}",
+            self.language.as_deref().unwrap_or_default(),
+            self.region.as_deref().unwrap_or_default(),
+            self.title
+        )
+    }
+
+    pub fn from_stored(value: &str) -> Option<Self> {
+        let mut parts = value.splitn(3, '\t');
+        let language = parts.next()?;
+        let region = parts.next()?;
+        let title = parts.next()?;
+        let non_empty = |value: &str| (!value.is_empty()).then(|| value.to_string());
+        Some(Self {
+            title: title.to_string(),
+            region: non_empty(region),
+            language: non_empty(language),
+        })
+    }
+}
+
+/// A title.principals entry, stored on its title so the cast can be listed without a join.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Principal {
+    pub ordering: u32,
+    pub nconst: String,
+    pub primary_name: String,
+    pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub job: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub characters: Vec<String>,
+}
+
+impl Principal {
+    /// Encodes the entry as JSON for the stored `principals` field.
+    pub fn to_stored(&self) -> Result<String> {
+        serde_json::to_string(self).context("encoding principal")
+    }
+
+    pub fn from_stored(value: &str) -> Option<Self> {
+        serde_json::from_str(value).ok()
+    }
+}
+
+/// A title.principals row held in memory while the title index is built.
+///
+/// There are tens of millions of rows, so the nconst and name are shared with the name lookup
+/// and categories are interned; rows only own their (usually absent) job and characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PrincipalCredit {
+    ordering: u32,
+    nconst: Arc<str>,
+    primary_name: Arc<str>,
+    category: Arc<str>,
+    job: Option<Box<str>>,
+    characters: Box<[Box<str>]>,
+}
+
+/// Borrowed [`Principal`] encoding, so storing a credit allocates nothing but the JSON.
+#[derive(Serialize)]
+struct StoredPrincipal<'a> {
+    ordering: u32,
+    nconst: &'a str,
+    primary_name: &'a str,
+    category: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    job: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    characters: &'a [Box<str>],
+}
+
+impl PrincipalCredit {
+    /// Encodes the credit as a stored [`Principal`].
+    fn to_stored(&self) -> Result<String> {
+        serde_json::to_string(&StoredPrincipal {
+            ordering: self.ordering,
+            nconst: &self.nconst,
+            primary_name: &self.primary_name,
+            category: &self.category,
+            job: self.job.as_deref(),
+            characters: &self.characters,
+        })
+        .context("encoding principal")
+    }
+}
+
+/// Principal credits by tconst, each list in billing order.
+type PrincipalsMap = HashMap<String, Vec<PrincipalCredit>>;
+
+/// Outcome and provenance of the last index build, persisted as the commit payload in the index
+/// `meta.json` so merges and garbage collection carry it along.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildSummary {
+    /// Schema version the index was built with; 0 for indexes built before it was recorded.
+    #[serde(default)]
+    pub schema_version: u32,
+    /// Rows written to the index.
+    pub processed: u64,
+    /// Rows dropped as malformed.
+    pub skipped: u64,
+    pub elapsed_ms: u64,
+    /// Last-modified time (RFC 3339) of each dataset file the index was built from, keyed by
+    /// file name.
+    #[serde(default)]
+    pub sources: BTreeMap<String, String>,
+    /// Fingerprint of the archive behind each dataset file (see
+    /// [`recorded_fingerprint`]), keyed by file name. An index is rebuilt on startup once a
+    /// dataset it was built from has a different fingerprint on disk.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub fingerprints: BTreeMap<String, String>,
+    /// When the build finished, RFC 3339.
+    #[serde(default)]
+    pub built_at: String,
+    /// When titles were last re-indexed in place by [`update_title_index`], RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+    /// Options the index was built with; `null` for indexes built before they were recorded.
+    #[serde(default)]
+    pub settings: Option<BuildSettings>,
+}
+
+/// Index-time options that change what an index contains. An index whose recorded settings
+/// differ from the configured ones is rebuilt on startup.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildSettings {
+    /// `[min, max]` gram sizes of the infix n-gram field; `null` when it is disabled.
+    pub infix_ngram: Option<[usize; 2]>,
+    /// Docstore compression, as accepted by `IMDB_DOCSTORE_COMPRESSION`.
+    pub docstore_compression: String,
+    /// Whether title.principals was loaded; `null` for the name index.
+    pub principals: Option<bool>,
+    /// Whether title.episode was loaded; `null` for the name index.
+    #[serde(default)]
+    pub episodes: Option<bool>,
+    /// title.basics column indexed as the overview, if any.
+    #[serde(default)]
+    pub overview_column: Option<String>,
+}
+
+impl BuildSummary {
+    fn new(
+        schema_version: u32,
+        settings: BuildSettings,
+        processed: usize,
+        skipped: usize,
+        started: Instant,
+        sources: &[&Path],
+    ) -> Self {
+        Self {
+            schema_version,
+            processed: processed as u64,
+            skipped: skipped as u64,
+            elapsed_ms: started.elapsed().as_millis() as u64,
+            sources: source_times(sources),
+            fingerprints: source_fingerprints(sources),
+            built_at: rfc3339(Utc::now()),
+            updated_at: None,
+            settings: Some(settings),
+        }
+    }
+
+    /// Reads the summary recorded by the last build, if the index has one.
+    pub fn load(index: &Index) -> Result<Option<Self>> {
+        let metas = index.load_metas().context("reading index metadata")?;
+        metas
+            .payload
+            .map(|payload| serde_json::from_str(&payload).context("decoding build summary"))
+            .transpose()
+    }
+
+    fn commit(&self, writer: &mut IndexWriter) -> Result<()> {
+        let mut commit = writer.prepare_commit()?;
+        commit.set_payload(&serde_json::to_string(self)?);
+        commit.commit()?;
+        Ok(())
+    }
+}
+
+/// Provenance of an index, written as `build_meta.json` in the index directory for operators and
+/// served by `/meta`. Derived from the [`BuildSummary`] of the same commit, which stays the record
+/// the indexer itself reads.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildMeta {
+    pub schema_version: u32,
+    /// Documents written by the build.
+    pub documents: u64,
+    /// Last-modified time (RFC 3339) of each dataset file the index was built from, keyed by
+    /// file name.
+    pub sources: BTreeMap<String, String>,
+    /// When the build finished, RFC 3339.
+    pub built_at: String,
+    /// When titles were last re-indexed in place by [`update_title_index`], RFC 3339.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<String>,
+}
+
+impl From<&BuildSummary> for BuildMeta {
+    fn from(summary: &BuildSummary) -> Self {
+        Self {
+            schema_version: summary.schema_version,
+            documents: summary.processed,
+            sources: summary.sources.clone(),
+            built_at: summary.built_at.clone(),
+            updated_at: summary.updated_at.clone(),
+        }
+    }
+}
+
+impl BuildMeta {
+    /// Written outside tantivy's managed files so merges and garbage collection leave it alone.
+    fn write(&self, index_dir: &Path) -> Result<()> {
+        let path = index_dir.join(BUILD_META_FILE);
+        std::fs::write(&path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+/// Last-modified time of each of `paths` that exists, keyed by file name.
+fn source_times(paths: &[&Path]) -> BTreeMap<String, String> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            let modified = std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()?;
+            Some((name, rfc3339(modified.into())))
+        })
+        .collect()
+}
+
+/// Recorded archive fingerprint of each of `paths` that has one, keyed by file name.
+fn source_fingerprints(paths: &[&Path]) -> BTreeMap<String, String> {
+    paths
+        .iter()
+        .filter_map(|path| {
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            Some((name, recorded_fingerprint(path)?))
+        })
+        .collect()
+}
+
+fn rfc3339(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Inclusive range of plausible years; values outside it are dropped at index time.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct YearBounds {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl YearBounds {
+    fn contains(&self, year: i64) -> bool {
+        self.min.is_none_or(|min| year >= min) && self.max.is_none_or(|max| year <= max)
+    }
+}
+
 #[derive(Clone)]
 pub struct TitleIndex {
     pub schema: Schema,
     pub fields: TitleFields,
     pub reader: IndexReader,
+    /// Typo-tolerant parser allowing one edit per term.
     pub query_parser: QueryParser,
+    /// Same fields and boosts as `query_parser`, allowing two edits per term.
+    pub loose_query_parser: QueryParser,
+    /// Same fields and boosts as `query_parser`, without typo-tolerant fuzzy matching.
+    pub exact_query_parser: QueryParser,
+    pub tokenizers: TokenizerManager,
+}
+
+impl TitleIndex {
+    /// Wraps an opened title index with a reader and the title query parser.
+    ///
+    /// Custom tokenizers must already be registered via [`register_title_tokenizers`].
+    pub fn from_index(index: &Index, options: &TitleIndexOptions) -> Result<Self> {
+        let schema = index.schema();
+        let fields = TitleFields::new(&schema)?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()
+            .context("constructing title index reader")?;
+        let mut searched_fields = vec![
+            fields.primary_title,
+            fields.original_title,
+            fields.search_titles,
+            fields.supporting_principals,
+            fields.genres,
+        ];
+        searched_fields.extend(fields.overview);
+        let mut exact_query_parser = QueryParser::for_index(index, searched_fields);
+        exact_query_parser.set_field_boost(fields.primary_title, options.primary_title_boost);
+        exact_query_parser.set_field_boost(fields.original_title, options.original_title_boost);
+        exact_query_parser.set_field_boost(fields.search_titles, 1.0);
+        exact_query_parser.set_field_boost(
+            fields.supporting_principals,
+            options.supporting_principals_boost,
+        );
+        exact_query_parser.set_field_boost(fields.genres, options.genres_boost);
+        if let Some(overview) = fields.overview {
+            exact_query_parser.set_field_boost(overview, options.overview_boost);
+        }
+        let fuzzy_parser = |distance| {
+            let mut parser = exact_query_parser.clone();
+            parser.set_field_fuzzy(fields.primary_title, false, distance, true);
+            parser.set_field_fuzzy(fields.original_title, false, distance, true);
+            parser.set_field_fuzzy(fields.search_titles, false, distance, true);
+            parser.set_field_fuzzy(fields.supporting_principals, false, distance, true);
+            parser
+        };
+        let query_parser = fuzzy_parser(1);
+        let loose_query_parser = fuzzy_parser(2);
+
+        Ok(Self {
+            schema,
+            fields,
+            reader,
+            query_parser,
+            loose_query_parser,
+            exact_query_parser,
+            tokenizers: index.tokenizers().clone(),
+        })
+    }
+
+    /// Picks the query parser tolerating `distance` edits per term (capped at two).
+    pub fn query_parser(&self, distance: u8) -> &QueryParser {
+        match distance {
+            0 => &self.exact_query_parser,
+            1 => &self.query_parser,
+            _ => &self.loose_query_parser,
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct NameIndex {
     pub fields: NameFields,
     pub reader: IndexReader,
+    /// Typo-tolerant parser allowing one edit per term.
     pub query_parser: QueryParser,
+    /// Same fields and boosts as `query_parser`, allowing two edits per term.
+    pub loose_query_parser: QueryParser,
+    /// Same fields and boosts as `query_parser`, without typo-tolerant fuzzy matching.
+    pub exact_query_parser: QueryParser,
+}
+
+impl NameIndex {
+    /// Wraps an opened name index with a reader and the name query parser.
+    pub fn from_index(index: &Index, options: &NameIndexOptions) -> Result<Self> {
+        let fields = NameFields::new(&index.schema())?;
+        let reader = index
+            .reader_builder()
+            .reload_policy(ReloadPolicy::OnCommitWithDelay)
+            .try_into()
+            .context("constructing name index reader")?;
+        let mut exact_query_parser = QueryParser::for_index(
+            index,
+            vec![fields.primary_name_search, fields.primary_profession],
+        );
+        exact_query_parser.set_field_boost(fields.primary_name_search, options.name_boost);
+        let fuzzy_parser = |distance| {
+            let mut parser = exact_query_parser.clone();
+            parser.set_field_fuzzy(fields.primary_name_search, false, distance, true);
+            parser.set_field_fuzzy(fields.primary_profession, false, distance, true);
+            parser
+        };
+        let query_parser = fuzzy_parser(1);
+        let loose_query_parser = fuzzy_parser(2);
+
+        Ok(Self {
+            fields,
+            reader,
+            query_parser,
+            loose_query_parser,
+            exact_query_parser,
+        })
+    }
+
+    /// Picks the query parser tolerating `distance` edits per term (capped at two).
+    pub fn query_parser(&self, distance: u8) -> &QueryParser {
+        match distance {
+            0 => &self.exact_query_parser,
+            1 => &self.query_parser,
+            _ => &self.loose_query_parser,
+        }
+    }
 }
 
 #[derive(Clone)]
@@ -133,190 +976,463 @@ pub struct PreparedIndexes {
     pub names: NameIndex,
 }
 
+/// Dataset archives read by [`prepare_indexes`] under `config`; cast and crew are only needed
+/// with principals enrichment enabled, and title.episode only with episodes enabled.
+pub fn required_datasets(config: &AppConfig) -> Vec<&'static str> {
+    let mut names = vec![
+        "name.basics.tsv.gz",
+        "title.akas.tsv.gz",
+        "title.basics.tsv.gz",
+        "title.ratings.tsv.gz",
+    ];
+    if config.index_principals {
+        names.extend(["title.crew.tsv.gz", "title.principals.tsv.gz"]);
+    }
+    if config.index_episodes {
+        names.push("title.episode.tsv.gz");
+    }
+    names
+}
+
 pub async fn prepare_indexes(
     config: &AppConfig,
     datasets: &[DatasetFile],
+) -> Result<PreparedIndexes> {
+    fs::create_dir_all(&config.index_dir)
+        .await
+        .with_context(|| format!("creating index root at {}", config.index_dir.display()))?;
+
+    let title_index_dir = live_index_dir(&config.index_dir, TITLE_INDEX_SUBDIR).await?;
+    let name_index_dir = live_index_dir(&config.index_dir, NAME_INDEX_SUBDIR).await?;
+
+    prepare_indexes_at(config, datasets, &title_index_dir, &name_index_dir).await
+}
+
+/// Indexes built by [`rebuild_indexes`], along with the directories they replace.
+pub struct RebuiltIndexes {
+    pub indexes: PreparedIndexes,
+    /// Directories of the previously live indexes. They are no longer referenced on disk but
+    /// may still be open; remove them with [`remove_retired_indexes`] once nothing serves them.
+    pub retired: Vec<PathBuf>,
+}
+
+/// Rebuilds both indexes from `datasets` into fresh `titles-<generation>`/`names-<generation>`
+/// directories and points the index root at them.
+///
+/// The previous directories are left untouched, so indexes still being served keep working
+/// until they are swapped out; only then should the caller remove [`RebuiltIndexes::retired`].
+/// A failed rebuild leaves the pointers, and so the live indexes, as they were.
+pub async fn rebuild_indexes(
+    config: &AppConfig,
+    datasets: &[DatasetFile],
+) -> Result<RebuiltIndexes> {
+    let root = &config.index_dir;
+    fs::create_dir_all(root)
+        .await
+        .with_context(|| format!("creating index root at {}", root.display()))?;
+
+    let generation = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let title_dir_name = format!("{TITLE_INDEX_SUBDIR}-{generation}");
+    let name_dir_name = format!("{NAME_INDEX_SUBDIR}-{generation}");
+    let title_index_dir = root.join(&title_dir_name);
+    let name_index_dir = root.join(&name_dir_name);
+    clear_index(&title_index_dir).await?;
+    clear_index(&name_index_dir).await?;
+
+    info!(generation, "building replacement indexes");
+    let built = prepare_indexes_at(config, datasets, &title_index_dir, &name_index_dir).await;
+    let indexes = match built {
+        Ok(indexes) => indexes,
+        Err(err) => {
+            let partial = [title_index_dir, name_index_dir];
+            if let Err(cleanup) = remove_retired_indexes(&partial).await {
+                warn!(error = %format!("{cleanup:#}"), "could not remove partial indexes");
+            }
+            return Err(err);
+        }
+    };
+
+    let mut retired = Vec::new();
+    for (subdir, dir_name) in [
+        (TITLE_INDEX_SUBDIR, &title_dir_name),
+        (NAME_INDEX_SUBDIR, &name_dir_name),
+    ] {
+        let previous = live_index_dir(root, subdir).await?;
+        set_live_index_dir(root, subdir, dir_name).await?;
+        if previous.exists() {
+            retired.push(previous);
+        }
+    }
+
+    Ok(RebuiltIndexes { indexes, retired })
+}
+
+/// Deletes index directories retired by [`rebuild_indexes`].
+pub async fn remove_retired_indexes(retired: &[PathBuf]) -> Result<()> {
+    for dir in retired {
+        clear_index(dir).await?;
+    }
+    Ok(())
+}
+
+/// Directory of the live `subdir` index: the one named by its `<subdir>.current` pointer file,
+/// or `subdir` itself for an index that was never rebuilt in place.
+async fn live_index_dir(root: &Path, subdir: &str) -> Result<PathBuf> {
+    let pointer = root.join(format!("{subdir}.current"));
+    match fs::read_to_string(&pointer).await {
+        Ok(name) if !name.trim().is_empty() => Ok(root.join(name.trim())),
+        Ok(_) => Ok(root.join(subdir)),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(root.join(subdir)),
+        Err(err) => Err(err).with_context(|| format!("reading {}", pointer.display())),
+    }
+}
+
+/// Points the `subdir` index at `dir_name`, replacing the pointer file atomically.
+async fn set_live_index_dir(root: &Path, subdir: &str, dir_name: &str) -> Result<()> {
+    let pointer = root.join(format!("{subdir}.current"));
+    let staged = pointer.with_extension("current.tmp");
+    fs::write(&staged, dir_name)
+        .await
+        .with_context(|| format!("writing {}", staged.display()))?;
+    fs::rename(&staged, &pointer)
+        .await
+        .with_context(|| format!("replacing {}", pointer.display()))
+}
+
+async fn prepare_indexes_at(
+    config: &AppConfig,
+    datasets: &[DatasetFile],
+    title_index_dir: &Path,
+    name_index_dir: &Path,
 ) -> Result<PreparedIndexes> {
     let dataset_lookup: HashMap<&str, &DatasetFile> = datasets
         .iter()
         .map(|dataset| (dataset.name, dataset))
         .collect();
+    let dataset = |name: &str| {
+        dataset_lookup
+            .get(name)
+            .copied()
+            .ok_or_else(|| anyhow!("missing {name} dataset"))
+    };
 
-    let basics = dataset_lookup
-        .get("title.basics.tsv.gz")
-        .ok_or_else(|| anyhow!("missing title.basics dataset"))?;
-    let ratings = dataset_lookup
-        .get("title.ratings.tsv.gz")
-        .ok_or_else(|| anyhow!("missing title.ratings dataset"))?;
-    let akas = dataset_lookup
-        .get("title.akas.tsv.gz")
-        .ok_or_else(|| anyhow!("missing title.akas dataset"))?;
-    let names = dataset_lookup
-        .get("name.basics.tsv.gz")
-        .ok_or_else(|| anyhow!("missing name.basics dataset"))?;
-    let principals = dataset_lookup
-        .get("title.principals.tsv.gz")
-        .ok_or_else(|| anyhow!("missing title.principals dataset"))?;
-
-    fs::create_dir_all(&config.index_dir)
-        .await
-        .with_context(|| format!("creating index root at {}", config.index_dir.display()))?;
-
-    let title_index_dir = config.index_dir.join(TITLE_INDEX_SUBDIR);
-    let name_index_dir = config.index_dir.join(NAME_INDEX_SUBDIR);
+    let title_options = TitleIndexOptions::from_config(config);
+    let basics = dataset("title.basics.tsv.gz")?;
+    let ratings = dataset("title.ratings.tsv.gz")?;
+    let akas = dataset("title.akas.tsv.gz")?;
+    let names = dataset("name.basics.tsv.gz")?;
+    let (principals, crew) = if title_options.principals {
+        (
+            Some(dataset("title.principals.tsv.gz")?),
+            Some(dataset("title.crew.tsv.gz")?),
+        )
+    } else {
+        (None, None)
+    };
+    let episodes = if title_options.episodes {
+        Some(dataset("title.episode.tsv.gz")?)
+    } else {
+        None
+    };
 
-    let name_lookup = Arc::new(load_name_map(&names.tsv_path)?);
-    let principals_map = Arc::new(load_principals_map(&principals.tsv_path, &name_lookup)?);
+    let principals_map = Arc::new(load_principals_enrichment(
+        names.path(),
+        principals.map(DatasetFile::path),
+    )?);
+
+    let sources = TitleSources {
+        basics: basics.path().to_path_buf(),
+        ratings: ratings.path().to_path_buf(),
+        akas: akas.path().to_path_buf(),
+        crew: crew.map(|crew| crew.path().to_path_buf()),
+        episodes: episodes.map(|episodes| episodes.path().to_path_buf()),
+        principals: principals
+            .map(|principals| (principals.path().to_path_buf(), names.path().to_path_buf())),
+    };
+    let title_index =
+        prepare_title_index(title_index_dir, sources, principals_map, title_options).await?;
 
-    let title_index = prepare_title_index(
-        &title_index_dir,
-        basics.tsv_path.clone(),
-        ratings.tsv_path.clone(),
-        akas.tsv_path.clone(),
-        Arc::clone(&principals_map),
+    let name_index = prepare_name_index(
+        name_index_dir,
+        names.path().to_path_buf(),
+        NameIndexOptions::from_config(config),
     )
     .await?;
 
-    let name_index = prepare_name_index(&name_index_dir, names.tsv_path.clone()).await?;
-
     Ok(PreparedIndexes {
         titles: title_index,
         names: name_index,
     })
 }
 
+/// Dataset files a title index is built from.
+#[derive(Debug, Clone)]
+struct TitleSources {
+    basics: PathBuf,
+    ratings: PathBuf,
+    akas: PathBuf,
+    crew: Option<PathBuf>,
+    episodes: Option<PathBuf>,
+    /// title.principals and name.basics, already joined into the principals map.
+    principals: Option<(PathBuf, PathBuf)>,
+}
+
+impl TitleSources {
+    fn paths(&self) -> Vec<&Path> {
+        let mut paths = vec![
+            self.basics.as_path(),
+            self.ratings.as_path(),
+            self.akas.as_path(),
+        ];
+        paths.extend(self.crew.as_deref());
+        paths.extend(self.episodes.as_deref());
+        if let Some((principals, names)) = &self.principals {
+            paths.extend([principals.as_path(), names.as_path()]);
+        }
+        paths
+    }
+}
+
 async fn prepare_title_index(
     index_dir: &Path,
-    basics_path: PathBuf,
-    ratings_path: PathBuf,
-    akas_path: PathBuf,
-    principals_map: Arc<HashMap<String, Vec<String>>>,
+    sources: TitleSources,
+    principals_map: Arc<PrincipalsMap>,
+    options: TitleIndexOptions,
 ) -> Result<TitleIndex> {
     if !index_exists(index_dir) {
         build_title_index(
             index_dir,
-            basics_path.clone(),
-            ratings_path.clone(),
-            akas_path.clone(),
+            sources.clone(),
             Arc::clone(&principals_map),
+            options.clone(),
         )
         .await?;
     }
 
     let mut index = Index::open_in_dir(index_dir)
         .with_context(|| format!("opening title index at {}", index_dir.display()))?;
-    let mut schema = index.schema();
-    let fields = match TitleFields::new(&schema) {
-        Ok(fields) => fields,
-        Err(_) => {
-            // Existing index schema is outdated; rebuild.
-            tokio::fs::remove_dir_all(index_dir)
-                .await
-                .with_context(|| {
-                    format!("clearing legacy title index at {}", index_dir.display())
-                })?;
-            build_title_index(
-                index_dir,
-                basics_path.clone(),
-                ratings_path.clone(),
-                akas_path.clone(),
-                Arc::clone(&principals_map),
-            )
-            .await?;
-            index = Index::open_in_dir(index_dir).with_context(|| {
-                format!("reopening rebuilt title index at {}", index_dir.display())
-            })?;
-            schema = index.schema();
-            TitleFields::new(&schema)?
-        }
-    };
-    let reader = index
-        .reader_builder()
-        .reload_policy(ReloadPolicy::OnCommitWithDelay)
-        .try_into()
-        .context("constructing title index reader")?;
-    let mut query_parser = QueryParser::for_index(
+    register_title_tokenizers(&index, &options)?;
+    let up_to_date = build_matches(
         &index,
-        vec![
-            fields.primary_title,
-            fields.original_title,
-            fields.search_titles,
-            fields.genres,
-        ],
-    );
-    query_parser.set_field_boost(fields.primary_title, 2.0);
-    query_parser.set_field_boost(fields.original_title, 1.2);
-    query_parser.set_field_boost(fields.search_titles, 1.0);
-    query_parser.set_field_boost(fields.genres, 0.3);
-    query_parser.set_field_fuzzy(fields.primary_title, false, 1, true);
-    query_parser.set_field_fuzzy(fields.original_title, false, 1, true);
-    query_parser.set_field_fuzzy(fields.search_titles, false, 1, true);
-
-    Ok(TitleIndex {
-        schema,
-        fields,
-        reader,
-        query_parser,
-    })
+        index_dir,
+        TITLE_SCHEMA_VERSION,
+        &options.build_settings(),
+        &sources.paths(),
+    )? && TitleFields::new(&index.schema()).is_ok_and(|fields| {
+        (options.infix_ngram.is_none() || fields.primary_title_ngram.is_some())
+            && (options.overview_column.is_none() || fields.overview.is_some())
+    });
+    if !up_to_date {
+        // Existing index is outdated or built from older datasets; rebuild.
+        tokio::fs::remove_dir_all(index_dir)
+            .await
+            .with_context(|| format!("clearing legacy title index at {}", index_dir.display()))?;
+        build_title_index(
+            index_dir,
+            sources.clone(),
+            Arc::clone(&principals_map),
+            options.clone(),
+        )
+        .await?;
+        index = Index::open_in_dir(index_dir)
+            .with_context(|| format!("reopening rebuilt title index at {}", index_dir.display()))?;
+        register_title_tokenizers(&index, &options)?;
+    }
+
+    TitleIndex::from_index(&index, &options)
 }
 
-async fn prepare_name_index(index_dir: &Path, names_path: PathBuf) -> Result<NameIndex> {
+async fn prepare_name_index(
+    index_dir: &Path,
+    names_path: PathBuf,
+    options: NameIndexOptions,
+) -> Result<NameIndex> {
     if !index_exists(index_dir) {
-        build_name_index(index_dir, names_path.clone()).await?;
+        build_name_index(index_dir, names_path.clone(), options).await?;
     }
 
     let mut index = Index::open_in_dir(index_dir)
         .with_context(|| format!("opening name index at {}", index_dir.display()))?;
-    let mut schema = index.schema();
-    let fields = match NameFields::new(&schema) {
-        Ok(fields) => fields,
-        Err(_) => {
-            tokio::fs::remove_dir_all(index_dir)
-                .await
-                .with_context(|| {
-                    format!("clearing legacy name index at {}", index_dir.display())
-                })?;
-            build_name_index(index_dir, names_path.clone()).await?;
-            index = Index::open_in_dir(index_dir).with_context(|| {
-                format!("reopening rebuilt name index at {}", index_dir.display())
-            })?;
-            schema = index.schema();
-            NameFields::new(&schema)?
+    if !build_matches(
+        &index,
+        index_dir,
+        NAME_SCHEMA_VERSION,
+        &options.build_settings(),
+        &[names_path.as_path()],
+    )? || NameFields::new(&index.schema()).is_err()
+    {
+        tokio::fs::remove_dir_all(index_dir)
+            .await
+            .with_context(|| format!("clearing legacy name index at {}", index_dir.display()))?;
+        build_name_index(index_dir, names_path.clone(), options).await?;
+        index = Index::open_in_dir(index_dir)
+            .with_context(|| format!("reopening rebuilt name index at {}", index_dir.display()))?;
+    }
+
+    NameIndex::from_index(&index, &options)
+}
+
+/// Removes an index directory so the next prepare step rebuilds it.
+async fn clear_index(index_dir: &Path) -> Result<()> {
+    match fs::remove_dir_all(index_dir).await {
+        Err(err) if err.kind() != std::io::ErrorKind::NotFound => {
+            Err(err).with_context(|| format!("clearing stale index at {}", index_dir.display()))
         }
+        _ => Ok(()),
+    }
+}
+
+/// Whether `index` was built with the `expected` schema version and settings from the datasets
+/// now at `sources`. An index without a build summary cannot be vouched for and counts as
+/// outdated.
+fn build_matches(
+    index: &Index,
+    index_dir: &Path,
+    expected: u32,
+    settings: &BuildSettings,
+    sources: &[&Path],
+) -> Result<bool> {
+    let Some(summary) = BuildSummary::load(index)
+        .with_context(|| format!("checking build summary of {}", index_dir.display()))?
+    else {
+        warn!(index = %index_dir.display(), "index has no build summary; rebuilding");
+        return Ok(false);
     };
-    let reader = index
-        .reader_builder()
-        .reload_policy(ReloadPolicy::OnCommitWithDelay)
-        .try_into()
-        .context("constructing name index reader")?;
-    let mut query_parser = QueryParser::for_index(
-        &index,
-        vec![fields.primary_name_search, fields.primary_profession],
-    );
-    query_parser.set_field_boost(fields.primary_name_search, 1.5);
-    query_parser.set_field_fuzzy(fields.primary_name_search, false, 1, true);
-    query_parser.set_field_fuzzy(fields.primary_profession, false, 1, true);
-
-    Ok(NameIndex {
-        fields,
-        reader,
-        query_parser,
-    })
+    if summary.schema_version != expected {
+        warn!(
+            index = %index_dir.display(),
+            found = summary.schema_version,
+            expected,
+            "index schema version is outdated; rebuilding"
+        );
+        return Ok(false);
+    }
+    if summary.settings.as_ref() != Some(settings) {
+        warn!(
+            index = %index_dir.display(),
+            found = ?summary.settings,
+            expected = ?settings,
+            "index was built with different settings; rebuilding"
+        );
+        return Ok(false);
+    }
+    let changed: Vec<String> = source_fingerprints(sources)
+        .into_iter()
+        .filter(|(name, fingerprint)| summary.fingerprints.get(name) != Some(fingerprint))
+        .map(|(name, _)| name)
+        .collect();
+    if !changed.is_empty() {
+        info!(
+            index = %index_dir.display(),
+            datasets = ?changed,
+            "datasets changed since the index was built; rebuilding"
+        );
+        return Ok(false);
+    }
+    Ok(true)
 }
 
 fn index_exists(index_dir: &Path) -> bool {
     index_dir.join("meta.json").exists()
 }
 
-fn build_title_schema() -> Schema {
+/// Registers the custom tokenizers referenced by the title schema.
+pub fn register_title_tokenizers(index: &Index, options: &TitleIndexOptions) -> Result<()> {
+    if let Some(ngram) = options.infix_ngram {
+        let tokenizer = NgramTokenizer::all_ngrams(ngram.min_gram, ngram.max_gram)
+            .context("configuring infix ngram tokenizer")?;
+        let analyzer = TextAnalyzer::builder(tokenizer).filter(LowerCaser).build();
+        index.tokenizers().register(TITLE_NGRAM_TOKENIZER, analyzer);
+    }
+    Ok(())
+}
+
+/// Soundex code of each word in `text`, in order; words without ASCII letters are skipped.
+pub fn phonetic_codes(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_ascii_alphabetic())
+        .filter_map(soundex)
+        .collect()
+}
+
+/// American Soundex: the first letter followed by three digits for the consonant sounds, so
+/// "Schwarzenegger" and "Shwarzeneger" both become `S625`.
+fn soundex(word: &str) -> Option<String> {
+    let digit = |letter: u8| match letter {
+        b'B' | b'F' | b'P' | b'V' => Some(b'1'),
+        b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => Some(b'2'),
+        b'D' | b'T' => Some(b'3'),
+        b'L' => Some(b'4'),
+        b'M' | b'N' => Some(b'5'),
+        b'R' => Some(b'6'),
+        _ => None,
+    };
+    let mut letters = word
+        .bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|letter| letter.to_ascii_uppercase());
+    let first = letters.next()?;
+    let mut code = vec![first];
+    let mut previous = digit(first);
+    for letter in letters {
+        match letter {
+            // H and W do not separate consonants with the same code.
+            b'H' | b'W' => {}
+            _ => {
+                let current = digit(letter);
+                if current.is_some() && current != previous {
+                    code.extend(current);
+                }
+                previous = current;
+            }
+        }
+        if code.len() == 4 {
+            break;
+        }
+    }
+    code.resize(4, b'0');
+    String::from_utf8(code).ok()
+}
+
+/// Alphabetical sort key of a title: lowercased, and with a leading English article ("the",
+/// "a", "an") dropped when `strip_articles` is set, so "The Matrix" files under M.
+pub fn title_sort_key(title: &str, strip_articles: bool) -> String {
+    let lower = title.trim().to_lowercase();
+    if strip_articles {
+        for article in ["the ", "a ", "an "] {
+            if let Some(rest) = lower.strip_prefix(article).map(str::trim_start)
+                && !rest.is_empty()
+            {
+                return rest.to_string();
+            }
+        }
+    }
+    lower
+}
+
+pub fn build_title_schema(options: &TitleIndexOptions) -> Schema {
     let mut schema_builder = Schema::builder();
 
-    schema_builder.add_text_field("tconst", STRING | STORED);
+    schema_builder.add_text_field("tconst", STRING | STORED | FAST);
     schema_builder.add_text_field("titleType", STRING | STORED);
     schema_builder.add_text_field("primaryTitle", TEXT | STORED);
     schema_builder.add_text_field("originalTitle", TEXT | STORED);
     schema_builder.add_text_field("genres", TEXT | STORED);
     schema_builder.add_text_field("searchTitles", TEXT);
+    schema_builder.add_text_field("supportingPrincipals", TEXT);
+    schema_builder.add_text_field("localizedTitles", STORED);
+    schema_builder.add_text_field("akaRegions", STRING);
+    schema_builder.add_text_field("akaLanguages", STRING);
+    schema_builder.add_text_field("principals", STORED);
+    // nconsts of the principals and crew (directors, writers) credited on the title.
+    schema_builder.add_text_field("peopleIds", STRING);
+    schema_builder.add_text_field("parentTconst", STRING | STORED);
+    schema_builder.add_text_field("popularityTier", STRING | STORED);
+    // Alphabetical sort keys (see `title_sort_key`); indexed raw so ties can be matched by term.
+    schema_builder.add_text_field("primaryTitleSort", STRING | FAST);
+    schema_builder.add_text_field("primaryTitleSortStripped", STRING | FAST);
 
     let exact_indexing = TextFieldIndexing::default()
         .set_tokenizer("raw")
@@ -328,6 +1444,20 @@ fn build_title_schema() -> Schema {
             .set_stored(),
     );
 
+    if options.infix_ngram.is_some() {
+        let ngram_indexing = TextFieldIndexing::default()
+            .set_tokenizer(TITLE_NGRAM_TOKENIZER)
+            .set_index_option(IndexRecordOption::Basic);
+        schema_builder.add_text_field(
+            "primaryTitleNgram",
+            TextOptions::default().set_indexing_options(ngram_indexing),
+        );
+    }
+
+    if options.overview_column.is_some() {
+        schema_builder.add_text_field("overview", TEXT | STORED);
+    }
+
     let numeric_options = NumericOptions::default()
         .set_indexed()
         .set_stored()
@@ -336,17 +1466,23 @@ fn build_title_schema() -> Schema {
     schema_builder.add_i64_field("startYear", numeric_options.clone());
     schema_builder.add_i64_field("endYear", numeric_options.clone());
     schema_builder.add_f64_field("averageRating", numeric_options.clone());
-    schema_builder.add_i64_field("numVotes", numeric_options);
+    schema_builder.add_i64_field("numVotes", numeric_options.clone());
+    schema_builder.add_i64_field("runtimeMinutes", numeric_options.clone());
+    schema_builder.add_i64_field("seasonNumber", numeric_options.clone());
+    schema_builder.add_i64_field("episodeNumber", numeric_options.clone());
+    // 1 for adult titles, 0 otherwise (including rows where the flag is missing).
+    schema_builder.add_i64_field("isAdult", numeric_options);
 
     schema_builder.build()
 }
 
-fn build_name_schema() -> Schema {
+pub fn build_name_schema() -> Schema {
     let mut schema_builder = Schema::builder();
 
     schema_builder.add_text_field("nconst", STRING | STORED);
     schema_builder.add_text_field("primaryName", TEXT | STORED);
     schema_builder.add_text_field("primaryNameSearch", TEXT);
+    schema_builder.add_text_field("primaryNamePhonetic", STRING);
     schema_builder.add_text_field("primaryProfession", TEXT | STORED);
     schema_builder.add_text_field("knownForTitles", TEXT | STORED);
 
@@ -363,20 +1499,13 @@ fn build_name_schema() -> Schema {
 
 async fn build_title_index(
     index_dir: &Path,
-    basics_path: PathBuf,
-    ratings_path: PathBuf,
-    akas_path: PathBuf,
-    principals_map: Arc<HashMap<String, Vec<String>>>,
+    sources: TitleSources,
+    principals_map: Arc<PrincipalsMap>,
+    options: TitleIndexOptions,
 ) -> Result<()> {
     let index_dir = index_dir.to_path_buf();
     task::spawn_blocking(move || {
-        build_title_index_sync(
-            &index_dir,
-            &basics_path,
-            &ratings_path,
-            &akas_path,
-            &principals_map,
-        )
+        build_title_index_sync(&index_dir, &sources, &principals_map, &options)
     })
     .await??;
     Ok(())
@@ -384,11 +1513,12 @@ async fn build_title_index(
 
 fn build_title_index_sync(
     index_dir: &Path,
-    basics_path: &Path,
-    ratings_path: &Path,
-    akas_path: &Path,
-    principals_map: &HashMap<String, Vec<String>>,
+    sources: &TitleSources,
+    principals_map: &PrincipalsMap,
+    options: &TitleIndexOptions,
 ) -> Result<()> {
+    let basics_path = sources.basics.as_path();
+    let started = Instant::now();
     if index_dir.exists() {
         std::fs::remove_dir_all(index_dir)
             .with_context(|| format!("clearing existing index at {}", index_dir.display()))?;
@@ -396,141 +1526,542 @@ fn build_title_index_sync(
     std::fs::create_dir_all(index_dir)
         .with_context(|| format!("creating index directory {}", index_dir.display()))?;
 
-    let schema = build_title_schema();
-    let index = Index::create_in_dir(index_dir, schema.clone())
+    let schema = build_title_schema(options);
+    let index = Index::builder()
+        .schema(schema.clone())
+        .settings(options.docstore_compression.index_settings())
+        .create_in_dir(index_dir)
         .with_context(|| format!("creating title index in {}", index_dir.display()))?;
+    register_title_tokenizers(&index, options)?;
 
     let mut writer = index
-        .writer::<TantivyDocument>(256 * 1024 * 1024)
+        .writer::<TantivyDocument>(options.writer_mb * 1024 * 1024)
         .context("creating title index writer")?;
 
-    let ratings_map = load_ratings_map(ratings_path)?;
-    info!(count = ratings_map.len(), "loaded ratings lookup");
-
-    let aka_map = load_aka_map(akas_path)?;
-    info!(count = aka_map.len(), "loaded aka titles");
+    let lookups = TitleLookups::load(
+        &sources.ratings,
+        &sources.akas,
+        sources.crew.as_deref(),
+        sources.episodes.as_deref(),
+        principals_map,
+    )?;
 
     let fields = TitleFields::new(&schema)?;
 
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .has_headers(true)
-        .flexible(true)
-        .from_path(basics_path)
-        .with_context(|| format!("opening {}", basics_path.display()))?;
+    let mut reader = tsv_reader(basics_path, TITLE_BASICS_HEADER)?;
+
+    let overview_column = overview_position(&mut reader, options, basics_path)?;
+
+    // The reader hands batches of rows to `options.workers` threads that build the documents;
+    // the writer indexes them on its own threads.
+    let processed = AtomicUsize::new(0);
+    let malformed_count = thread::scope(|scope| -> Result<usize> {
+        let (batch_tx, batch_rx) =
+            crossbeam_channel::bounded::<Vec<StringRecord>>(options.workers * 2);
+        let workers: Vec<_> = (0..options.workers)
+            .map(|_| {
+                let batch_rx = batch_rx.clone();
+                let (writer, fields, lookups, processed) = (&writer, &fields, &lookups, &processed);
+                scope.spawn(move || -> Result<usize> {
+                    let mut malformed = 0;
+                    for record in batch_rx.into_iter().flatten() {
+                        if !has_expected_columns(
+                            &record,
+                            TITLE_BASICS_COLUMNS,
+                            options.malformed_rows,
+                            basics_path,
+                        )? {
+                            malformed += 1;
+                            continue;
+                        }
+
+                        let Some(doc) = title_document(&record, fields, overview_column, lookups)?
+                        else {
+                            continue;
+                        };
+                        writer
+                            .add_document(doc)
+                            .context("adding document to title index")?;
+
+                        let count = processed.fetch_add(1, Ordering::Relaxed) + 1;
+                        if count.is_multiple_of(50_000) {
+                            info!(processed = count, "title indexing progress");
+                        }
+                    }
+                    Ok(malformed)
+                })
+            })
+            .collect();
+        drop(batch_rx);
+
+        let mut batch = Vec::with_capacity(TITLE_BATCH_SIZE);
+        for result in reader.records() {
+            batch.push(result.with_context(|| format!("reading {}", basics_path.display()))?);
+            if batch.len() < TITLE_BATCH_SIZE {
+                continue;
+            }
+            // Workers only stop early on an error, which is reported when they are joined.
+            let batch = std::mem::replace(&mut batch, Vec::with_capacity(TITLE_BATCH_SIZE));
+            if workers.iter().any(|worker| worker.is_finished()) || batch_tx.send(batch).is_err() {
+                break;
+            }
+        }
+        if !batch.is_empty() {
+            let _ = batch_tx.send(batch);
+        }
+        drop(batch_tx);
 
-    let mut record_count = 0usize;
+        workers
+            .into_iter()
+            .map(|worker| worker.join().expect("title indexing worker panicked"))
+            .sum()
+    })?;
+    let record_count = processed.into_inner();
+
+    if malformed_count > 0 {
+        warn!(skipped = malformed_count, path = %basics_path.display(), "skipped malformed rows");
+    }
+    info!(processed = record_count, "committing title index");
+    let summary = BuildSummary::new(
+        TITLE_SCHEMA_VERSION,
+        options.build_settings(),
+        record_count,
+        malformed_count,
+        started,
+        &sources.paths(),
+    );
+    summary
+        .commit(&mut writer)
+        .context("committing title index")?;
+    BuildMeta::from(&summary).write(index_dir)
+}
+
+/// Outcome of [`update_title_index`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TitleUpdate {
+    /// Requested titles re-added from the current datasets.
+    pub updated: u64,
+    /// Requested titles that were indexed but are no longer in title.basics, now dropped.
+    pub removed: u64,
+}
 
+/// Re-indexes `tconsts` in the existing title index from the current datasets instead of
+/// rebuilding it: their documents are deleted and re-added from title.basics, ratings, akas and
+/// principals, then committed. Titles missing from title.basics are dropped. Open readers pick
+/// the change up on their next reload.
+pub async fn update_title_index(
+    config: &AppConfig,
+    datasets: &[DatasetFile],
+    tconsts: HashSet<String>,
+) -> Result<TitleUpdate> {
+    let dataset = |name: &str| {
+        datasets
+            .iter()
+            .find(|dataset| dataset.name == name)
+            .ok_or_else(|| anyhow!("missing {name} dataset"))
+    };
+    let options = TitleIndexOptions::from_config(config);
+    let basics_path = dataset("title.basics.tsv.gz")?.path().to_path_buf();
+    let ratings_path = dataset("title.ratings.tsv.gz")?.path().to_path_buf();
+    let akas_path = dataset("title.akas.tsv.gz")?.path().to_path_buf();
+    let names_path = dataset("name.basics.tsv.gz")?.path().to_path_buf();
+    let (principals_path, crew_path) = if options.principals {
+        (
+            Some(dataset("title.principals.tsv.gz")?.path().to_path_buf()),
+            Some(dataset("title.crew.tsv.gz")?.path().to_path_buf()),
+        )
+    } else {
+        (None, None)
+    };
+    let episodes_path = if options.episodes {
+        Some(dataset("title.episode.tsv.gz")?.path().to_path_buf())
+    } else {
+        None
+    };
+    let index_dir = live_index_dir(&config.index_dir, TITLE_INDEX_SUBDIR).await?;
+
+    task::spawn_blocking(move || {
+        let sources: Vec<&Path> = [&basics_path, &ratings_path, &akas_path, &names_path]
+            .into_iter()
+            .map(PathBuf::as_path)
+            .chain(principals_path.as_deref())
+            .chain(crew_path.as_deref())
+            .chain(episodes_path.as_deref())
+            .collect();
+        let principals_map = load_principals_enrichment(&names_path, principals_path.as_deref())?;
+        let lookups = TitleLookups::load(
+            &ratings_path,
+            &akas_path,
+            crew_path.as_deref(),
+            episodes_path.as_deref(),
+            &principals_map,
+        )?;
+        update_title_index_sync(
+            &index_dir,
+            &basics_path,
+            &lookups,
+            &options,
+            &tconsts,
+            &sources,
+        )
+    })
+    .await?
+}
+
+fn update_title_index_sync(
+    index_dir: &Path,
+    basics_path: &Path,
+    lookups: &TitleLookups,
+    options: &TitleIndexOptions,
+    tconsts: &HashSet<String>,
+    sources: &[&Path],
+) -> Result<TitleUpdate> {
+    let index = Index::open_in_dir(index_dir)
+        .with_context(|| format!("opening title index at {}", index_dir.display()))?;
+    register_title_tokenizers(&index, options)?;
+    let fields = TitleFields::new(&index.schema())?;
+    // Kept as the commit payload so `/stats` and `/meta` still describe the last full build,
+    // refreshed with the datasets the update read.
+    let last_build = BuildSummary::load(&index)?.map(|summary| BuildSummary {
+        sources: source_times(sources),
+        updated_at: Some(rfc3339(Utc::now())),
+        ..summary
+    });
+
+    // Opened before the deletes are committed, to tell which requested titles were indexed.
+    let searcher = index
+        .reader()
+        .context("opening title index reader")?
+        .searcher();
+
+    let mut writer = index
+        .writer::<TantivyDocument>(options.writer_mb * 1024 * 1024)
+        .context("creating title index writer")?;
+    for tconst in tconsts {
+        writer.delete_term(Term::from_field_text(fields.tconst, tconst));
+    }
+
+    let mut reader = tsv_reader(basics_path, TITLE_BASICS_HEADER)?;
+    let overview_column = overview_position(&mut reader, options, basics_path)?;
+    let mut update = TitleUpdate::default();
+    let mut readded = HashSet::new();
     for result in reader.records() {
         let record = result.with_context(|| format!("reading {}", basics_path.display()))?;
+        if !record.get(0).is_some_and(|tconst| tconsts.contains(tconst))
+            || !has_expected_columns(
+                &record,
+                TITLE_BASICS_COLUMNS,
+                options.malformed_rows,
+                basics_path,
+            )?
+        {
+            continue;
+        }
+        if let Some(doc) = title_document(&record, &fields, overview_column, lookups)? {
+            writer
+                .add_document(doc)
+                .context("adding document to title index")?;
+            update.updated += 1;
+            readded.insert(record[0].to_string());
+        }
+    }
+    for tconst in tconsts.iter().filter(|tconst| !readded.contains(*tconst)) {
+        let query = TermQuery::new(
+            Term::from_field_text(fields.tconst, tconst),
+            IndexRecordOption::Basic,
+        );
+        let indexed = searcher
+            .search(&query, &Count)
+            .context("checking for indexed title")?;
+        update.removed += indexed as u64;
+    }
+
+    info!(
+        updated = update.updated,
+        removed = update.removed,
+        "committing title index update"
+    );
+    match &last_build {
+        Some(summary) => summary.commit(&mut writer),
+        None => writer.commit().map(drop).map_err(Into::into),
+    }
+    .context("committing title index update")?;
+    if let Some(summary) = &last_build {
+        BuildMeta::from(summary).write(index_dir)?;
+    }
+    Ok(update)
+}
+
+/// Lookups joined onto title.basics rows while building title documents.
+struct TitleLookups<'a> {
+    ratings: HashMap<String, (f64, i64)>,
+    akas: HashMap<String, Vec<AkaTitle>>,
+    crew: HashMap<String, Vec<String>>,
+    episodes: HashMap<String, EpisodeOf>,
+    principals: &'a PrincipalsMap,
+}
+
+impl<'a> TitleLookups<'a> {
+    fn load(
+        ratings_path: &Path,
+        akas_path: &Path,
+        crew_path: Option<&Path>,
+        episodes_path: Option<&Path>,
+        principals: &'a PrincipalsMap,
+    ) -> Result<Self> {
+        let ratings = load_ratings_map(ratings_path)?;
+        info!(count = ratings.len(), "loaded ratings lookup");
+
+        let akas = load_aka_map(akas_path)?;
+        info!(count = akas.len(), "loaded aka titles");
+
+        let crew = match crew_path {
+            Some(crew_path) => {
+                let crew = load_crew_map(crew_path)?;
+                info!(count = crew.len(), "loaded crew");
+                crew
+            }
+            None => HashMap::new(),
+        };
+
+        let episodes = match episodes_path {
+            Some(episodes_path) => {
+                let episodes = load_episode_map(episodes_path)?;
+                info!(count = episodes.len(), "loaded episodes");
+                episodes
+            }
+            None => HashMap::new(),
+        };
+
+        Ok(Self {
+            ratings,
+            akas,
+            crew,
+            episodes,
+            principals,
+        })
+    }
+}
 
-        let Some(tconst_raw) = record.get(0) else {
-            continue;
-        };
-        if tconst_raw.is_empty() || tconst_raw == "\\N" {
-            continue;
-        }
-        let tconst = tconst_raw.to_string();
+/// Position of the configured overview column in title.basics, if the file has it.
+fn overview_position<R: Read>(
+    reader: &mut Reader<R>,
+    options: &TitleIndexOptions,
+    basics_path: &Path,
+) -> Result<Option<usize>> {
+    let Some(column) = options.overview_column.as_deref() else {
+        return Ok(None);
+    };
+    let headers = reader
+        .headers()
+        .with_context(|| format!("reading headers of {}", basics_path.display()))?;
+    let position = headers.iter().position(|header| header == column);
+    if position.is_none() {
+        info!(column, path = %basics_path.display(), "overview column absent; skipping");
+    }
+    Ok(position)
+}
 
-        let title_type = record.get(1).unwrap_or_default().to_string();
+/// Principal names indexed for search per title; the rest stay in the stored principals.
+const SEARCH_PRINCIPALS_LIMIT: usize = 10;
+/// Leading principal names indexed in `searchTitles` at full weight; the others go to the
+/// lower-boosted `supportingPrincipals`.
+pub const TOP_BILLED_PRINCIPALS: usize = 3;
+
+/// Distinct principal names (compared case-insensitively) to index for search, top-billed first.
+///
+/// `principals` must already be in billing order, as [`load_principals_map`] returns them.
+fn search_principal_names(principals: &[PrincipalCredit]) -> Vec<&str> {
+    let mut seen = HashSet::new();
+    principals
+        .iter()
+        .map(|principal| &*principal.primary_name)
+        .filter(|name| seen.insert(name.to_lowercase()))
+        .take(SEARCH_PRINCIPALS_LIMIT)
+        .collect()
+}
 
-        let Some(primary_title_raw) = record.get(2) else {
-            continue;
-        };
-        let primary_title = primary_title_raw.to_string();
-        let primary_title_lower = primary_title.to_lowercase();
+/// Builds the index document for a title.basics row, or `None` for rows without an id or title.
+fn title_document(
+    record: &StringRecord,
+    fields: &TitleFields,
+    overview_column: Option<usize>,
+    lookups: &TitleLookups,
+) -> Result<Option<TantivyDocument>> {
+    let Some(tconst_raw) = record.get(0) else {
+        return Ok(None);
+    };
+    if tconst_raw.is_empty() || tconst_raw == "\\N" {
+        return Ok(None);
+    }
+    let tconst = tconst_raw.to_string();
 
-        let original_title = record
-            .get(3)
-            .filter(|value| *value != "\\N" && !value.is_empty())
-            .map(|value| value.to_string());
-        let start_year = parse_i64(record.get(5));
-        let end_year = parse_i64(record.get(6));
-        let genres: Vec<String> = record
-            .get(8)
-            .map(|value| {
-                value
-                    .split(',')
-                    .filter(|s| *s != "\\N" && !s.is_empty())
-                    .map(|s| s.to_string())
-                    .collect()
-            })
-            .unwrap_or_default();
+    let title_type = record.get(1).unwrap_or_default().to_string();
 
-        let mut doc = TantivyDocument::default();
-        doc.add_text(fields.tconst, &tconst);
-        doc.add_text(fields.title_type, &title_type);
-        doc.add_text(fields.primary_title, &primary_title);
-        doc.add_text(fields.search_titles, &primary_title);
+    let Some(primary_title_raw) = record.get(2) else {
+        return Ok(None);
+    };
+    let primary_title = primary_title_raw.to_string();
+    let primary_title_lower = primary_title.to_lowercase();
+
+    let original_title = record
+        .get(3)
+        .filter(|value| *value != "\\N" && !value.is_empty())
+        .map(|value| value.to_string());
+    let is_adult = record.get(4) == Some("1");
+    let start_year = parse_i64(record.get(5));
+    let end_year = parse_i64(record.get(6));
+    let runtime_minutes = parse_i64(record.get(7));
+    let genres: Vec<String> = record
+        .get(8)
+        .map(|value| {
+            value
+                .split(',')
+                .filter(|s| *s != "\\N" && !s.is_empty())
+                .map(|s| s.to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut doc = TantivyDocument::default();
+    doc.add_text(fields.tconst, &tconst);
+    doc.add_text(fields.title_type, &title_type);
+    doc.add_text(fields.primary_title, &primary_title);
+    doc.add_text(
+        fields.primary_title_sort,
+        title_sort_key(&primary_title, false),
+    );
+    doc.add_text(
+        fields.primary_title_sort_stripped,
+        title_sort_key(&primary_title, true),
+    );
+    doc.add_text(fields.search_titles, &primary_title);
+    if let Some(primary_title_exact) = fields.primary_title_exact {
+        doc.add_text(primary_title_exact, &primary_title_lower);
+    }
+    if let Some(primary_title_ngram) = fields.primary_title_ngram {
+        doc.add_text(primary_title_ngram, &primary_title);
+    }
+    if let Some(original_title) = original_title.as_ref() {
+        doc.add_text(fields.original_title, original_title);
+        doc.add_text(fields.search_titles, original_title);
         if let Some(primary_title_exact) = fields.primary_title_exact {
-            doc.add_text(primary_title_exact, &primary_title_lower);
+            doc.add_text(primary_title_exact, original_title.to_lowercase());
         }
+    }
+
+    if let Some(aka_titles) = lookups.akas.get(&tconst) {
+        // Case-insensitive so "The Matrix" and "THE MATRIX" only index once.
+        let mut seen = HashSet::new();
+        seen.insert(primary_title_lower.clone());
         if let Some(original_title) = original_title.as_ref() {
-            doc.add_text(fields.original_title, original_title);
-            doc.add_text(fields.search_titles, original_title);
-            if let Some(primary_title_exact) = fields.primary_title_exact {
-                doc.add_text(primary_title_exact, &original_title.to_lowercase());
-            }
+            seen.insert(original_title.to_lowercase());
         }
-
-        if let Some(aka_titles) = aka_map.get(&tconst) {
-            let mut seen = HashSet::new();
-            seen.insert(primary_title.clone());
-            if let Some(original_title) = original_title.as_ref() {
-                seen.insert(original_title.clone());
+        let mut regions = HashSet::new();
+        let mut languages = HashSet::new();
+        for aka in aka_titles {
+            if aka.region.is_some() || aka.language.is_some() {
+                doc.add_text(fields.localized_titles, aka.to_stored());
             }
-            for aka in aka_titles {
-                if seen.insert(aka.clone()) {
-                    doc.add_text(fields.search_titles, aka);
-                    if let Some(primary_title_exact) = fields.primary_title_exact {
-                        doc.add_text(primary_title_exact, &aka.to_lowercase());
-                    }
+            if let Some(region) = aka.region.as_deref() {
+                regions.insert(region.to_lowercase());
+            }
+            if let Some(language) = aka.language.as_deref() {
+                languages.insert(language.to_lowercase());
+            }
+            if seen.insert(aka.title.to_lowercase()) {
+                doc.add_text(fields.search_titles, &aka.title);
+                if let Some(primary_title_exact) = fields.primary_title_exact {
+                    doc.add_text(primary_title_exact, aka.title.to_lowercase());
                 }
             }
         }
+        for region in regions {
+            doc.add_text(fields.aka_regions, region);
+        }
+        for language in languages {
+            doc.add_text(fields.aka_languages, language);
+        }
+    }
 
-        if let Some(names) = principals_map.get(&tconst) {
-            for name in names {
+    let mut people_ids = HashSet::new();
+    if let Some(principals) = lookups.principals.get(&tconst) {
+        for (position, name) in search_principal_names(principals).into_iter().enumerate() {
+            if position < TOP_BILLED_PRINCIPALS {
                 doc.add_text(fields.search_titles, name);
+            } else {
+                doc.add_text(fields.supporting_principals, name);
             }
         }
-
-        for genre in genres {
-            doc.add_text(fields.genres, genre);
-        }
-        if let Some(year) = start_year {
-            doc.add_i64(fields.start_year, year);
-        }
-        if let Some(year) = end_year {
-            doc.add_i64(fields.end_year, year);
+        for principal in principals {
+            doc.add_text(fields.principals, principal.to_stored()?);
+            people_ids.insert(&*principal.nconst);
         }
-        if let Some((rating, votes)) = ratings_map.get(&tconst) {
-            doc.add_f64(fields.average_rating, *rating);
-            doc.add_i64(fields.num_votes, *votes);
-        }
-
-        writer
-            .add_document(doc)
-            .context("adding document to title index")?;
-        record_count += 1;
+    }
+    if let Some(crew) = lookups.crew.get(&tconst) {
+        people_ids.extend(crew.iter().map(String::as_str));
+    }
+    for nconst in people_ids {
+        doc.add_text(fields.people_ids, nconst);
+    }
 
-        if record_count.is_multiple_of(50_000) {
-            info!(processed = record_count, "title indexing progress");
+    for genre in genres {
+        doc.add_text(fields.genres, genre);
+    }
+    if let (Some(field), Some(column)) = (fields.overview, overview_column)
+        && let Some(overview) = record
+            .get(column)
+            .filter(|value| *value != "\\N" && !value.is_empty())
+    {
+        doc.add_text(field, overview);
+    }
+    if let Some(year) = start_year {
+        doc.add_i64(fields.start_year, year);
+    }
+    if let Some(year) = end_year {
+        doc.add_i64(fields.end_year, year);
+    }
+    doc.add_i64(fields.is_adult, i64::from(is_adult));
+    if let Some(minutes) = runtime_minutes {
+        doc.add_i64(fields.runtime_minutes, minutes);
+    }
+    if let Some((rating, votes)) = lookups.ratings.get(&tconst) {
+        doc.add_f64(fields.average_rating, *rating);
+        doc.add_i64(fields.num_votes, *votes);
+        doc.add_text(
+            fields.popularity_tier,
+            PopularityTier::for_votes(*votes).as_str(),
+        );
+    }
+    if let Some(episode) = lookups.episodes.get(&tconst) {
+        doc.add_text(fields.parent_tconst, &episode.parent);
+        if let Some(season) = episode.season {
+            doc.add_i64(fields.season_number, season);
+        }
+        if let Some(number) = episode.episode {
+            doc.add_i64(fields.episode_number, number);
         }
     }
 
-    info!(processed = record_count, "committing title index");
-    writer.commit().context("committing title index")?;
-    Ok(())
+    Ok(Some(doc))
 }
 
-async fn build_name_index(index_dir: &Path, names_path: PathBuf) -> Result<()> {
+async fn build_name_index(
+    index_dir: &Path,
+    names_path: PathBuf,
+    options: NameIndexOptions,
+) -> Result<()> {
     let index_dir = index_dir.to_path_buf();
-    task::spawn_blocking(move || build_name_index_sync(&index_dir, &names_path)).await??;
+    task::spawn_blocking(move || build_name_index_sync(&index_dir, &names_path, options)).await??;
     Ok(())
 }
 
-fn build_name_index_sync(index_dir: &Path, names_path: &Path) -> Result<()> {
+fn build_name_index_sync(
+    index_dir: &Path,
+    names_path: &Path,
+    options: NameIndexOptions,
+) -> Result<()> {
+    let started = Instant::now();
     if index_dir.exists() {
         std::fs::remove_dir_all(index_dir)
             .with_context(|| format!("clearing existing index at {}", index_dir.display()))?;
@@ -539,26 +2070,34 @@ fn build_name_index_sync(index_dir: &Path, names_path: &Path) -> Result<()> {
         .with_context(|| format!("creating index directory {}", index_dir.display()))?;
 
     let schema = build_name_schema();
-    let index = Index::create_in_dir(index_dir, schema.clone())
+    let index = Index::builder()
+        .schema(schema.clone())
+        .settings(options.docstore_compression.index_settings())
+        .create_in_dir(index_dir)
         .with_context(|| format!("creating name index in {}", index_dir.display()))?;
 
     let mut writer = index
-        .writer::<TantivyDocument>(128 * 1024 * 1024)
+        .writer::<TantivyDocument>(options.writer_mb * 1024 * 1024)
         .context("creating name index writer")?;
 
     let fields = NameFields::new(&schema)?;
 
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .has_headers(true)
-        .flexible(true)
-        .from_path(names_path)
-        .with_context(|| format!("opening {}", names_path.display()))?;
+    let mut reader = tsv_reader(names_path, NAME_BASICS_HEADER)?;
 
     let mut record_count = 0usize;
+    let mut malformed_count = 0usize;
 
     for result in reader.records() {
         let record = result.with_context(|| format!("reading {}", names_path.display()))?;
+        if !has_expected_columns(
+            &record,
+            NAME_BASICS_COLUMNS,
+            options.malformed_rows,
+            names_path,
+        )? {
+            malformed_count += 1;
+            continue;
+        }
 
         let Some(nconst_raw) = record.get(0) else {
             continue;
@@ -573,18 +2112,28 @@ fn build_name_index_sync(index_dir: &Path, names_path: &Path) -> Result<()> {
             continue;
         }
 
-        let birth_year = parse_i64(record.get(2));
-        let death_year = parse_i64(record.get(3));
+        let (birth_year, death_year) = sanitize_life_years(
+            parse_i64(record.get(2)),
+            parse_i64(record.get(3)),
+            options.year_bounds,
+        );
         let primary_profession = record.get(4).unwrap_or_default().to_string();
-        let known_for_titles = record.get(5).unwrap_or_default().to_string();
+        // Stored verbatim: IMDb lists the most significant titles first.
+        let known_for_titles = record
+            .get(5)
+            .filter(|value| *value != "\\N")
+            .unwrap_or_default()
+            .to_string();
 
         let mut doc = TantivyDocument::default();
         doc.add_text(fields.nconst, &nconst);
         doc.add_text(fields.primary_name, &primary_name);
         doc.add_text(fields.primary_name_search, &primary_name);
+        for code in phonetic_codes(&primary_name) {
+            doc.add_text(fields.primary_name_phonetic, code);
+        }
         if !primary_profession.is_empty() {
             doc.add_text(fields.primary_profession, &primary_profession);
-            doc.add_text(fields.primary_name_search, &primary_profession);
         }
         if !known_for_titles.is_empty() {
             doc.add_text(fields.known_for_titles, &known_for_titles);
@@ -606,19 +2155,27 @@ fn build_name_index_sync(index_dir: &Path, names_path: &Path) -> Result<()> {
         }
     }
 
+    if malformed_count > 0 {
+        warn!(skipped = malformed_count, path = %names_path.display(), "skipped malformed rows");
+    }
     info!(processed = record_count, "committing name index");
-    writer.commit().context("committing name index")?;
-    Ok(())
+    let summary = BuildSummary::new(
+        NAME_SCHEMA_VERSION,
+        options.build_settings(),
+        record_count,
+        malformed_count,
+        started,
+        &[names_path],
+    );
+    summary
+        .commit(&mut writer)
+        .context("committing name index")?;
+    BuildMeta::from(&summary).write(index_dir)
 }
 
 fn load_ratings_map(path: &Path) -> Result<HashMap<String, (f64, i64)>> {
     let mut map = HashMap::new();
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .has_headers(true)
-        .flexible(true)
-        .from_path(path)
-        .with_context(|| format!("opening {}", path.display()))?;
+    let mut reader = tsv_reader(path, TITLE_RATINGS_HEADER)?;
 
     for result in reader.records() {
         let record = result.with_context(|| format!("reading {}", path.display()))?;
@@ -639,14 +2196,9 @@ fn load_ratings_map(path: &Path) -> Result<HashMap<String, (f64, i64)>> {
     Ok(map)
 }
 
-fn load_aka_map(path: &Path) -> Result<HashMap<String, Vec<String>>> {
-    let mut map: HashMap<String, Vec<String>> = HashMap::new();
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .has_headers(true)
-        .flexible(true)
-        .from_path(path)
-        .with_context(|| format!("opening {}", path.display()))?;
+fn load_aka_map(path: &Path) -> Result<HashMap<String, Vec<AkaTitle>>> {
+    let mut map: HashMap<String, Vec<AkaTitle>> = HashMap::new();
+    let mut reader = tsv_reader(path, TITLE_AKAS_HEADER)?;
 
     for result in reader.records() {
         let record = result.with_context(|| format!("reading {}", path.display()))?;
@@ -659,22 +2211,100 @@ fn load_aka_map(path: &Path) -> Result<HashMap<String, Vec<String>>> {
         if title.is_empty() || title == "\\N" {
             continue;
         }
-        map.entry(title_id.to_string())
-            .or_default()
-            .push(title.to_string());
+        let optional = |value: Option<&str>| {
+            value
+                .filter(|value| !value.is_empty() && *value != "\\N")
+                .map(str::to_string)
+        };
+        map.entry(title_id.to_string()).or_default().push(AkaTitle {
+            title: title.to_string(),
+            region: optional(record.get(3)),
+            language: optional(record.get(4)),
+        });
     }
 
     Ok(map)
 }
 
-fn load_name_map(path: &Path) -> Result<HashMap<String, String>> {
+/// Position of an episode within its series, from title.episode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EpisodeOf {
+    parent: String,
+    season: Option<i64>,
+    episode: Option<i64>,
+}
+
+/// Loads the series, season and episode number of every episode, keyed by the episode tconst.
+fn load_episode_map(path: &Path) -> Result<HashMap<String, EpisodeOf>> {
     let mut map = HashMap::new();
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .has_headers(true)
-        .flexible(true)
-        .from_path(path)
-        .with_context(|| format!("opening {}", path.display()))?;
+    let mut reader = tsv_reader(path, TITLE_EPISODE_HEADER)?;
+
+    for result in reader.records() {
+        let record = result.with_context(|| format!("reading {}", path.display()))?;
+        let (Some(tconst), Some(parent)) = (record.get(0), record.get(1)) else {
+            continue;
+        };
+        if parent.is_empty() || parent == "\\N" {
+            continue;
+        }
+        map.insert(
+            tconst.to_string(),
+            EpisodeOf {
+                parent: parent.to_string(),
+                season: parse_i64(record.get(2)),
+                episode: parse_i64(record.get(3)),
+            },
+        );
+    }
+
+    Ok(map)
+}
+
+/// Loads the directors and writers of each title from title.crew, keyed by tconst.
+fn load_crew_map(path: &Path) -> Result<HashMap<String, Vec<String>>> {
+    let mut map: HashMap<String, Vec<String>> = HashMap::new();
+    let mut reader = tsv_reader(path, TITLE_CREW_HEADER)?;
+
+    for result in reader.records() {
+        let record = result.with_context(|| format!("reading {}", path.display()))?;
+        let Some(tconst) = record.get(0) else {
+            continue;
+        };
+        let people: Vec<String> = [record.get(1), record.get(2)]
+            .into_iter()
+            .flatten()
+            .flat_map(|value| value.split(','))
+            .filter(|nconst| !nconst.is_empty() && *nconst != "\\N")
+            .map(str::to_string)
+            .collect();
+        if !people.is_empty() {
+            map.insert(tconst.to_string(), people);
+        }
+    }
+
+    Ok(map)
+}
+
+/// Loads principals keyed by tconst, or an empty map when principals enrichment is disabled
+/// and no principals file is given.
+fn load_principals_enrichment(
+    names_path: &Path,
+    principals_path: Option<&Path>,
+) -> Result<PrincipalsMap> {
+    let Some(principals_path) = principals_path else {
+        info!("principals enrichment disabled; skipping title.principals");
+        return Ok(HashMap::new());
+    };
+    let name_lookup = load_name_map(names_path)?;
+    let principals = load_principals_map(principals_path, &name_lookup)?;
+    info!(titles = principals.len(), "loaded principals lookup");
+    Ok(principals)
+}
+
+/// Person names by nconst; both are shared by every credit of the person.
+fn load_name_map(path: &Path) -> Result<HashMap<Arc<str>, Arc<str>>> {
+    let mut map = HashMap::new();
+    let mut reader = tsv_reader(path, NAME_BASICS_HEADER)?;
 
     for result in reader.records() {
         let record = result.with_context(|| format!("reading {}", path.display()))?;
@@ -687,23 +2317,25 @@ fn load_name_map(path: &Path) -> Result<HashMap<String, String>> {
         if nconst.is_empty() || nconst == "\\N" || primary_name.is_empty() {
             continue;
         }
-        map.insert(nconst.to_string(), primary_name.to_string());
+        map.insert(Arc::from(nconst), Arc::from(primary_name));
     }
 
     Ok(map)
 }
 
+/// `value` unless it is missing, empty or the dataset's `\\N` null marker.
+fn present(value: Option<&str>) -> Option<&str> {
+    value.filter(|value| *value != "\\N" && !value.is_empty())
+}
+
+/// Loads title.principals keyed by tconst, each list ordered by IMDb's `ordering` column.
 fn load_principals_map(
     path: &Path,
-    name_lookup: &HashMap<String, String>,
-) -> Result<HashMap<String, Vec<String>>> {
-    let mut map: HashMap<String, HashSet<String>> = HashMap::new();
-    let mut reader = ReaderBuilder::new()
-        .delimiter(b'\t')
-        .has_headers(true)
-        .flexible(true)
-        .from_path(path)
-        .with_context(|| format!("opening {}", path.display()))?;
+    name_lookup: &HashMap<Arc<str>, Arc<str>>,
+) -> Result<PrincipalsMap> {
+    let mut map: PrincipalsMap = HashMap::new();
+    let mut categories: HashSet<Arc<str>> = HashSet::new();
+    let mut reader = tsv_reader(path, TITLE_PRINCIPALS_HEADER)?;
 
     for result in reader.records() {
         let record = result.with_context(|| format!("reading {}", path.display()))?;
@@ -718,19 +2350,128 @@ fn load_principals_map(
             continue;
         }
 
-        let Some(name) = name_lookup.get(nconst) else {
+        let Some((nconst, name)) = name_lookup.get_key_value(nconst) else {
             continue;
         };
 
+        let category = present(record.get(3)).unwrap_or_default();
+        let category = match categories.get(category) {
+            Some(interned) => Arc::clone(interned),
+            None => {
+                let interned: Arc<str> = Arc::from(category);
+                categories.insert(Arc::clone(&interned));
+                interned
+            }
+        };
+        let characters = present(record.get(5))
+            .and_then(|raw| serde_json::from_str::<Vec<Box<str>>>(raw).ok())
+            .unwrap_or_default()
+            .into_boxed_slice();
+
         map.entry(tconst.to_string())
             .or_default()
-            .insert(name.clone());
+            .push(PrincipalCredit {
+                ordering: record
+                    .get(1)
+                    .and_then(|value| value.parse().ok())
+                    .unwrap_or(u32::MAX),
+                nconst: Arc::clone(nconst),
+                primary_name: Arc::clone(name),
+                category,
+                job: present(record.get(4)).map(Box::from),
+                characters,
+            });
     }
 
-    Ok(map
-        .into_iter()
-        .map(|(tconst, names)| (tconst, names.into_iter().collect()))
-        .collect())
+    for principals in map.values_mut() {
+        principals.sort_by_key(|principal| principal.ordering);
+        principals.shrink_to_fit();
+    }
+    Ok(map)
+}
+
+/// Opens a tab-separated dataset, plain or gzip-compressed (see [`open_dataset`]), after
+/// checking that its header starts with `expected`.
+fn tsv_reader(path: &Path, expected: &[&str]) -> Result<Reader<Box<dyn Read + Send>>> {
+    let mut reader = ReaderBuilder::new()
+        .delimiter(b'\t')
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(open_dataset(path)?);
+    let headers = reader
+        .headers()
+        .with_context(|| format!("reading headers of {}", path.display()))?;
+    check_header(headers, expected).with_context(|| {
+        format!(
+            "unexpected column layout in {}; the dataset format may have changed",
+            path.display()
+        )
+    })?;
+    Ok(reader)
+}
+
+/// Fails on the first position where `headers` differs from `expected`.
+fn check_header(headers: &StringRecord, expected: &[&str]) -> Result<()> {
+    for (position, column) in expected.iter().enumerate() {
+        match headers.get(position) {
+            Some(found) if found == *column => {}
+            Some(found) => {
+                return Err(anyhow!(
+                    "expected column {position} to be `{column}`, found `{found}`"
+                ));
+            }
+            None => {
+                return Err(anyhow!(
+                    "expected column {position} to be `{column}`, but the header has only {} columns",
+                    headers.len()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `record` has at least `expected` columns, failing or reporting a skip per `policy`.
+fn has_expected_columns(
+    record: &StringRecord,
+    expected: usize,
+    policy: MalformedRowPolicy,
+    path: &Path,
+) -> Result<bool> {
+    if record.len() >= expected {
+        return Ok(true);
+    }
+    let line = record.position().map(|pos| pos.line()).unwrap_or_default();
+    match policy {
+        MalformedRowPolicy::Skip => {
+            debug!(
+                path = %path.display(),
+                line,
+                columns = record.len(),
+                expected,
+                "skipping malformed row"
+            );
+            Ok(false)
+        }
+        MalformedRowPolicy::Fail => Err(anyhow!(
+            "{}:{line}: expected at least {expected} columns, found {}",
+            path.display(),
+            record.len()
+        )),
+    }
+}
+
+/// Drops birth/death years outside `bounds` and death years that precede the birth year.
+fn sanitize_life_years(
+    birth_year: Option<i64>,
+    death_year: Option<i64>,
+    bounds: YearBounds,
+) -> (Option<i64>, Option<i64>) {
+    let birth_year = birth_year.filter(|year| bounds.contains(*year));
+    let death_year = death_year
+        .filter(|year| bounds.contains(*year))
+        .filter(|year| birth_year.is_none_or(|birth| *year >= birth));
+    (birth_year, death_year)
 }
 
 fn parse_i64(value: Option<&str>) -> Option<i64> {
@@ -748,3 +2489,890 @@ fn parse_f64(value: Option<&str>) -> Option<f64> {
     }
     value.parse().ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Bound;
+
+    use tantivy::collector::TopDocs;
+    use tantivy::query::RangeQuery;
+    use tantivy::schema::Value;
+
+    use super::*;
+
+    #[test]
+    fn short_name_rows_are_skipped() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-names-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt0133093\n\
+             nm0000001\tFred Astaire\t1899\n",
+        )
+        .unwrap();
+        let index_dir = dir.join("index");
+
+        build_name_index_sync(&index_dir, &names_path, NameIndexOptions::default()).unwrap();
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        assert_eq!(index.reader().unwrap().searcher().num_docs(), 1);
+        let summary = BuildSummary::load(&index).unwrap().unwrap();
+        assert_eq!((summary.processed, summary.skipped), (1, 1));
+        assert_eq!(summary.schema_version, NAME_SCHEMA_VERSION);
+        assert_eq!(
+            summary.sources.keys().collect::<Vec<_>>(),
+            vec!["name.basics.tsv"]
+        );
+        let meta: BuildMeta =
+            serde_json::from_slice(&std::fs::read(index_dir.join(BUILD_META_FILE)).unwrap())
+                .unwrap();
+        assert_eq!(meta, BuildMeta::from(&summary));
+        assert_eq!(meta.documents, 1);
+
+        let searcher = index.reader().unwrap().searcher();
+        let fields = NameFields::new(&index.schema()).unwrap();
+        let hits = |field: Field| {
+            let query = TermQuery::new(
+                Term::from_field_text(field, "actor"),
+                IndexRecordOption::Basic,
+            );
+            searcher
+                .search(&query, &TopDocs::with_limit(1))
+                .unwrap()
+                .len()
+        };
+        assert_eq!(hits(fields.primary_profession), 1);
+        assert_eq!(hits(fields.primary_name_search), 0);
+
+        let strict = NameIndexOptions {
+            malformed_rows: MalformedRowPolicy::Fail,
+            ..NameIndexOptions::default()
+        };
+        assert!(build_name_index_sync(&index_dir, &names_path, strict).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn outdated_schema_versions_are_rebuilt() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-schema-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt0133093\n",
+        )
+        .unwrap();
+        let index_dir = dir.join("index");
+        build_name_index_sync(&index_dir, &names_path, NameIndexOptions::default()).unwrap();
+
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        let mut summary = BuildSummary::load(&index).unwrap().unwrap();
+        summary.schema_version = NAME_SCHEMA_VERSION + 1;
+        let mut writer = index.writer::<TantivyDocument>(15_000_000).unwrap();
+        summary.commit(&mut writer).unwrap();
+        drop(writer);
+
+        let names = prepare_name_index(&index_dir, names_path, NameIndexOptions::default())
+            .await
+            .unwrap();
+        let summary = BuildSummary::load(names.reader.searcher().index())
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.schema_version, NAME_SCHEMA_VERSION);
+        assert_eq!(names.reader.searcher().num_docs(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn indexes_without_a_summary_or_with_other_settings_are_rebuilt() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-settings-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt0133093\n",
+        )
+        .unwrap();
+        let index_dir = dir.join("index");
+        build_name_index_sync(&index_dir, &names_path, NameIndexOptions::default()).unwrap();
+
+        // A plain commit drops the payload, as an index from an unknown build would lack one.
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        let mut writer = index.writer::<TantivyDocument>(15_000_000).unwrap();
+        writer.commit().unwrap();
+        drop(writer);
+        assert_eq!(BuildSummary::load(&index).unwrap(), None);
+
+        let names = prepare_name_index(&index_dir, names_path.clone(), NameIndexOptions::default())
+            .await
+            .unwrap();
+        let summary = BuildSummary::load(names.reader.searcher().index())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            summary
+                .settings
+                .map(|settings| settings.docstore_compression),
+            Some("lz4".to_string())
+        );
+
+        let zstd = NameIndexOptions {
+            docstore_compression: DocstoreCompression::Zstd(Some(3)),
+            ..NameIndexOptions::default()
+        };
+        let names = prepare_name_index(&index_dir, names_path, zstd)
+            .await
+            .unwrap();
+        let index = names.reader.searcher().index().clone();
+        assert_eq!(
+            index.settings().docstore_compression,
+            Compressor::Zstd(ZstdCompressor {
+                compression_level: Some(3)
+            })
+        );
+        assert_eq!(names.reader.searcher().num_docs(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn indexes_are_rebuilt_when_their_datasets_change() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-sources-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt0133093\n",
+        )
+        .unwrap();
+        std::fs::write(dir.join("name.basics.tsv.source"), "etag:\"v1\"").unwrap();
+        let index_dir = dir.join("index");
+        build_name_index_sync(&index_dir, &names_path, NameIndexOptions::default()).unwrap();
+
+        // Marks the build, so a rebuild is visible as the mark disappearing.
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        let mut summary = BuildSummary::load(&index).unwrap().unwrap();
+        assert_eq!(summary.fingerprints["name.basics.tsv"], "etag:\"v1\"");
+        summary.processed = 99;
+        let mut writer = index.writer::<TantivyDocument>(15_000_000).unwrap();
+        summary.commit(&mut writer).unwrap();
+        drop(writer);
+
+        // Datasets this index is not built from do not count.
+        std::fs::write(dir.join("title.basics.tsv.source"), "etag:\"v2\"").unwrap();
+        let names = prepare_name_index(&index_dir, names_path.clone(), NameIndexOptions::default())
+            .await
+            .unwrap();
+        let summary = BuildSummary::load(names.reader.searcher().index())
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.processed, 99);
+        drop(names);
+
+        std::fs::write(dir.join("name.basics.tsv.source"), "etag:\"v2\"").unwrap();
+        let names = prepare_name_index(&index_dir, names_path, NameIndexOptions::default())
+            .await
+            .unwrap();
+        let summary = BuildSummary::load(names.reader.searcher().index())
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.processed, 1);
+        assert_eq!(summary.fingerprints["name.basics.tsv"], "etag:\"v2\"");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Builds a title index from `basics` with empty ratings/akas files, clearing `dir` first.
+    fn build_titles_from_tsv(dir: &Path, basics: &str, options: &TitleIndexOptions) -> Index {
+        build_titles_with_principals(dir, basics, &HashMap::new(), options)
+    }
+
+    fn build_titles_with_principals(
+        dir: &Path,
+        basics: &str,
+        principals: &PrincipalsMap,
+        options: &TitleIndexOptions,
+    ) -> Index {
+        let _ = std::fs::remove_dir_all(dir);
+        std::fs::create_dir_all(dir).unwrap();
+        let basics_path = dir.join("title.basics.tsv");
+        std::fs::write(&basics_path, basics).unwrap();
+        let ratings_path = dir.join("title.ratings.tsv");
+        std::fs::write(&ratings_path, "tconst\taverageRating\tnumVotes\n").unwrap();
+        let akas_path = dir.join("title.akas.tsv");
+        std::fs::write(
+            &akas_path,
+            "titleId\tordering\ttitle\tregion\tlanguage\ttypes\tattributes\tisOriginalTitle\n",
+        )
+        .unwrap();
+        let crew_path = dir.join("title.crew.tsv");
+        std::fs::write(&crew_path, "tconst\tdirectors\twriters\n").unwrap();
+        let index_dir = dir.join("index");
+
+        let sources = TitleSources {
+            basics: basics_path,
+            ratings: ratings_path,
+            akas: akas_path,
+            crew: Some(crew_path),
+            episodes: None,
+            principals: None,
+        };
+        build_title_index_sync(&index_dir, &sources, principals, options).unwrap();
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        register_title_tokenizers(&index, options).unwrap();
+        index
+    }
+
+    #[test]
+    fn overview_column_is_optional() {
+        let options = TitleIndexOptions {
+            overview_column: Some("overview".to_string()),
+            ..TitleIndexOptions::default()
+        };
+        let dir = std::env::temp_dir().join(format!("imdb-rs-overview-{}", std::process::id()));
+
+        let enriched = build_titles_from_tsv(
+            &dir,
+            "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\toverview\n\
+             tt0133093\tmovie\tThe Matrix\tThe Matrix\t0\t1999\t\\N\t136\tAction,Sci-Fi\tA hacker learns reality is simulated.\n",
+            &options,
+        );
+        let titles = TitleIndex::from_index(&enriched, &options).unwrap();
+        let overview = titles.fields.overview.expect("overview field");
+        let searcher = titles.reader.searcher();
+        let query = titles.query_parser.parse_query("hacker").unwrap();
+        let hits = searcher.search(&query, &TopDocs::with_limit(1)).unwrap();
+        assert_eq!(hits.len(), 1);
+        let doc: TantivyDocument = searcher.doc(hits[0].1).unwrap();
+        assert_eq!(
+            doc.get_first(overview).and_then(|value| value.as_str()),
+            Some("A hacker learns reality is simulated.")
+        );
+        let settings = BuildSummary::load(&enriched)
+            .unwrap()
+            .and_then(|summary| summary.settings)
+            .unwrap();
+        assert_eq!(settings.overview_column.as_deref(), Some("overview"));
+        assert_eq!(settings.episodes, Some(true));
+        assert_ne!(settings, TitleIndexOptions::default().build_settings());
+
+        let standard = build_titles_from_tsv(
+            &dir,
+            "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n\
+             tt0133093\tmovie\tThe Matrix\tThe Matrix\t0\t1999\t\\N\t136\tAction,Sci-Fi\n",
+            &options,
+        );
+        let titles = TitleIndex::from_index(&standard, &options).unwrap();
+        let searcher = titles.reader.searcher();
+        assert_eq!(searcher.num_docs(), 1);
+        let query = titles.query_parser.parse_query("hacker").unwrap();
+        assert_eq!(searcher.search(&query, &Count).unwrap(), 0);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn top_billed_principals_outrank_supporting_ones() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-billing-{}", std::process::id()));
+        let principal = |ordering: u32, nconst: &str, name: &str| PrincipalCredit {
+            ordering,
+            nconst: nconst.into(),
+            primary_name: name.into(),
+            category: "actor".into(),
+            job: None,
+            characters: Box::default(),
+        };
+        let cast = |star_position: u32| {
+            (1..=6)
+                .map(|ordering| {
+                    if ordering == star_position {
+                        principal(ordering, "nm0000206", "Keanu Reeves")
+                    } else {
+                        principal(
+                            ordering,
+                            &format!("nm100000{ordering}"),
+                            &format!("Extra {ordering}"),
+                        )
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let principals = HashMap::from([
+            ("tt0000001".to_string(), cast(5)),
+            ("tt0000002".to_string(), cast(1)),
+        ]);
+        let index = build_titles_with_principals(
+            &dir,
+            "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n\
+             tt0000001\tmovie\tQuiet Days\tQuiet Days\t0\t2001\t\\N\t90\tDrama\n\
+             tt0000002\tmovie\tLoud Nights\tLoud Nights\t0\t2002\t\\N\t90\tDrama\n",
+            &principals,
+            &TitleIndexOptions::default(),
+        );
+        let titles = TitleIndex::from_index(&index, &TitleIndexOptions::default()).unwrap();
+        let searcher = titles.reader.searcher();
+        let query = titles.query_parser.parse_query("keanu reeves").unwrap();
+        let hits = searcher.search(&query, &TopDocs::with_limit(2)).unwrap();
+        let tconsts: Vec<String> = hits
+            .iter()
+            .map(|(_, address)| {
+                let doc: TantivyDocument = searcher.doc(*address).unwrap();
+                doc.get_first(titles.fields.tconst)
+                    .and_then(|value| value.as_str())
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(tconsts, ["tt0000002", "tt0000001"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn field_boosts_apply_without_rebuilding() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-boosts-{}", std::process::id()));
+        let index = build_titles_from_tsv(
+            &dir,
+            "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n\
+             tt0000001\tmovie\tDrama Queen\tDrama Queen\t0\t2001\t\\N\t90\tComedy\n\
+             tt0000002\tmovie\tQuiet Days\tQuiet Days\t0\t2002\t\\N\t90\tDrama\n",
+            &TitleIndexOptions::default(),
+        );
+        let top_hit = |options: &TitleIndexOptions| {
+            let titles = TitleIndex::from_index(&index, options).unwrap();
+            let searcher = titles.reader.searcher();
+            let query = titles.query_parser.parse_query("drama").unwrap();
+            let hits = searcher.search(&query, &TopDocs::with_limit(1)).unwrap();
+            let doc: TantivyDocument = searcher.doc(hits[0].1).unwrap();
+            doc.get_first(titles.fields.tconst)
+                .and_then(|value| value.as_str())
+                .map(str::to_string)
+        };
+
+        assert_eq!(
+            top_hit(&TitleIndexOptions::default()).as_deref(),
+            Some("tt0000001")
+        );
+        let genre_heavy = TitleIndexOptions {
+            primary_title_boost: 0.1,
+            original_title_boost: 0.1,
+            genres_boost: 10.0,
+            ..TitleIndexOptions::default()
+        };
+        assert_eq!(top_hit(&genre_heavy).as_deref(), Some("tt0000002"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn updates_replace_only_the_requested_titles() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-update-{}", std::process::id()));
+        let options = TitleIndexOptions::default();
+        let header = "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n";
+        let index = build_titles_from_tsv(
+            &dir,
+            &format!(
+                "{header}\
+                 tt0133093\tmovie\tThe Matrix\tThe Matrix\t0\t1999\t\\N\t136\tAction\n\
+                 tt0234215\tmovie\tThe Matrix Reloaded\tThe Matrix Reloaded\t0\t2003\t\\N\t138\tAction\n\
+                 tt0242653\tmovie\tThe Matrix Revolutions\tThe Matrix Revolutions\t0\t2003\t\\N\t129\tAction\n"
+            ),
+            &options,
+        );
+        let fields = TitleFields::new(&index.schema()).unwrap();
+        let summary = BuildSummary::load(&index).unwrap();
+
+        // The daily delta: a new rating for The Matrix, and Revolutions pulled from the dataset.
+        let basics_path = dir.join("title.basics.tsv");
+        std::fs::write(
+            &basics_path,
+            format!(
+                "{header}\
+                 tt0133093\tmovie\tThe Matrix\tThe Matrix\t0\t1999\t\\N\t136\tAction,Sci-Fi\n\
+                 tt0234215\tmovie\tThe Matrix Reloaded\tThe Matrix Reloaded\t0\t2003\t\\N\t138\tAction\n"
+            ),
+        )
+        .unwrap();
+        let ratings_path = dir.join("title.ratings.tsv");
+        std::fs::write(
+            &ratings_path,
+            "tconst\taverageRating\tnumVotes\ntt0133093\t8.7\t2000000\ntt0234215\t7.2\t650000\n",
+        )
+        .unwrap();
+        let principals = HashMap::new();
+        let lookups = TitleLookups::load(
+            &ratings_path,
+            &dir.join("title.akas.tsv"),
+            None,
+            None,
+            &principals,
+        )
+        .unwrap();
+        // tt9999999 was never indexed, so nothing is removed for it.
+        let tconsts = HashSet::from([
+            "tt0133093".to_string(),
+            "tt0242653".to_string(),
+            "tt9999999".to_string(),
+        ]);
+
+        let update = update_title_index_sync(
+            &dir.join("index"),
+            &basics_path,
+            &lookups,
+            &options,
+            &tconsts,
+            &[basics_path.as_path(), ratings_path.as_path()],
+        )
+        .unwrap();
+        assert_eq!(
+            update,
+            TitleUpdate {
+                updated: 1,
+                removed: 1
+            }
+        );
+
+        let reader = index.reader().unwrap();
+        reader.reload().unwrap();
+        let searcher = reader.searcher();
+        assert_eq!(searcher.num_docs(), 2);
+        let votes = |tconst: &str| {
+            let query = TermQuery::new(
+                Term::from_field_text(fields.tconst, tconst),
+                IndexRecordOption::Basic,
+            );
+            let (_, addr) = searcher.search(&query, &TopDocs::with_limit(1)).unwrap()[0];
+            let doc: TantivyDocument = searcher.doc(addr).unwrap();
+            doc.get_first(fields.num_votes)
+                .and_then(|value| value.as_i64())
+        };
+        assert_eq!(votes("tt0133093"), Some(2_000_000));
+        // Not requested, so untouched even though the ratings file now has it.
+        assert_eq!(votes("tt0234215"), None);
+        let refreshed = BuildSummary::load(&index).unwrap().unwrap();
+        let summary = summary.unwrap();
+        assert_eq!(
+            (refreshed.processed, &refreshed.built_at),
+            (summary.processed, &summary.built_at)
+        );
+        assert!(refreshed.updated_at.is_some());
+        assert!(refreshed.sources.contains_key("title.ratings.tsv"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn episodes_carry_their_series_season_and_number() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-episodes-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, contents: &str| {
+            let path = dir.join(name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        };
+        let sources = TitleSources {
+            basics: write(
+                "title.basics.tsv",
+                "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n\
+                 tt0903747\ttvSeries\tBreaking Bad\tBreaking Bad\t0\t2008\t2013\t49\tDrama\n\
+                 tt0959621\ttvEpisode\tPilot\tPilot\t0\t2008\t\\N\t58\tDrama\n",
+            ),
+            ratings: write("title.ratings.tsv", "tconst\taverageRating\tnumVotes\n"),
+            akas: write(
+                "title.akas.tsv",
+                "titleId\tordering\ttitle\tregion\tlanguage\ttypes\tattributes\tisOriginalTitle\n",
+            ),
+            crew: None,
+            episodes: Some(write(
+                "title.episode.tsv",
+                "tconst\tparentTconst\tseasonNumber\tepisodeNumber\n\
+                 tt0959621\ttt0903747\t1\t1\n",
+            )),
+            principals: None,
+        };
+        let index_dir = dir.join("index");
+        let options = TitleIndexOptions::default();
+        build_title_index_sync(&index_dir, &sources, &HashMap::new(), &options).unwrap();
+
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        let fields = TitleFields::new(&index.schema()).unwrap();
+        let searcher = index.reader().unwrap().searcher();
+        let query = TermQuery::new(
+            Term::from_field_text(fields.parent_tconst, "tt0903747"),
+            IndexRecordOption::Basic,
+        );
+        let hits = searcher.search(&query, &TopDocs::with_limit(10)).unwrap();
+        assert_eq!(hits.len(), 1);
+        let doc: TantivyDocument = searcher.doc(hits[0].1).unwrap();
+        assert_eq!(
+            doc.get_first(fields.tconst)
+                .and_then(|value| value.as_str()),
+            Some("tt0959621")
+        );
+        assert_eq!(
+            doc.get_first(fields.season_number)
+                .and_then(|value| value.as_i64()),
+            Some(1)
+        );
+        assert_eq!(
+            doc.get_first(fields.episode_number)
+                .and_then(|value| value.as_i64()),
+            Some(1)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn auto_fuzzy_distance_follows_the_longest_word() {
+        let auto = FuzzyDistance::Auto;
+        assert_eq!(auto.for_term("ran"), 0);
+        assert_eq!(auto.for_term("the matrix"), 1);
+        assert_eq!(auto.for_term("up Schwarzenegger"), 2);
+        assert_eq!(auto.for_term("genres:drama"), 1);
+        assert_eq!(FuzzyDistance::Fixed(1).for_term("ran"), 1);
+
+        assert_eq!("auto".parse::<FuzzyDistance>().unwrap(), auto);
+        assert_eq!(
+            "2".parse::<FuzzyDistance>().unwrap(),
+            FuzzyDistance::Fixed(2)
+        );
+        assert!("3".parse::<FuzzyDistance>().is_err());
+    }
+
+    #[test]
+    fn popularity_tiers_split_vote_counts_at_powers_of_ten() {
+        assert_eq!(PopularityTier::for_votes(0), PopularityTier::Indie);
+        assert_eq!(PopularityTier::for_votes(9_999), PopularityTier::Indie);
+        assert_eq!(PopularityTier::for_votes(10_000), PopularityTier::Niche);
+        assert_eq!(PopularityTier::for_votes(100_000), PopularityTier::Popular);
+        assert_eq!(PopularityTier::for_votes(999_999), PopularityTier::Popular);
+        assert_eq!(
+            PopularityTier::for_votes(1_000_000),
+            PopularityTier::Blockbuster
+        );
+        for tier in PopularityTier::ALL {
+            assert_eq!(tier.as_str().parse::<PopularityTier>().unwrap(), tier);
+        }
+    }
+
+    #[test]
+    fn soundex_groups_similar_sounding_names() {
+        assert_eq!(soundex("Schwarzenegger").as_deref(), Some("S625"));
+        assert_eq!(soundex("Shwarzeneger").as_deref(), Some("S625"));
+        assert_eq!(soundex("Robert"), soundex("Rupert"));
+        assert_eq!(soundex("Ashcraft").as_deref(), Some("A261"));
+        assert_eq!(soundex("Tymczak").as_deref(), Some("T522"));
+        assert_eq!(soundex("Pfister").as_deref(), Some("P236"));
+        assert_eq!(soundex("Lee").as_deref(), Some("L000"));
+        assert_eq!(phonetic_codes("Keanu Reeves"), ["K500", "R120"]);
+        assert!(phonetic_codes("42").is_empty());
+    }
+
+    #[test]
+    fn reordered_columns_fail_loudly() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-header-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tdeathYear\tbirthYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t\\N\t1964\tactor\ttt0133093\n",
+        )
+        .unwrap();
+
+        let err =
+            build_name_index_sync(&dir.join("index"), &names_path, NameIndexOptions::default())
+                .unwrap_err();
+        let message = format!("{err:#}");
+        assert!(message.contains("name.basics.tsv"), "{message}");
+        assert!(
+            message.contains("expected column 2 to be `birthYear`, found `deathYear`"),
+            "{message}"
+        );
+
+        let ratings = StringRecord::from(vec!["tconst", "averageRating"]);
+        assert!(check_header(&ratings, TITLE_RATINGS_HEADER).is_err());
+        let extended = StringRecord::from(vec!["tconst", "averageRating", "numVotes", "extra"]);
+        assert!(check_header(&extended, TITLE_RATINGS_HEADER).is_ok());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn workers_index_every_batch() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-workers-{}", std::process::id()));
+        let mut basics = TITLE_BASICS_HEADER.join("\t") + "\n";
+        for id in 0..TITLE_BATCH_SIZE * 3 + 7 {
+            basics.push_str(&format!(
+                "tt{id:07}\tmovie\tTitle {id}\tTitle {id}\t0\t2000\t\\N\t90\tDrama\n"
+            ));
+        }
+        basics.push_str("tt9999999\tmovie\tTruncated\n");
+        let options = TitleIndexOptions {
+            workers: 4,
+            ..TitleIndexOptions::default()
+        };
+        let index = build_titles_from_tsv(&dir, &basics, &options);
+
+        let searcher = index.reader().unwrap().searcher();
+        assert_eq!(searcher.num_docs() as usize, TITLE_BATCH_SIZE * 3 + 7);
+        let summary = BuildSummary::load(&index).unwrap().unwrap();
+        assert_eq!(summary.processed as usize, TITLE_BATCH_SIZE * 3 + 7);
+        assert_eq!(summary.skipped, 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn series_end_years_are_indexed() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-titles-{}", std::process::id()));
+        let index = build_titles_from_tsv(
+            &dir,
+            "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n\
+             tt0903747\ttvSeries\tBreaking Bad\tBreaking Bad\t0\t2008\t2013\t49\tCrime,Drama,Thriller\n\
+             tt0386676\ttvSeries\tThe Office\tThe Office\t0\t2005\t\\N\t22\tComedy\n",
+            &TitleIndexOptions::default(),
+        );
+        let fields = TitleFields::new(&index.schema()).unwrap();
+        let searcher = index.reader().unwrap().searcher();
+
+        let ended_by_2015 = RangeQuery::new(
+            Bound::Unbounded,
+            Bound::Included(Term::from_field_i64(fields.end_year, 2015)),
+        );
+        let hits = searcher
+            .search(&ended_by_2015, &TopDocs::with_limit(10))
+            .unwrap();
+        assert_eq!(hits.len(), 1);
+        let doc: TantivyDocument = searcher.doc(hits[0].1).unwrap();
+        assert_eq!(
+            doc.get_first(fields.end_year)
+                .and_then(|value| value.as_i64()),
+            Some(2013)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn principals_keep_roles_in_billing_order() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-principals-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("title.principals.tsv");
+        std::fs::write(
+            &path,
+            "tconst\tordering\tnconst\tcategory\tjob\tcharacters\n\
+             tt0133093\t5\tnm0905154\tdirector\t\\N\t\\N\n\
+             tt0133093\t1\tnm0000206\tactor\t\\N\t[\"Neo\"]\n\
+             tt0133093\t2\tnm9999999\tactor\t\\N\t[\"Nobody\"]\n\
+             tt0234215\t1\tnm0000206\tactor\t\\N\t[\"Neo\"]\n",
+        )
+        .unwrap();
+        let names: HashMap<Arc<str>, Arc<str>> = HashMap::from([
+            ("nm0000206".into(), "Keanu Reeves".into()),
+            ("nm0905154".into(), "Lana Wachowski".into()),
+        ]);
+
+        let map = load_principals_map(&path, &names).unwrap();
+        let principals = &map["tt0133093"];
+        assert_eq!(principals.len(), 2);
+        assert_eq!(&*principals[0].nconst, "nm0000206");
+        assert_eq!(&*principals[0].category, "actor");
+        assert_eq!(principals[0].characters, [Box::from("Neo")].into());
+        assert_eq!(&*principals[1].category, "director");
+        assert_eq!(principals[1].job, None);
+        assert_eq!(
+            Principal::from_stored(&principals[0].to_stored().unwrap()),
+            Some(Principal {
+                ordering: 1,
+                nconst: "nm0000206".to_string(),
+                primary_name: "Keanu Reeves".to_string(),
+                category: "actor".to_string(),
+                job: None,
+                characters: vec!["Neo".to_string()],
+            })
+        );
+        // Credits of the same person share one name and category allocation.
+        let sequel = &map["tt0234215"][0];
+        assert!(Arc::ptr_eq(
+            &principals[0].primary_name,
+            &sequel.primary_name
+        ));
+        assert!(Arc::ptr_eq(&principals[0].category, &sequel.category));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn search_principal_names_are_capped_and_deduplicated() {
+        let principal = |nconst: &str, name: &str| PrincipalCredit {
+            ordering: 0,
+            nconst: nconst.into(),
+            primary_name: name.into(),
+            category: "actor".into(),
+            job: None,
+            characters: Box::default(),
+        };
+        let mut principals = vec![
+            principal("nm0000206", "Keanu Reeves"),
+            principal("nm0000207", "KEANU REEVES"),
+        ];
+        principals.extend((0..20).map(|i| principal(&format!("nm1{i:06}"), &format!("Extra {i}"))));
+
+        let names = search_principal_names(&principals);
+        assert_eq!(names.len(), SEARCH_PRINCIPALS_LIMIT);
+        assert_eq!(names[0], "Keanu Reeves");
+        assert_eq!(names[1], "Extra 0");
+        assert_eq!(names.last(), Some(&"Extra 8"));
+    }
+
+    #[test]
+    fn known_for_titles_keep_source_order() {
+        let dir = std::env::temp_dir().join(format!("imdb-rs-known-for-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt1375666,tt0133093,tt0106519\n\
+             nm0000001\tFred Astaire\t1899\t1987\tactor\t\\N\n",
+        )
+        .unwrap();
+        let index_dir = dir.join("index");
+
+        build_name_index_sync(&index_dir, &names_path, NameIndexOptions::default()).unwrap();
+        let names = NameIndex::from_index(
+            &Index::open_in_dir(&index_dir).unwrap(),
+            &NameIndexOptions::default(),
+        )
+        .unwrap();
+        let searcher = names.reader.searcher();
+        let known_for = |nconst: &str| {
+            let query = TermQuery::new(
+                Term::from_field_text(names.fields.nconst, nconst),
+                Default::default(),
+            );
+            let (_, addr) = searcher.search(&query, &TopDocs::with_limit(1)).unwrap()[0];
+            let doc: TantivyDocument = searcher.doc(addr).unwrap();
+            doc.get_first(names.fields.known_for_titles)
+                .and_then(|value| value.as_str().map(str::to_string))
+        };
+
+        assert_eq!(
+            known_for("nm0000206").as_deref(),
+            Some("tt1375666,tt0133093,tt0106519")
+        );
+        assert_eq!(known_for("nm0000001"), None);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn docstore_compression_is_applied_at_build_time() {
+        assert_eq!(
+            "zstd:9".parse::<DocstoreCompression>().unwrap(),
+            DocstoreCompression::Zstd(Some(9))
+        );
+        assert_eq!(
+            "none".parse::<DocstoreCompression>().unwrap(),
+            DocstoreCompression::None
+        );
+        assert!("zstd:high".parse::<DocstoreCompression>().is_err());
+        assert!("brotli".parse::<DocstoreCompression>().is_err());
+
+        let dir = std::env::temp_dir().join(format!("imdb-rs-zstd-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let names_path = dir.join("name.basics.tsv");
+        std::fs::write(
+            &names_path,
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt0133093\n",
+        )
+        .unwrap();
+        let index_dir = dir.join("index");
+        let options = NameIndexOptions {
+            docstore_compression: DocstoreCompression::Zstd(Some(9)),
+            ..NameIndexOptions::default()
+        };
+
+        build_name_index_sync(&index_dir, &names_path, options).unwrap();
+        let index = Index::open_in_dir(&index_dir).unwrap();
+        assert_eq!(
+            index.settings().docstore_compression,
+            Compressor::Zstd(ZstdCompressor {
+                compression_level: Some(9)
+            })
+        );
+        assert_eq!(index.reader().unwrap().searcher().num_docs(), 1);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn principals_enrichment_can_be_disabled() {
+        let missing = std::env::temp_dir().join(format!("imdb-rs-missing-{}", std::process::id()));
+
+        let map = load_principals_enrichment(&missing, None).unwrap();
+        assert!(map.is_empty());
+        assert!(load_principals_enrichment(&missing, Some(&missing)).is_err());
+    }
+
+    #[test]
+    fn cast_and_crew_are_only_required_with_principals() {
+        let mut config = AppConfig::default();
+        let names = required_datasets(&config);
+        assert!(names.contains(&"title.principals.tsv.gz"));
+        assert!(names.contains(&"title.crew.tsv.gz"));
+        assert!(names.contains(&"title.episode.tsv.gz"));
+
+        config.index_principals = false;
+        config.index_episodes = false;
+        assert_eq!(
+            required_datasets(&config),
+            [
+                "name.basics.tsv.gz",
+                "title.akas.tsv.gz",
+                "title.basics.tsv.gz",
+                "title.ratings.tsv.gz",
+            ]
+        );
+    }
+
+    #[test]
+    fn implausible_life_years_are_dropped() {
+        let bounds = YearBounds {
+            min: Some(1000),
+            max: Some(2100),
+        };
+
+        assert_eq!(
+            sanitize_life_years(Some(7), Some(1950), bounds),
+            (None, Some(1950))
+        );
+        assert_eq!(sanitize_life_years(Some(9999), None, bounds), (None, None));
+        assert_eq!(
+            sanitize_life_years(Some(1960), Some(1940), bounds),
+            (Some(1960), None)
+        );
+        assert_eq!(
+            sanitize_life_years(Some(1964), None, YearBounds::default()),
+            (Some(1964), None)
+        );
+    }
+}
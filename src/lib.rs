@@ -1,4 +1,8 @@
+#[macro_use]
+mod macros;
+
 pub mod api;
 pub mod config;
 pub mod datasets;
 pub mod indexer;
+pub mod search;
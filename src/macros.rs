@@ -0,0 +1,78 @@
+/// Declares a fieldless enum whose variants each carry one string name, and derives `ALL`,
+/// `NAMES`, `as_str`, `from_name`, `FromStr`, `Serialize` and `Deserialize` from that single
+/// table so the spellings cannot drift apart.
+///
+/// `$what` names the enum in errors, e.g. `unknown sort mode `bogus``.
+macro_rules! named_enum {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident ($what:literal) {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident = $text:literal,
+            )+
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )+
+        }
+
+        impl $name {
+            pub const ALL: [$name; [$($text),+].len()] = [$($name::$variant),+];
+
+            /// Names in [`Self::ALL`] order, as accepted by `FromStr` and serde.
+            pub const NAMES: [&'static str; [$($text),+].len()] = [$($text),+];
+
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)+
+                }
+            }
+
+            /// The variant named `value`, if any.
+            pub fn from_name(value: &str) -> Option<Self> {
+                $name::ALL.into_iter().find(|item| item.as_str() == value)
+            }
+        }
+
+        impl std::str::FromStr for $name {
+            type Err = anyhow::Error;
+
+            fn from_str(value: &str) -> Result<Self, Self::Err> {
+                $name::from_name(value)
+                    .ok_or_else(|| anyhow::anyhow!(concat!("unknown ", $what, " `{}`"), value))
+            }
+        }
+
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                struct Visitor;
+
+                impl serde::de::Visitor<'_> for Visitor {
+                    type Value = $name;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                        formatter.write_str(concat!("a ", $what))
+                    }
+
+                    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<$name, E> {
+                        $name::from_name(value)
+                            .ok_or_else(|| E::unknown_variant(value, &$name::NAMES))
+                    }
+                }
+
+                deserializer.deserialize_str(Visitor)
+            }
+        }
+    };
+}
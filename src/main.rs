@@ -1,12 +1,101 @@
-use anyhow::Result;
+use std::collections::HashSet;
+use std::ffi::OsString;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand};
 use imdb_rs::config::AppConfig;
+use imdb_rs::datasets::DatasetFile;
 use imdb_rs::{api, datasets, indexer};
 use tracing::info;
 use tracing_subscriber::EnvFilter;
 
+/// IMDb search service. Flags override environment variables, which override the config file.
+#[derive(Debug, Parser)]
+#[command(version)]
+struct Cli {
+    /// TOML config file, instead of the one named by `IMDB_CONFIG`.
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Address the HTTP server listens on (`IMDB_BIND_ADDR`).
+    #[arg(long, global = true, value_name = "ADDR")]
+    bind: Option<SocketAddr>,
+    /// Directory holding the dataset files (`IMDB_DATA_DIR`).
+    #[arg(long, global = true, value_name = "DIR")]
+    data_dir: Option<PathBuf>,
+    /// Directory holding the indexes (`IMDB_INDEX_DIR`).
+    #[arg(long, global = true, value_name = "DIR")]
+    index_dir: Option<PathBuf>,
+    /// Rebuild the indexes from the datasets before serving; only valid with `serve`.
+    #[arg(long, global = true)]
+    reindex: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum Command {
+    /// Prepare the datasets and indexes, then serve the API (the default).
+    Serve,
+    /// Prepare the datasets and rebuild both indexes, then exit.
+    Reindex,
+    /// Download (and decompress) the datasets, then exit.
+    DownloadOnly,
+    /// Prepare the datasets and re-index the listed titles in place, then exit.
+    Update {
+        /// File listing the `tconst`s to re-index, one per line.
+        #[arg(long, value_name = "PATH")]
+        tconsts: PathBuf,
+    },
+}
+
+impl Cli {
+    /// Parses `args`, rejecting `--reindex` with subcommands that do not serve, which would
+    /// otherwise ignore it.
+    fn try_parse_args<I, T>(args: I) -> Result<Self, clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString> + Clone,
+    {
+        let cli = Self::try_parse_from(args)?;
+        if cli.reindex && !matches!(cli.command, None | Some(Command::Serve)) {
+            return Err(Self::command().error(
+                ErrorKind::ArgumentConflict,
+                "`--reindex` only applies to `serve`; the `reindex` subcommand rebuilds the \
+                 indexes and exits",
+            ));
+        }
+        Ok(cli)
+    }
+
+    fn load_config(&self) -> Result<AppConfig> {
+        let mut config = match &self.config {
+            Some(path) => AppConfig::from_env_with_file(path)?,
+            None => AppConfig::from_env()?,
+        };
+        if let Some(data_dir) = &self.data_dir {
+            // Keep a derived index directory next to the new data directory.
+            if config.index_dir == config.data_dir.join("tantivy_index") {
+                config.index_dir = data_dir.join("tantivy_index");
+            }
+            config.data_dir = data_dir.clone();
+        }
+        if let Some(index_dir) = &self.index_dir {
+            config.index_dir = index_dir.clone();
+        }
+        if let Some(bind) = self.bind {
+            config.bind_addr = bind;
+        }
+        Ok(config)
+    }
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     dotenvy::dotenv().ok();
+    let cli = Cli::try_parse_args(std::env::args_os()).unwrap_or_else(|err| err.exit());
 
     tracing_subscriber::fmt()
         .with_env_filter(
@@ -16,7 +105,7 @@ async fn main() -> Result<()> {
         .pretty()
         .init();
 
-    let config = AppConfig::from_env()?;
+    let config = cli.load_config()?;
     info!(
         data_dir = %config.data_dir.display(),
         index_dir = %config.index_dir.display(),
@@ -24,11 +113,57 @@ async fn main() -> Result<()> {
         "loaded configuration"
     );
 
-    let datasets = datasets::prepare_datasets(&config).await?;
+    let datasets = prepare_datasets(&config).await?;
+    match cli.command.clone().unwrap_or(Command::Serve) {
+        Command::DownloadOnly => Ok(()),
+        Command::Reindex => {
+            let rebuilt = indexer::rebuild_indexes(&config, &datasets).await?;
+            indexer::remove_retired_indexes(&rebuilt.retired).await?;
+            info!("indexes rebuilt");
+            Ok(())
+        }
+        Command::Update { tconsts } => {
+            let tconsts = read_tconsts(&tconsts)?;
+            let update = indexer::update_title_index(&config, &datasets, tconsts).await?;
+            info!(
+                updated = update.updated,
+                removed = update.removed,
+                "title index updated"
+            );
+            Ok(())
+        }
+        Command::Serve => serve(config, &datasets, cli.reindex).await,
+    }
+}
+
+/// Reads the non-empty lines of `path` as title ids.
+fn read_tconsts(path: &Path) -> Result<HashSet<String>> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+async fn prepare_datasets(config: &AppConfig) -> Result<Vec<DatasetFile>> {
+    let dataset_names = datasets::configured_datasets(config);
+    let datasets = datasets::prepare_datasets(config, &dataset_names).await?;
     info!(file_count = datasets.len(), "datasets ready");
+    Ok(datasets)
+}
 
-    let prepared_indexes = indexer::prepare_indexes(&config, &datasets).await?;
-    let app_state = api::AppState::new(prepared_indexes);
+async fn serve(config: AppConfig, datasets: &[DatasetFile], reindex: bool) -> Result<()> {
+    let prepared_indexes = if reindex {
+        let rebuilt = indexer::rebuild_indexes(&config, datasets).await?;
+        indexer::remove_retired_indexes(&rebuilt.retired).await?;
+        rebuilt.indexes
+    } else {
+        indexer::prepare_indexes(&config, datasets).await?
+    };
+    let app_state = api::AppState::new(prepared_indexes, config.clone());
     let app = api::router(app_state);
 
     let listener = tokio::net::TcpListener::bind(config.bind_addr).await?;
@@ -37,3 +172,28 @@ async fn main() -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reindex_flag_is_only_accepted_when_serving() {
+        for args in [
+            &["imdb-rs", "--reindex"][..],
+            &["imdb-rs", "serve", "--reindex"],
+            &["imdb-rs", "--reindex", "serve"],
+        ] {
+            let cli = Cli::try_parse_args(args).unwrap();
+            assert!(cli.reindex, "{args:?}");
+        }
+        for args in [
+            &["imdb-rs", "--reindex", "reindex"][..],
+            &["imdb-rs", "download-only", "--reindex"],
+            &["imdb-rs", "update", "--tconsts", "ids.txt", "--reindex"],
+        ] {
+            let err = Cli::try_parse_args(args).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::ArgumentConflict, "{args:?}");
+        }
+    }
+}
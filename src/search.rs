@@ -0,0 +1,64 @@
+named_enum! {
+    /// Order of title search results.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum SortMode("sort mode") {
+        #[default]
+        Relevance = "relevance",
+        RatingDesc = "rating_desc",
+        RatingAsc = "rating_asc",
+        VotesDesc = "votes_desc",
+        VotesAsc = "votes_asc",
+        YearDesc = "year_desc",
+        YearAsc = "year_asc",
+        RuntimeDesc = "runtime_desc",
+        RuntimeAsc = "runtime_asc",
+        TitleAsc = "title_asc",
+        TitleDesc = "title_desc",
+    }
+}
+
+/// Secondary ordering applied to title search results that tie on the requested sort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TieBreak {
+    /// Most votes first.
+    Votes,
+    /// Most recent start year first.
+    Year,
+    /// Primary title A–Z, ignoring case.
+    Title,
+    /// Ascending `tconst`.
+    #[default]
+    Tconst,
+}
+
+impl std::str::FromStr for TieBreak {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "votes" => Ok(TieBreak::Votes),
+            "year" => Ok(TieBreak::Year),
+            "title" => Ok(TieBreak::Title),
+            "tconst" => Ok(TieBreak::Tconst),
+            other => Err(anyhow::anyhow!("unknown tie-break `{other}`")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_modes_round_trip_through_their_names() {
+        for sort in SortMode::ALL {
+            let json = serde_json::to_string(&sort).unwrap();
+            assert_eq!(json, format!("\"{}\"", sort.as_str()));
+            assert_eq!(serde_json::from_str::<SortMode>(&json).unwrap(), sort);
+            assert_eq!(sort.as_str().parse::<SortMode>().unwrap(), sort);
+        }
+        let err = serde_json::from_str::<SortMode>("\"bogus\"").unwrap_err();
+        assert!(err.to_string().contains("rating_desc"), "{err}");
+        assert!("bogus".parse::<SortMode>().is_err());
+    }
+}
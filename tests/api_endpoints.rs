@@ -1,183 +1,316 @@
 use axum::body::{self, Body};
 use axum::http::{Request, StatusCode};
-use serde_json::from_slice;
-use tantivy::Index;
-use tantivy::query::QueryParser;
-use tantivy::schema::{
-    IndexRecordOption, NumericOptions, STORED, STRING, Schema, TEXT, TextFieldIndexing, TextOptions,
+use imdb_rs::api::types::TieBreak;
+use imdb_rs::indexer::{
+    AkaTitle, FuzzyDistance, NameFields, NameIndex, NameIndexOptions, NgramOptions, PopularityTier,
+    PreparedIndexes, Principal, TitleFields, TitleIndex, TitleIndexOptions, build_name_schema,
+    build_title_schema, phonetic_codes, register_title_tokenizers, title_sort_key,
 };
+use serde_json::from_slice;
+use tantivy::schema::TantivyDocument;
+use tantivy::{Index, Term};
 use tower::ServiceExt;
 
 type TestResult<T> = Result<T, Box<dyn std::error::Error>>;
 
-fn build_title_schema() -> (Schema, imdb_rs::indexer::TitleFields, Index) {
-    let schema = {
-        let mut builder = Schema::builder();
-        builder.add_text_field("tconst", STRING | STORED);
-        builder.add_text_field("titleType", STRING | STORED);
-        builder.add_text_field("primaryTitle", TEXT | STORED);
-        builder.add_text_field("originalTitle", TEXT | STORED);
-        builder.add_text_field("genres", TEXT | STORED);
-        builder.add_text_field("searchTitles", TEXT);
-        let exact_indexing = TextFieldIndexing::default()
-            .set_tokenizer("raw")
-            .set_index_option(IndexRecordOption::Basic);
-        builder.add_text_field(
-            "primary_title_exact",
-            TextOptions::default()
-                .set_indexing_options(exact_indexing)
-                .set_stored(),
-        );
-        let numeric = NumericOptions::default()
-            .set_indexed()
-            .set_stored()
-            .set_fast();
-        builder.add_i64_field("startYear", numeric.clone());
-        builder.add_i64_field("endYear", numeric.clone());
-        builder.add_f64_field("averageRating", numeric.clone());
-        builder.add_i64_field("numVotes", numeric);
-        builder.build()
-    };
-
-    let index = Index::create_in_ram(schema.clone());
-    let schema_from_index = index.schema();
-    let fields = imdb_rs::indexer::TitleFields {
-        tconst: schema_from_index.get_field("tconst").unwrap(),
-        primary_title: schema_from_index.get_field("primaryTitle").unwrap(),
-        primary_title_exact: schema_from_index.get_field("primary_title_exact").ok(),
-        original_title: schema_from_index.get_field("originalTitle").unwrap(),
-        title_type: schema_from_index.get_field("titleType").unwrap(),
-        start_year: schema_from_index.get_field("startYear").unwrap(),
-        end_year: schema_from_index.get_field("endYear").unwrap(),
-        genres: schema_from_index.get_field("genres").unwrap(),
-        average_rating: schema_from_index.get_field("averageRating").unwrap(),
-        num_votes: schema_from_index.get_field("numVotes").unwrap(),
-        search_titles: schema_from_index.get_field("searchTitles").unwrap(),
-    };
-
-    (schema, fields, index)
-}
-
-fn build_name_schema() -> (Schema, imdb_rs::indexer::NameFields, Index) {
-    let schema = {
-        let mut builder = Schema::builder();
-        builder.add_text_field("nconst", STRING | STORED);
-        builder.add_text_field("primaryName", TEXT | STORED);
-        builder.add_text_field("primaryNameSearch", TEXT);
-        builder.add_text_field("primaryProfession", TEXT | STORED);
-        builder.add_text_field("knownForTitles", TEXT | STORED);
-        let numeric = NumericOptions::default()
-            .set_indexed()
-            .set_stored()
-            .set_fast();
-        builder.add_i64_field("birthYear", numeric.clone());
-        builder.add_i64_field("deathYear", numeric);
-        builder.build()
-    };
-
-    let index = Index::create_in_ram(schema.clone());
-    let schema_from_index = index.schema();
-    let fields = imdb_rs::indexer::NameFields {
-        nconst: schema_from_index.get_field("nconst").unwrap(),
-        primary_name: schema_from_index.get_field("primaryName").unwrap(),
-        primary_name_search: schema_from_index.get_field("primaryNameSearch").unwrap(),
-        birth_year: schema_from_index.get_field("birthYear").unwrap(),
-        death_year: schema_from_index.get_field("deathYear").unwrap(),
-        primary_profession: schema_from_index.get_field("primaryProfession").unwrap(),
-        known_for_titles: schema_from_index.get_field("knownForTitles").unwrap(),
-    };
-
-    (schema, fields, index)
-}
-
-fn build_test_indexes() -> imdb_rs::indexer::PreparedIndexes {
-    let (_schema, fields, index) = build_title_schema();
-    let mut writer = index
-        .writer::<tantivy::schema::TantivyDocument>(20_000_000)
-        .unwrap();
-    let mut doc = tantivy::schema::TantivyDocument::default();
+fn build_test_title_index(options: &TitleIndexOptions) -> TitleIndex {
+    let index = Index::create_in_ram(build_title_schema(options));
+    register_title_tokenizers(&index, options).unwrap();
+    let fields = TitleFields::new(&index.schema()).unwrap();
+    let mut writer = index.writer::<TantivyDocument>(20_000_000).unwrap();
+    let mut doc = TantivyDocument::default();
     doc.add_text(fields.tconst, "tt0133093");
     doc.add_text(fields.title_type, "movie");
     doc.add_text(fields.primary_title, "The Matrix");
+    doc.add_text(fields.primary_title_sort, "the matrix");
+    doc.add_text(fields.primary_title_sort_stripped, "matrix");
     doc.add_text(fields.original_title, "The Matrix");
     doc.add_text(fields.search_titles, "The Matrix");
     if let Some(exact) = fields.primary_title_exact {
         doc.add_text(exact, "the matrix");
     }
+    if let Some(ngram) = fields.primary_title_ngram {
+        doc.add_text(ngram, "The Matrix");
+    }
+    doc.add_text(
+        fields.localized_titles,
+        AkaTitle {
+            title: "Matrix".into(),
+            region: Some("DE".into()),
+            language: None,
+        }
+        .to_stored(),
+    );
+    doc.add_text(fields.aka_regions, "de");
     doc.add_text(fields.genres, "Action");
     doc.add_text(fields.genres, "Sci-Fi");
     doc.add_i64(fields.start_year, 1999);
     doc.add_i64(fields.end_year, 1999);
     doc.add_f64(fields.average_rating, 8.7);
     doc.add_i64(fields.num_votes, 1_900_000);
+    doc.add_i64(fields.is_adult, 0);
+    // Stored out of billing order to check the endpoint sorts by `ordering`.
+    for principal in [
+        Principal {
+            ordering: 5,
+            nconst: "nm0905154".into(),
+            primary_name: "Lana Wachowski".into(),
+            category: "director".into(),
+            job: None,
+            characters: Vec::new(),
+        },
+        Principal {
+            ordering: 1,
+            nconst: "nm0000206".into(),
+            primary_name: "Keanu Reeves".into(),
+            category: "actor".into(),
+            job: None,
+            characters: vec!["Neo".into()],
+        },
+    ] {
+        doc.add_text(fields.principals, principal.to_stored().unwrap());
+        doc.add_text(fields.people_ids, &principal.nconst);
+    }
     writer.add_document(doc).unwrap();
     writer.commit().unwrap();
-    let reader = index.reader().unwrap();
-    reader.reload().unwrap();
-    let mut query_parser = QueryParser::for_index(
-        &index,
-        vec![
-            fields.primary_title,
-            fields.original_title,
-            fields.search_titles,
-            fields.genres,
-        ],
+
+    TitleIndex::from_index(&index, options).unwrap()
+}
+
+struct TestTitle {
+    tconst: &'static str,
+    title_type: &'static str,
+    title: &'static str,
+    /// `0` leaves the start year unset.
+    year: i64,
+    genres: &'static [&'static str],
+    rating: f64,
+    votes: i64,
+    runtime: Option<i64>,
+    adult: bool,
+}
+
+impl TestTitle {
+    /// A rated 2001 drama movie; tests override the fields they care about.
+    fn new(tconst: &'static str, title: &'static str) -> Self {
+        Self {
+            tconst,
+            title,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for TestTitle {
+    fn default() -> Self {
+        Self {
+            tconst: "tt0000001",
+            title_type: "movie",
+            title: "Harbor Lights",
+            year: 2001,
+            genres: &["Drama"],
+            rating: 7.0,
+            votes: 1_000,
+            runtime: None,
+            adult: false,
+        }
+    }
+}
+
+const CATALOG: &[TestTitle] = &[
+    TestTitle {
+        tconst: "tt0133093",
+        title_type: "movie",
+        title: "The Matrix",
+        year: 1999,
+        genres: &["Action", "Sci-Fi"],
+        rating: 8.7,
+        votes: 1_900_000,
+        runtime: Some(136),
+        adult: false,
+    },
+    TestTitle {
+        tconst: "tt0234215",
+        title_type: "movie",
+        title: "The Matrix Reloaded",
+        year: 2003,
+        genres: &["Action", "Sci-Fi"],
+        rating: 7.2,
+        votes: 650_000,
+        runtime: Some(138),
+        adult: false,
+    },
+    TestTitle {
+        tconst: "tt0242653",
+        title_type: "movie",
+        title: "The Matrix Revolutions",
+        year: 2003,
+        genres: &["Action", "Sci-Fi"],
+        rating: 6.7,
+        votes: 540_000,
+        runtime: Some(129),
+        adult: false,
+    },
+    TestTitle {
+        tconst: "tt10838180",
+        title_type: "movie",
+        title: "The Matrix Resurrections",
+        year: 2021,
+        genres: &["Action", "Sci-Fi"],
+        rating: 5.7,
+        votes: 330_000,
+        runtime: Some(148),
+        adult: false,
+    },
+];
+
+/// The title index document of `title`.
+fn catalog_document(fields: &TitleFields, title: &TestTitle) -> TantivyDocument {
+    let mut doc = TantivyDocument::default();
+    doc.add_text(fields.tconst, title.tconst);
+    doc.add_text(fields.title_type, title.title_type);
+    doc.add_text(fields.primary_title, title.title);
+    doc.add_text(
+        fields.primary_title_sort,
+        title_sort_key(title.title, false),
+    );
+    doc.add_text(
+        fields.primary_title_sort_stripped,
+        title_sort_key(title.title, true),
+    );
+    doc.add_text(fields.original_title, title.title);
+    doc.add_text(fields.search_titles, title.title);
+    if let Some(exact) = fields.primary_title_exact {
+        doc.add_text(exact, title.title.to_lowercase());
+    }
+    for genre in title.genres {
+        doc.add_text(fields.genres, genre);
+    }
+    if title.year != 0 {
+        doc.add_i64(fields.start_year, title.year);
+        doc.add_i64(fields.end_year, title.year);
+    }
+    doc.add_f64(fields.average_rating, title.rating);
+    doc.add_i64(fields.num_votes, title.votes);
+    doc.add_text(
+        fields.popularity_tier,
+        PopularityTier::for_votes(title.votes).as_str(),
     );
-    query_parser.set_field_boost(fields.primary_title, 2.0);
-    query_parser.set_field_boost(fields.original_title, 1.2);
-    query_parser.set_field_boost(fields.search_titles, 1.0);
-    query_parser.set_field_boost(fields.genres, 0.3);
-    query_parser.set_field_fuzzy(fields.primary_title, false, 1, true);
-    query_parser.set_field_fuzzy(fields.original_title, false, 1, true);
-    query_parser.set_field_fuzzy(fields.search_titles, false, 1, true);
-
-    let title_index = imdb_rs::indexer::TitleIndex {
-        schema: index.schema(),
-        fields,
-        reader,
-        query_parser,
-    };
-
-    let (_schema, fields, index) = build_name_schema();
-    let mut writer = index
-        .writer::<tantivy::schema::TantivyDocument>(20_000_000)
-        .unwrap();
-    let mut doc = tantivy::schema::TantivyDocument::default();
+    doc.add_i64(fields.is_adult, i64::from(title.adult));
+    if let Some(runtime) = title.runtime {
+        doc.add_i64(fields.runtime_minutes, runtime);
+    }
+    doc
+}
+
+/// Builds a title index holding every entry of `titles`, for tests needing more than one hit.
+fn build_catalog_title_index(titles: &[TestTitle]) -> TitleIndex {
+    let options = TitleIndexOptions::default();
+    let index = Index::create_in_ram(build_title_schema(&options));
+    register_title_tokenizers(&index, &options).unwrap();
+    let fields = TitleFields::new(&index.schema()).unwrap();
+    let mut writer = index.writer::<TantivyDocument>(20_000_000).unwrap();
+    for title in titles {
+        writer
+            .add_document(catalog_document(&fields, title))
+            .unwrap();
+    }
+    writer.commit().unwrap();
+
+    TitleIndex::from_index(&index, &options).unwrap()
+}
+
+/// Indexes holding `titles` and the single-person test name index.
+fn catalog_indexes(titles: &[TestTitle]) -> PreparedIndexes {
+    PreparedIndexes {
+        titles: build_catalog_title_index(titles),
+        names: build_test_name_index(),
+    }
+}
+
+/// Router serving `titles` (see [`catalog_indexes`]) with `config`.
+fn app_with_titles(titles: &[TestTitle], config: imdb_rs::config::AppConfig) -> axum::Router {
+    imdb_rs::api::router(imdb_rs::api::AppState::new(catalog_indexes(titles), config))
+}
+
+fn build_test_name_index() -> NameIndex {
+    let index = Index::create_in_ram(build_name_schema());
+    let fields = NameFields::new(&index.schema()).unwrap();
+    let mut writer = index.writer::<TantivyDocument>(20_000_000).unwrap();
+    let mut doc = TantivyDocument::default();
     doc.add_text(fields.nconst, "nm0000206");
     doc.add_text(fields.primary_name, "Keanu Reeves");
     doc.add_text(fields.primary_name_search, "Keanu Reeves");
+    for code in phonetic_codes("Keanu Reeves") {
+        doc.add_text(fields.primary_name_phonetic, code);
+    }
     doc.add_text(fields.primary_profession, "actor");
-    doc.add_text(fields.primary_name_search, "actor");
-    doc.add_text(fields.known_for_titles, "tt0133093");
+    // Deliberately not sorted: IMDb's order is most significant first.
+    doc.add_text(
+        fields.known_for_titles,
+        "tt0133093,tt0111257,tt0234215,tt0111257",
+    );
     doc.add_i64(fields.birth_year, 1964);
     writer.add_document(doc).unwrap();
     writer.commit().unwrap();
-    let reader = index.reader().unwrap();
-    reader.reload().unwrap();
-    let mut query_parser = QueryParser::for_index(
-        &index,
-        vec![fields.primary_name_search, fields.primary_profession],
-    );
-    query_parser.set_field_boost(fields.primary_name_search, 1.5);
-    query_parser.set_field_fuzzy(fields.primary_name_search, false, 1, true);
-    query_parser.set_field_fuzzy(fields.primary_profession, false, 1, true);
 
-    let name_index = imdb_rs::indexer::NameIndex {
-        fields,
-        reader,
-        query_parser,
-    };
+    NameIndex::from_index(&index, &NameIndexOptions::default()).unwrap()
+}
+
+/// People as `(nconst, primary name, birth year, death year)`, all of them actors unless
+/// `professions` lists others by nconst.
+fn build_people_name_index(
+    people: &[(&str, &str, Option<i64>, Option<i64>)],
+    professions: &[(&str, &[&str])],
+) -> NameIndex {
+    let index = Index::create_in_ram(build_name_schema());
+    let fields = NameFields::new(&index.schema()).unwrap();
+    let mut writer = index.writer::<TantivyDocument>(20_000_000).unwrap();
+    for &(nconst, name, birth_year, death_year) in people {
+        let mut doc = TantivyDocument::default();
+        doc.add_text(fields.nconst, nconst);
+        doc.add_text(fields.primary_name, name);
+        doc.add_text(fields.primary_name_search, name);
+        let listed = professions
+            .iter()
+            .find(|(listed, _)| *listed == nconst)
+            .map_or(&["actor"][..], |(_, professions)| professions);
+        for profession in listed {
+            doc.add_text(fields.primary_profession, profession);
+        }
+        if let Some(year) = birth_year {
+            doc.add_i64(fields.birth_year, year);
+        }
+        if let Some(year) = death_year {
+            doc.add_i64(fields.death_year, year);
+        }
+        writer.add_document(doc).unwrap();
+    }
+    writer.commit().unwrap();
+
+    NameIndex::from_index(&index, &NameIndexOptions::default()).unwrap()
+}
+
+/// Actors as `(nconst, primary name, birth year, death year)`.
+fn build_actor_name_index(actors: &[(&str, &str, Option<i64>, Option<i64>)]) -> NameIndex {
+    build_people_name_index(actors, &[])
+}
 
-    imdb_rs::indexer::PreparedIndexes {
-        titles: title_index,
-        names: name_index,
+fn build_test_indexes_with(options: &TitleIndexOptions) -> PreparedIndexes {
+    PreparedIndexes {
+        titles: build_test_title_index(options),
+        names: build_test_name_index(),
     }
 }
 
+fn build_test_indexes() -> PreparedIndexes {
+    build_test_indexes_with(&TitleIndexOptions::default())
+}
+
 #[tokio::test]
 async fn title_search_returns_expected_result() -> TestResult<()> {
     let indexes = build_test_indexes();
-    let state = imdb_rs::api::AppState::new(indexes);
+    let state = imdb_rs::api::AppState::new(indexes, imdb_rs::config::AppConfig::default());
     let app = imdb_rs::api::router(state);
 
     let response = app
@@ -190,6 +323,13 @@ async fn title_search_returns_expected_result() -> TestResult<()> {
         .await?;
 
     assert_eq!(response.status(), StatusCode::OK);
+    let generation = response
+        .headers()
+        .get("x-index-generation")
+        .expect("search responses carry the index generation")
+        .to_str()?
+        .to_string();
+    assert_eq!(generation.len(), 16);
     let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
     let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
     assert_eq!(parsed.results.len(), 1);
@@ -200,7 +340,7 @@ async fn title_search_returns_expected_result() -> TestResult<()> {
 #[tokio::test]
 async fn title_id_endpoint_returns_document() -> TestResult<()> {
     let indexes = build_test_indexes();
-    let state = imdb_rs::api::AppState::new(indexes);
+    let state = imdb_rs::api::AppState::new(indexes, imdb_rs::config::AppConfig::default());
     let app = imdb_rs::api::router(state);
 
     let response = app
@@ -216,13 +356,178 @@ async fn title_id_endpoint_returns_document() -> TestResult<()> {
     let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
     let parsed: imdb_rs::api::types::TitleSearchResult = from_slice(&bytes)?;
     assert_eq!(parsed.primary_title, "The Matrix");
+    assert_eq!(parsed.localized_title, None);
+    assert_eq!(parsed.imdb_url, "https://www.imdb.com/title/tt0133093/");
+    assert!(!parsed.is_series);
+    assert!(parsed.popularity.is_some_and(|popularity| popularity > 0.0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn id_lookups_normalize_bare_and_unpadded_ids() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    for uri in [
+        "/titles/tt0133093",
+        "/titles/133093",
+        "/titles/0133093",
+        "/titles/TT133093",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: imdb_rs::api::types::TitleSearchResult = from_slice(&bytes)?;
+        assert_eq!(parsed.tconst, "tt0133093", "{uri}");
+    }
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/names/206").body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    for uri in ["/titles/133094", "/titles/nm0133093", "/names/tt0000206"] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND, "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn id_lookups_honour_if_none_match() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let get = |uri: &str, if_none_match: Option<&str>| {
+        let mut request = Request::builder().uri(uri);
+        if let Some(etag) = if_none_match {
+            request = request.header("if-none-match", etag);
+        }
+        request.body(Body::empty())
+    };
+
+    for uri in ["/titles/tt0133093", "/names/nm0000206"] {
+        let response = app.clone().oneshot(get(uri, None)?).await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response.headers()["etag"].to_str()?.to_string();
+
+        let response = app.clone().oneshot(get(uri, Some(&etag))?).await?;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED, "{uri}");
+        assert_eq!(response.headers()["etag"], etag.as_str());
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        assert!(bytes.is_empty());
+
+        let stale = app
+            .clone()
+            .oneshot(get(uri, Some("W/\"tt0000000-0-0\""))?)
+            .await?;
+        assert_eq!(stale.status(), StatusCode::OK, "{uri}");
+    }
+
+    // Localized responses are cached separately.
+    let plain = app.clone().oneshot(get("/titles/tt0133093", None)?).await?;
+    let localized = app
+        .clone()
+        .oneshot(get("/titles/tt0133093?lang=de", None)?)
+        .await?;
+    assert_ne!(plain.headers()["etag"], localized.headers()["etag"]);
+
+    let missing = app.oneshot(get("/titles/tt9999999", Some("*"))?).await?;
+    assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn localized_title_is_returned_for_lang() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=Matrix&lang=de")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.results[0].primary_title, "The Matrix");
+    assert_eq!(parsed.results[0].localized_title.as_deref(), Some("Matrix"));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0133093?lang=fr")
+                .body(Body::empty())?,
+        )
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResult = from_slice(&bytes)?;
+    assert_eq!(parsed.localized_title, None);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0133093?language=de")
+                .body(Body::empty())?,
+        )
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResult = from_slice(&bytes)?;
+    assert_eq!(parsed.primary_title, "The Matrix");
+    assert_eq!(parsed.localized_title.as_deref(), Some("Matrix"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn region_and_aka_language_filters_restrict_to_aka_releases() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let german = fetch_titles(&app, "/titles/search?query=Matrix&region=DE").await?;
+    assert_eq!(german.total, 1);
+    assert_eq!(german.results[0].localized_title.as_deref(), Some("Matrix"));
+
+    for uri in [
+        "/titles/search?query=Matrix&region=FR",
+        "/titles/search?query=Matrix&aka_language=de",
+    ] {
+        assert_eq!(fetch_titles(&app, uri).await?.total, 0, "{uri}");
+    }
+
+    // `language` only localizes, like `lang`.
+    let localized = fetch_titles(&app, "/titles/search?query=Matrix&language=de").await?;
+    let unfiltered = fetch_titles(&app, "/titles/search?query=Matrix").await?;
+    assert_eq!(localized.total, unfiltered.total);
+    assert_eq!(
+        localized.results[0].localized_title.as_deref(),
+        Some("Matrix")
+    );
     Ok(())
 }
 
 #[tokio::test]
 async fn name_search_supports_typos_and_filters() -> TestResult<()> {
     let indexes = build_test_indexes();
-    let state = imdb_rs::api::AppState::new(indexes);
+    let state = imdb_rs::api::AppState::new(indexes, imdb_rs::config::AppConfig::default());
     let app = imdb_rs::api::router(state);
 
     let response = app
@@ -248,3 +553,2937 @@ async fn name_search_supports_typos_and_filters() -> TestResult<()> {
     assert_eq!(parsed.results[0].nconst, "nm0000206");
     Ok(())
 }
+
+#[tokio::test]
+async fn phonetic_name_search_complements_fuzzy_matching() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    // Two edits per word: beyond fuzzy matching, but the same Soundex codes.
+    assert!(
+        fetch_nconsts(&app, "/names/search?query=Kiyanu%20Reavs")
+            .await?
+            .is_empty()
+    );
+    let phonetic = fetch_nconsts(&app, "/names/search?query=Kiyanu%20Reavs&phonetic=true").await?;
+    assert_eq!(phonetic.len(), 1);
+    assert_eq!(phonetic[0], "nm0000206");
+
+    // Every word has to sound alike, and exact matches keep working.
+    assert!(
+        fetch_nconsts(&app, "/names/search?query=Kiyanu%20Smith&phonetic=true")
+            .await?
+            .is_empty()
+    );
+    assert_eq!(
+        fetch_nconsts(&app, "/names/search?query=Keanu&phonetic=true")
+            .await?
+            .len(),
+        1
+    );
+
+    // Operators and field names are query syntax, not words of the name.
+    for uri in [
+        "/names/search?query=Kiyanu%20OR%20Reavs&phonetic=true",
+        "/names/search?query=primaryNameSearch:Kiyanu%20Reavs&phonetic=true",
+        "/names/search?query=Kiyanu%20Reavs%20NOT%20Smith&phonetic=true",
+    ] {
+        assert_eq!(fetch_nconsts(&app, uri).await?.len(), 1, "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn names_sort_by_birth_year_with_unknown_years_last() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        PreparedIndexes {
+            titles: build_catalog_title_index(CATALOG),
+            names: build_actor_name_index(&[
+                ("nm0000001", "Older Actor", Some(1950), None),
+                ("nm0000002", "Undated Actor", None, None),
+                ("nm0000003", "Younger Actor", Some(1990), None),
+                ("nm0000004", "Middle Actor", Some(1970), None),
+            ]),
+        },
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let youngest = fetch_names(&app, "/names/search?query=actor&sort=birth_year_desc").await?;
+    assert_eq!(youngest.total, 4);
+    let nconsts: Vec<&str> = youngest.results.iter().map(|r| r.nconst.as_str()).collect();
+    assert_eq!(
+        nconsts,
+        ["nm0000003", "nm0000004", "nm0000001", "nm0000002"]
+    );
+    assert!(youngest.results.iter().all(|r| r.score.is_none()));
+
+    let oldest = fetch_names(
+        &app,
+        "/names/search?query=actor&sort=birth_year_asc&limit=2",
+    )
+    .await?;
+    let nconsts: Vec<&str> = oldest.results.iter().map(|r| r.nconst.as_str()).collect();
+    assert_eq!(nconsts, ["nm0000001", "nm0000004"]);
+
+    let relevance = fetch_names(&app, "/names/search?query=younger").await?;
+    assert_eq!(relevance.results[0].nconst, "nm0000003");
+    assert!(relevance.results[0].score.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn name_search_excludes_with_and_not() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        PreparedIndexes {
+            titles: build_catalog_title_index(CATALOG),
+            names: build_actor_name_index(&[
+                ("nm0000001", "Keanu Reeves", Some(1964), None),
+                ("nm0000002", "Keanu Smith", Some(1980), None),
+            ]),
+        },
+        imdb_rs::config::AppConfig::default(),
+    ));
+    assert_eq!(
+        fetch_nconsts(&app, "/names/search?query=keanu%20NOT%20smith&fuzzy=false").await?,
+        ["nm0000001"]
+    );
+    assert_eq!(
+        fetch_nconsts(
+            &app,
+            "/names/search?query=keanu%20AND%20NOT%20smith&fuzzy=false"
+        )
+        .await?,
+        ["nm0000001"],
+        "`AND NOT` excludes like `NOT`"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn names_filter_on_whether_a_death_year_is_known() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        PreparedIndexes {
+            titles: build_catalog_title_index(CATALOG),
+            names: build_actor_name_index(&[
+                ("nm0000001", "Living Actor", Some(1960), None),
+                ("nm0000002", "Late Actor", Some(1920), Some(1990)),
+                ("nm0000003", "Undated Actor", None, None),
+            ]),
+        },
+        imdb_rs::config::AppConfig::default(),
+    ));
+    assert_eq!(
+        fetch_nconsts(&app, "/names/search?query=actor&alive=true").await?,
+        ["nm0000001", "nm0000003"]
+    );
+    assert_eq!(
+        fetch_nconsts(&app, "/names/search?alive=true").await?,
+        ["nm0000001", "nm0000003"]
+    );
+    assert_eq!(
+        fetch_nconsts(&app, "/names/search?query=actor&alive=false").await?,
+        ["nm0000002"]
+    );
+    assert_eq!(
+        fetch_nconsts(&app, "/names/search?alive=false").await?,
+        ["nm0000002"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn profession_mode_any_matches_either_profession() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        PreparedIndexes {
+            titles: build_catalog_title_index(CATALOG),
+            names: build_people_name_index(
+                &[
+                    ("nm0000001", "Screen Actor", Some(1960), None),
+                    ("nm0000002", "Film Director", Some(1950), None),
+                    ("nm0000003", "Actor Director", Some(1940), None),
+                    ("nm0000004", "Film Composer", Some(1930), None),
+                ],
+                &[
+                    ("nm0000002", &["director"]),
+                    ("nm0000003", &["actor", "director"]),
+                    ("nm0000004", &["composer"]),
+                ],
+            ),
+        },
+        imdb_rs::config::AppConfig::default(),
+    ));
+    assert_eq!(
+        fetch_nconsts(
+            &app,
+            "/names/search?primary_profession=actor&primary_profession=director"
+        )
+        .await?,
+        ["nm0000003"]
+    );
+    assert_eq!(
+        fetch_nconsts(
+            &app,
+            "/names/search?primary_profession=actor&primary_profession=director&profession_mode=any"
+        )
+        .await?,
+        ["nm0000001", "nm0000002", "nm0000003"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn combined_search_returns_titles_and_names() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let search = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+            TestResult::Ok(from_slice::<imdb_rs::api::types::CombinedSearchResponse>(
+                &bytes,
+            )?)
+        }
+    };
+
+    let matrix = search("/search?query=matrix").await?;
+    assert_eq!(matrix.titles.len(), 1);
+    assert_eq!(matrix.titles[0].tconst, "tt0133093");
+    assert!(matrix.names.is_empty());
+
+    let keanu = search("/search?query=keanu&limit=1").await?;
+    assert!(keanu.titles.is_empty());
+    assert_eq!(keanu.names.len(), 1);
+    assert_eq!(keanu.names[0].nconst, "nm0000206");
+
+    // Too short for title search, which /titles/search rejects; names are still searched.
+    let short = search("/search?query=k").await?;
+    assert!(short.titles.is_empty());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/search?query=%20")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn admin_segments_requires_token() -> TestResult<()> {
+    let indexes = build_test_indexes();
+    let config = imdb_rs::config::AppConfig {
+        admin_token: Some("secret".into()),
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let state = imdb_rs::api::AppState::new(indexes, config);
+    let app = imdb_rs::api::router(state);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/admin/segments")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    for wrong in ["secre", "secrets", "secreT", ""] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/admin/segments")
+                    .header("authorization", format!("Bearer {wrong}"))
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{wrong:?}");
+    }
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/admin/segments")
+                .header("authorization", "Bearer secret")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::SegmentsResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.titles.num_docs, 1);
+    assert_eq!(parsed.names.num_docs, 1);
+    assert_eq!(parsed.titles.segment_count, parsed.titles.segments.len());
+    Ok(())
+}
+
+#[tokio::test]
+async fn disallowed_sort_mode_is_rejected() -> TestResult<()> {
+    let indexes = build_test_indexes();
+    let config = imdb_rs::config::AppConfig {
+        allowed_sorts: Some(vec![imdb_rs::api::types::SortMode::Relevance]),
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let state = imdb_rs::api::AppState::new(indexes, config);
+    let app = imdb_rs::api::router(state);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=Matrix&sort=votes_desc")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=Matrix&sort=relevance")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    Ok(())
+}
+
+#[tokio::test]
+async fn filters_endpoint_reports_corpus_ranges() -> TestResult<()> {
+    let indexes = build_test_indexes();
+    let state = imdb_rs::api::AppState::new(indexes, imdb_rs::config::AppConfig::default());
+    let app = imdb_rs::api::router(state);
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/filters").body(Body::empty())?)
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::FiltersResponse = from_slice(&bytes)?;
+    let start_year = parsed
+        .titles
+        .iter()
+        .find(|filter| filter.name == "start_year_min")
+        .expect("start_year_min is described");
+    assert_eq!(start_year.min, Some(1999.0));
+    assert_eq!(start_year.max, Some(1999.0));
+    let birth_year = parsed
+        .names
+        .iter()
+        .find(|filter| filter.name == "birth_year_min")
+        .expect("birth_year_min is described");
+    assert_eq!(birth_year.min, Some(1964.0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn infix_search_requires_ngram_field() -> TestResult<()> {
+    let plain = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let response = plain
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=atri")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
+    assert!(parsed.results.is_empty());
+
+    let options = TitleIndexOptions {
+        infix_ngram: Some(NgramOptions {
+            min_gram: 3,
+            max_gram: 3,
+        }),
+        ..TitleIndexOptions::default()
+    };
+    let ngram = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes_with(&options),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let response = ngram
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=atri&infix=true")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.results.len(), 1);
+    assert_eq!(parsed.results[0].tconst, "tt0133093");
+    Ok(())
+}
+
+#[tokio::test]
+async fn browse_min_rating_only_applies_without_query() -> TestResult<()> {
+    let config = imdb_rs::config::AppConfig {
+        browse_min_rating: Some(9.0),
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(build_test_indexes(), config));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
+    assert!(parsed.results.is_empty());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=Matrix")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.results.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn random_title_echoes_seed() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+    let pick = |seed: u64| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .uri(format!("/titles/random?seed={seed}"))
+                        .body(Body::empty())?,
+                )
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert_eq!(
+                response.headers().get("x-random-seed").unwrap(),
+                &seed.to_string()
+            );
+            let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+            let parsed: imdb_rs::api::types::TitleSearchResult = from_slice(&bytes)?;
+            TestResult::Ok(parsed.tconst)
+        }
+    };
+
+    let first = pick(42).await?;
+    assert_eq!(pick(42).await?, first, "a seed always picks the same title");
+    assert_ne!(
+        pick(1).await?,
+        pick(2).await?,
+        "different seeds pick different titles"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn random_titles_span_segments_and_skip_deleted_documents() -> TestResult<()> {
+    const SEGMENTS: [[&str; 4]; 3] = [
+        ["tt0000201", "tt0000202", "tt0000203", "tt0000204"],
+        ["tt0000205", "tt0000206", "tt0000207", "tt0000208"],
+        ["tt0000209", "tt0000210", "tt0000211", "tt0000212"],
+    ];
+    const DELETED: [&str; 3] = ["tt0000201", "tt0000203", "tt0000210"];
+
+    let options = TitleIndexOptions::default();
+    let index = Index::create_in_ram(build_title_schema(&options));
+    register_title_tokenizers(&index, &options)?;
+    let fields = TitleFields::new(&index.schema())?;
+    // One indexing thread and no merges keep each commit in a segment of its own.
+    let mut writer = index.writer_with_num_threads::<TantivyDocument>(1, 15_000_000)?;
+    writer.set_merge_policy(Box::new(tantivy::indexer::NoMergePolicy));
+    for segment in SEGMENTS {
+        for tconst in segment {
+            writer.add_document(catalog_document(&fields, &TestTitle::new(tconst, "Harbor")))?;
+        }
+        writer.commit()?;
+    }
+    for tconst in DELETED {
+        writer.delete_term(Term::from_field_text(fields.tconst, tconst));
+    }
+    writer.commit()?;
+    let titles = TitleIndex::from_index(&index, &options)?;
+    let searcher = titles.reader.searcher();
+    assert_eq!(searcher.segment_readers().len(), SEGMENTS.len());
+    assert!(
+        searcher
+            .segment_readers()
+            .iter()
+            .any(|segment| segment.has_deletes())
+    );
+
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        PreparedIndexes {
+            titles,
+            names: build_test_name_index(),
+        },
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let mut picked = std::collections::BTreeSet::new();
+    for seed in 0..200 {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/titles/random?seed={seed}"))
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK, "seed {seed}");
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: imdb_rs::api::types::TitleSearchResult = from_slice(&bytes)?;
+        picked.insert(parsed.tconst);
+    }
+    let live: std::collections::BTreeSet<String> = SEGMENTS
+        .iter()
+        .flatten()
+        .filter(|tconst| !DELETED.contains(tconst))
+        .map(|tconst| tconst.to_string())
+        .collect();
+    assert_eq!(
+        picked, live,
+        "every live title and no deleted one is picked"
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn invalid_search_params_return_json_400() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    for uri in [
+        "/titles/search?query=Matrix&sort=bogus",
+        "/titles/search?query=Matrix&limit=ten",
+        "/titles/search?min_rating=9&max_rating=2",
+        "/names/search?query=Keanu&birth_year_min=abc",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: serde_json::Value = from_slice(&bytes)?;
+        assert!(parsed["message"].is_string(), "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn genre_filters_support_any_mode() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    for (uri, expected) in [
+        ("/titles/search?genres=Sci-Fi&genres=Comedy", 0),
+        (
+            "/titles/search?genres=Sci-Fi&genres=Comedy&genre_mode=any",
+            1,
+        ),
+        ("/titles/search?genres=action", 1),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK, "{uri}");
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: imdb_rs::api::types::TitleSearchResponse = from_slice(&bytes)?;
+        assert_eq!(parsed.results.len(), expected, "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn excluded_genres_drop_titles_in_either_genre_mode() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            genres: &["Action", "Sci-Fi"],
+            ..TestTitle::new("tt0000001", "Harbor Raid")
+        },
+        TestTitle {
+            genres: &["Action", "Comedy"],
+            ..TestTitle::new("tt0000002", "Harbor Heist")
+        },
+        TestTitle {
+            genres: &["Drama"],
+            ..TestTitle::new("tt0000003", "Harbor Lights")
+        },
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    for (uri, expected) in [
+        (
+            "/titles/search?genres=Action&exclude_genres=Comedy",
+            &["tt0000001"][..],
+        ),
+        (
+            "/titles/search?genres=Action&exclude_genres=sci-fi",
+            &["tt0000002"],
+        ),
+        (
+            "/titles/search?genres=Sci-Fi&genres=Comedy&genre_mode=any&exclude_genres=Comedy",
+            &["tt0000001"],
+        ),
+        (
+            "/titles/search?genres=Sci-Fi&genres=Comedy&genre_mode=any&exclude_genres=action",
+            &[],
+        ),
+        ("/titles/search?exclude_genres=Action", &["tt0000003"]),
+        (
+            "/titles/search?exclude_genres=Action&exclude_genres=Drama",
+            &[],
+        ),
+    ] {
+        let mut results = fetch_tconsts(&app, uri).await?;
+        results.sort();
+        assert_eq!(results, expected, "{uri}");
+    }
+    Ok(())
+}
+
+async fn fetch_titles(
+    app: &axum::Router,
+    uri: &str,
+) -> TestResult<imdb_rs::api::types::TitleSearchResponse> {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok(from_slice(&bytes)?)
+}
+
+async fn fetch_tconsts(app: &axum::Router, uri: &str) -> TestResult<Vec<String>> {
+    let parsed = fetch_titles(app, uri).await?;
+    Ok(parsed.results.into_iter().map(|r| r.tconst).collect())
+}
+
+async fn fetch_names(
+    app: &axum::Router,
+    uri: &str,
+) -> TestResult<imdb_rs::api::types::NameSearchResponse> {
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK, "{uri}");
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    Ok(from_slice(&bytes)?)
+}
+
+/// The `nconst`s a name search returns, sorted, for tests that only check who matches.
+async fn fetch_nconsts(app: &axum::Router, uri: &str) -> TestResult<Vec<String>> {
+    let parsed = fetch_names(app, uri).await?;
+    let mut nconsts: Vec<String> = parsed.results.into_iter().map(|r| r.nconst).collect();
+    nconsts.sort();
+    Ok(nconsts)
+}
+
+#[tokio::test]
+async fn title_search_pages_with_offset() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    for sort in ["relevance", "votes_desc"] {
+        let page_one = fetch_tconsts(
+            &app,
+            &format!("/titles/search?query=Matrix&limit=2&sort={sort}"),
+        )
+        .await?;
+        let page_two = fetch_tconsts(
+            &app,
+            &format!("/titles/search?query=Matrix&limit=2&offset=2&sort={sort}"),
+        )
+        .await?;
+        assert_eq!(page_one.len(), 2, "{sort}");
+        assert_eq!(page_two.len(), 2, "{sort}");
+        assert!(
+            page_one.iter().all(|tconst| !page_two.contains(tconst)),
+            "{sort}"
+        );
+    }
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=Matrix&offset=20000")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn relevance_pages_slice_one_ranking() -> TestResult<()> {
+    // Ascending votes in index order, so re-ranking by popularity reverses the BM25 order.
+    let titles: Vec<TestTitle> = [
+        ("tt0000001", 1_000),
+        ("tt0000002", 5_000),
+        ("tt0000003", 20_000),
+        ("tt0000004", 80_000),
+        ("tt0000005", 300_000),
+        ("tt0000006", 1_000_000),
+    ]
+    .into_iter()
+    .map(|(tconst, votes)| TestTitle {
+        votes,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    })
+    .collect();
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    for query in ["", "harbor"] {
+        let mut paged = fetch_tconsts(
+            &app,
+            &format!("/titles/search?query={query}&sort=relevance&limit=2"),
+        )
+        .await?;
+        paged.extend(
+            fetch_tconsts(
+                &app,
+                &format!("/titles/search?query={query}&sort=relevance&limit=2&offset=2"),
+            )
+            .await?,
+        );
+        let single = fetch_tconsts(
+            &app,
+            &format!("/titles/search?query={query}&sort=relevance&limit=4"),
+        )
+        .await?;
+        assert_eq!(paged, single, "{query:?}");
+        assert_eq!(single[0], "tt0000006", "{query:?}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_search_supports_boolean_operators() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+    let search = |query: &str| {
+        format!("/titles/search?query={query}&limit=10&fuzzy=false&include_adult=true")
+    };
+
+    let mut all = fetch_tconsts(&app, &search("matrix%20revolutions")).await?;
+    all.sort();
+    assert_eq!(
+        all,
+        ["tt0133093", "tt0234215", "tt0242653", "tt10838180"],
+        "plain words are OR-ed by default"
+    );
+
+    let excluded = fetch_tconsts(&app, &search("matrix%20-reloaded")).await?;
+    assert_eq!(excluded.len(), 3);
+    assert!(!excluded.contains(&"tt0234215".to_string()));
+
+    let not = fetch_tconsts(&app, &search("matrix%20NOT%20reloaded")).await?;
+    assert_eq!(not, excluded);
+
+    let and_not = fetch_tconsts(&app, &search("matrix%20AND%20NOT%20reloaded")).await?;
+    assert_eq!(and_not, excluded, "`AND NOT` excludes like `NOT`");
+
+    let both = fetch_tconsts(&app, &search("matrix%20AND%20reloaded")).await?;
+    assert_eq!(both, ["tt0234215"]);
+
+    let either = fetch_tconsts(&app, &search("reloaded%20OR%20revolutions")).await?;
+    assert_eq!(either.len(), 2);
+
+    let conjunction = fetch_tconsts(
+        &app,
+        &format!("{}&default_operator=and", search("matrix%20revolutions")),
+    )
+    .await?;
+    assert_eq!(conjunction, ["tt0242653"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_types_endpoint_counts_titles_per_type() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            year: 1990,
+            ..TestTitle::new("tt0000001", "First Feature")
+        },
+        TestTitle {
+            title_type: "short",
+            year: 1991,
+            ..TestTitle::new("tt0000002", "Brief")
+        },
+        TestTitle {
+            year: 1992,
+            ..TestTitle::new("tt0000003", "Second Feature")
+        },
+        TestTitle {
+            title_type: "tvSeries",
+            year: 1993,
+            ..TestTitle::new("tt0000004", "Long Story")
+        },
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let response = app
+        .oneshot(Request::builder().uri("/title-types").body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleTypesResponse = from_slice(&bytes)?;
+    let counts: Vec<(&str, u64)> = parsed
+        .title_types
+        .iter()
+        .map(|entry| (entry.title_type.as_str(), entry.count))
+        .collect();
+    assert_eq!(counts, [("movie", 2), ("short", 1), ("tvSeries", 1)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn popularity_tier_filters_and_labels_titles() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let blockbusters = fetch_titles(&app, "/titles/search?query=matrix&tier=blockbuster").await?;
+    assert_eq!(blockbusters.results.len(), 1);
+    assert_eq!(blockbusters.results[0].tconst, "tt0133093");
+    assert_eq!(
+        blockbusters.results[0].popularity_tier,
+        Some(PopularityTier::Blockbuster)
+    );
+
+    let popular = fetch_tconsts(&app, "/titles/search?query=matrix&tier=popular").await?;
+    assert_eq!(popular.len(), 3);
+    assert!(!popular.contains(&"tt0133093".to_string()));
+    assert!(
+        fetch_tconsts(&app, "/titles/search?query=matrix&tier=indie")
+            .await?
+            .is_empty()
+    );
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=matrix&tier=huge")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn disabling_implicit_defaults_applies_only_requested_filters() -> TestResult<()> {
+    let titles = [TestTitle {
+        title_type: "short",
+        year: 1967,
+        genres: &["Animation"],
+        rating: 6.1,
+        votes: 120,
+        ..TestTitle::new("tt0061552", "Early Short")
+    }];
+    let indexes = || catalog_indexes(&titles);
+
+    let defaults = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    assert!(fetch_tconsts(&defaults, "/titles/search").await?.is_empty());
+
+    let config = imdb_rs::config::AppConfig {
+        disable_implicit_defaults: true,
+        browse_min_rating: Some(9.0),
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let plain = imdb_rs::api::router(imdb_rs::api::AppState::new(indexes(), config));
+    assert_eq!(
+        fetch_tconsts(&plain, "/titles/search").await?,
+        ["tt0061552"]
+    );
+    assert!(
+        fetch_tconsts(&plain, "/titles/search?title_type=movie")
+            .await?
+            .is_empty()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn short_queries_need_a_filter_and_empty_queries_browse_with_defaults() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            title_type: "short",
+            year: 1967,
+            genres: &["Animation"],
+            rating: 6.1,
+            votes: 120,
+            ..TestTitle::new("tt0061552", "Early Short")
+        },
+        TestTitle {
+            year: 1971,
+            genres: &["Crime"],
+            rating: 8.3,
+            votes: 900_000,
+            runtime: Some(136),
+            ..TestTitle::new("tt0066921", "A Clockwork Orange")
+        },
+        TestTitle {
+            genres: &["Adventure"],
+            rating: 8.9,
+            votes: 2_000_000,
+            runtime: Some(178),
+            ..TestTitle::new("tt0120737", "The Fellowship of the Ring")
+        },
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=a")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: serde_json::Value = from_slice(&bytes)?;
+    assert!(parsed["message"].as_str().unwrap().contains("at least 2"));
+
+    // A filter narrows the search enough for a single character.
+    for uri in [
+        "/titles/search?query=a&start_year_min=1960",
+        "/titles/search?query=a&decade=1970",
+        "/titles/search?query=a&year=1971",
+        "/titles/search?query=a&region=DE",
+        "/titles/search?query=a&aka_language=de",
+        "/titles/search?query=a&parent=tt0120737",
+        "/titles/search?query=a&season=1",
+    ] {
+        fetch_titles(&app, uri).await?;
+    }
+    // Display, sort and paging options are not filters.
+    for uri in [
+        "/titles/search?query=a&lang=de",
+        "/titles/search?query=a&sort=year_asc",
+        "/titles/search?query=a&limit=5",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+    }
+
+    // An empty query browses with the implicit title type and 1980 floor.
+    for uri in [
+        "/titles/search?query=",
+        "/titles/search?query=%20",
+        "/titles/search",
+    ] {
+        assert_eq!(fetch_tconsts(&app, uri).await?, ["tt0120737"], "{uri}");
+    }
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?query=&start_year_min=1900&sort=year_asc"
+        )
+        .await?,
+        ["tt0066921", "tt0120737"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn default_title_types_come_from_config() -> TestResult<()> {
+    let title = |tconst, title_type| TestTitle {
+        title_type,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", "movie"),
+        title("tt0000002", "tvMiniSeries"),
+    ];
+    let app_with = |config| app_with_titles(&titles, config);
+
+    let defaults = app_with(imdb_rs::config::AppConfig::default());
+    assert_eq!(
+        fetch_tconsts(&defaults, "/titles/search?query=harbor").await?,
+        ["tt0000001"]
+    );
+
+    let tv_only = app_with(imdb_rs::config::AppConfig {
+        default_title_types: vec!["tvSeries".to_string(), "tvMiniSeries".to_string()],
+        ..imdb_rs::config::AppConfig::default()
+    });
+    assert_eq!(
+        fetch_tconsts(&tv_only, "/titles/search?query=harbor").await?,
+        ["tt0000002"]
+    );
+    assert_eq!(
+        fetch_tconsts(&tv_only, "/titles/search?query=harbor&title_type=movie").await?,
+        ["tt0000001"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn search_total_ignores_paging_but_respects_filters() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let first = fetch_titles(&app, "/titles/search?query=Matrix&limit=1").await?;
+    assert_eq!(first.total, 4);
+    assert_eq!(first.results.len(), 1);
+
+    let later = fetch_titles(
+        &app,
+        "/titles/search?query=Matrix&limit=1&offset=3&sort=rating_desc",
+    )
+    .await?;
+    assert_eq!(later.total, 4);
+
+    let filtered = fetch_titles(
+        &app,
+        "/titles/search?query=Matrix&start_year_min=2003&start_year_max=2003",
+    )
+    .await?;
+    assert_eq!(filtered.total, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn related_genres_rank_co_occurring_genres() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            genres: &["Sci-Fi", "Action", "Thriller"],
+            rating: 6.0,
+            votes: 100,
+            ..TestTitle::new("tt0000001", "Space Chase")
+        },
+        TestTitle {
+            year: 2002,
+            genres: &["Sci-Fi", "Action"],
+            rating: 6.0,
+            votes: 100,
+            ..TestTitle::new("tt0000002", "Space Brawl")
+        },
+        TestTitle {
+            year: 2003,
+            genres: &["Drama", "Thriller"],
+            rating: 6.0,
+            votes: 100,
+            ..TestTitle::new("tt0000003", "Quiet Drama")
+        },
+    ];
+    let indexes = catalog_indexes(&titles);
+    let index = indexes.titles.reader.searcher().index().clone();
+    let reader = indexes.titles.reader.clone();
+    let tconst_field = indexes.titles.fields.tconst;
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        indexes,
+        imdb_rs::config::AppConfig::default(),
+    ));
+    let fetch_related = || async {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/genres/sci-fi/related")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: imdb_rs::api::types::RelatedGenresResponse = from_slice(&bytes)?;
+        assert_eq!(parsed.genre, "Sci-Fi");
+        let related: Vec<(String, usize)> = parsed
+            .related
+            .into_iter()
+            .map(|entry| (entry.genre, entry.count))
+            .collect();
+        TestResult::Ok((parsed.titles, related))
+    };
+
+    for _ in 0..2 {
+        let (titles, related) = fetch_related().await?;
+        assert_eq!(titles, 2);
+        assert_eq!(related, [("Action".into(), 2), ("Thriller".into(), 1)]);
+    }
+
+    // An in-place commit changes the index generation, so the cached entry is recomputed.
+    let mut writer = index.writer::<TantivyDocument>(20_000_000)?;
+    writer.delete_term(Term::from_field_text(tconst_field, "tt0000002"));
+    writer.commit()?;
+    reader.reload()?;
+    let (titles, related) = fetch_related().await?;
+    assert_eq!(titles, 1);
+    assert_eq!(related, [("Action".into(), 1), ("Thriller".into(), 1)]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/genres/unknown/related")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn decade_expands_to_its_start_year_range() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            year: 1999,
+            genres: &["Action", "Sci-Fi"],
+            rating: 8.7,
+            votes: 1_900_000,
+            runtime: Some(136),
+            ..TestTitle::new("tt0133093", "The Matrix")
+        },
+        TestTitle {
+            genres: &["Adventure"],
+            rating: 8.9,
+            votes: 2_000_000,
+            runtime: Some(178),
+            ..TestTitle::new("tt0120737", "The Fellowship of the Ring")
+        },
+        TestTitle {
+            year: 1990,
+            genres: &["Crime"],
+            rating: 8.7,
+            votes: 1_200_000,
+            runtime: Some(145),
+            ..TestTitle::new("tt0099685", "Goodfellas")
+        },
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?decade=1990&sort=year_asc").await?,
+        ["tt0099685", "tt0133093"]
+    );
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?decade=1990&genres=Sci-Fi&min_rating=8"
+        )
+        .await?,
+        ["tt0133093"]
+    );
+
+    for uri in [
+        "/titles/search?decade=1990&start_year_min=1995",
+        "/titles/search?decade=1995",
+        "/titles/search?decade=1700",
+        "/titles/search?decade=9223372036854775800",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn year_matches_a_single_start_year() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            year: 1999,
+            genres: &["Action", "Sci-Fi"],
+            rating: 8.7,
+            votes: 1_900_000,
+            runtime: Some(136),
+            ..TestTitle::new("tt0133093", "The Matrix")
+        },
+        TestTitle {
+            year: 1998,
+            genres: &["Mystery", "Sci-Fi"],
+            rating: 7.6,
+            votes: 210_000,
+            runtime: Some(100),
+            ..TestTitle::new("tt0120669", "Dark City")
+        },
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?year=1999&genres=Sci-Fi").await?,
+        ["tt0133093"]
+    );
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?year=1998&genres=Sci-Fi").await?,
+        ["tt0120669"]
+    );
+
+    for uri in [
+        "/titles/search?year=1999&start_year_min=1990",
+        "/titles/search?year=1999&start_year_max=2000",
+        "/titles/search?year=1999&decade=1990",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn csv_format_returns_one_quoted_line_per_result() -> TestResult<()> {
+    let titles = [TestTitle {
+        year: 2000,
+        genres: &["Action", "Adventure"],
+        rating: 7.9,
+        votes: 280_000,
+        runtime: Some(120),
+        ..TestTitle::new("tt0190332", "Crouching Tiger, Hidden Dragon")
+    }];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=tiger&format=csv")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        "text/csv; charset=utf-8"
+    );
+    assert!(response.headers().contains_key("x-index-generation"));
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let text = String::from_utf8(bytes.to_vec())?;
+    assert!(
+        text.contains("\"Crouching Tiger, Hidden Dragon\""),
+        "{text}"
+    );
+
+    let mut reader = csv::Reader::from_reader(text.as_bytes());
+    assert_eq!(
+        reader.headers()?,
+        vec![
+            "tconst",
+            "primary_title",
+            "title_type",
+            "start_year",
+            "average_rating",
+            "num_votes",
+            "genres"
+        ]
+    );
+    let rows: Vec<csv::StringRecord> = reader.records().collect::<Result<_, _>>()?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(
+        rows[0],
+        vec![
+            "tt0190332",
+            "Crouching Tiger, Hidden Dragon",
+            "movie",
+            "2000",
+            "7.9",
+            "280000",
+            "Action|Adventure"
+        ]
+    );
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=tiger&format=csv&group_by=title_type")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn limits_are_capped_by_config_with_a_higher_csv_ceiling() -> TestResult<()> {
+    let config = imdb_rs::config::AppConfig {
+        max_limit: 2,
+        max_export_limit: 3,
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let app = app_with_titles(CATALOG, config);
+
+    let json = fetch_titles(&app, "/titles/search?query=matrix&limit=50").await?;
+    assert_eq!(json.results.len(), 2);
+    assert_eq!(json.total, CATALOG.len());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=matrix&limit=50&format=csv")
+                .body(Body::empty())?,
+        )
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let mut reader = csv::Reader::from_reader(bytes.as_ref());
+    assert_eq!(reader.records().count(), 3);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=matrix&offset=9999&limit=2")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_search_filters_on_runtime() -> TestResult<()> {
+    let titles = [
+        TestTitle {
+            title_type: "short",
+            year: 2010,
+            genres: &["Animation"],
+            votes: 100,
+            runtime: Some(12),
+            ..TestTitle::new("tt0000001", "Paper Boat")
+        },
+        TestTitle {
+            title_type: "short",
+            year: 2011,
+            votes: 100,
+            runtime: Some(45),
+            ..TestTitle::new("tt0000002", "Paper Moon Landing")
+        },
+        TestTitle {
+            title_type: "short",
+            year: 2012,
+            votes: 100,
+            ..TestTitle::new("tt0000003", "Paper Trail")
+        },
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let under_40 = fetch_titles(
+        &app,
+        "/titles/search?query=paper&title_type=short&max_runtime=40",
+    )
+    .await?;
+    let found: Vec<_> = under_40
+        .results
+        .iter()
+        .map(|title| (title.tconst.as_str(), title.runtime_minutes))
+        .collect();
+    assert_eq!(found, [("tt0000001", Some(12))]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?min_runtime=90&max_runtime=30")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_search_groups_results_by_title_type() -> TestResult<()> {
+    let title = |tconst, title_type, title, votes| TestTitle {
+        title_type,
+        year: 2000,
+        genres: &["Sci-Fi"],
+        votes,
+        ..TestTitle::new(tconst, title)
+    };
+    let titles = [
+        title("tt0000001", "movie", "Star Voyage", 900),
+        title("tt0000002", "movie", "Star Voyage II", 500),
+        title("tt0000003", "tvSeries", "Star Voyage: The Series", 800),
+        title("tt0000004", "tvSeries", "Star Voyage: Academy", 300),
+        title("tt0000005", "movie", "Star Voyage III", 100),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=star&group_by=title_type&limit=2&sort=votes_desc")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::GroupedTitleSearchResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.total, 5);
+    let bucket = |name: &str| -> Vec<String> {
+        parsed.groups[name]
+            .iter()
+            .map(|title| title.tconst.clone())
+            .collect()
+    };
+    assert_eq!(parsed.groups.len(), 2);
+    assert_eq!(bucket("movie"), ["tt0000001", "tt0000002"]);
+    assert_eq!(bucket("tvSeries"), ["tt0000003", "tt0000004"]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=star&group_by=title_type&offset=10")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    Ok(())
+}
+
+#[tokio::test]
+async fn adult_titles_are_hidden_unless_requested() -> TestResult<()> {
+    let title = |tconst, title, adult| TestTitle {
+        year: 2005,
+        rating: 6.0,
+        votes: 100,
+        adult,
+        ..TestTitle::new(tconst, title)
+    };
+    let titles = [
+        title("tt0000001", "Midnight Garden", false),
+        title("tt0000002", "Midnight Garden After Dark", true),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let hidden = fetch_tconsts(&app, "/titles/search?query=midnight").await?;
+    assert_eq!(hidden, ["tt0000001"]);
+
+    let mut shown = fetch_tconsts(&app, "/titles/search?query=midnight&include_adult=true").await?;
+    shown.sort();
+    assert_eq!(shown, ["tt0000001", "tt0000002"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_cast_lists_principals_in_billing_order() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0133093/cast")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleCastResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.tconst, "tt0133093");
+    let cast: Vec<_> = parsed
+        .cast
+        .iter()
+        .map(|member| {
+            (
+                member.nconst.as_str(),
+                member.category.as_str(),
+                member.ordering,
+            )
+        })
+        .collect();
+    assert_eq!(
+        cast,
+        [("nm0000206", "actor", 1), ("nm0905154", "director", 5)]
+    );
+    assert_eq!(parsed.cast[0].characters, ["Neo"]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt9999999/cast")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+/// A series (`tt0903747`) with three numbered episodes over two seasons and one unnumbered one.
+fn build_series_indexes() -> PreparedIndexes {
+    let options = TitleIndexOptions::default();
+    let index = Index::create_in_ram(build_title_schema(&options));
+    register_title_tokenizers(&index, &options).unwrap();
+    let fields = TitleFields::new(&index.schema()).unwrap();
+    let mut writer = index.writer::<TantivyDocument>(20_000_000).unwrap();
+    let mut series = TantivyDocument::default();
+    series.add_text(fields.tconst, "tt0903747");
+    series.add_text(fields.title_type, "tvSeries");
+    series.add_text(fields.primary_title, "Breaking Bad");
+    series.add_text(fields.primary_title_sort, "breaking bad");
+    series.add_text(fields.primary_title_sort_stripped, "breaking bad");
+    writer.add_document(series).unwrap();
+    for (tconst, title, season, episode) in [
+        ("tt1232249", "Unnumbered", None, None),
+        ("tt1232244", "Seven Thirty-Seven", Some(2), Some(1)),
+        ("tt0959621", "Pilot", Some(1), Some(1)),
+        ("tt1054724", "Cat's in the Bag...", Some(1), Some(2)),
+    ] {
+        let mut doc = TantivyDocument::default();
+        doc.add_text(fields.tconst, tconst);
+        doc.add_text(fields.title_type, "tvEpisode");
+        doc.add_text(fields.primary_title, title);
+        doc.add_text(fields.primary_title_sort, title_sort_key(title, false));
+        doc.add_text(
+            fields.primary_title_sort_stripped,
+            title_sort_key(title, true),
+        );
+        doc.add_text(fields.parent_tconst, "tt0903747");
+        if let Some(season) = season {
+            doc.add_i64(fields.season_number, season);
+        }
+        if let Some(episode) = episode {
+            doc.add_i64(fields.episode_number, episode);
+        }
+        doc.add_f64(fields.average_rating, 8.5);
+        doc.add_i64(fields.num_votes, 30_000);
+        writer.add_document(doc).unwrap();
+    }
+    writer.commit().unwrap();
+    PreparedIndexes {
+        titles: TitleIndex::from_index(&index, &options).unwrap(),
+        names: build_test_name_index(),
+    }
+}
+
+/// Harbor series that ended (1995–2005, 1975–1979) or are still running (since 1999 and
+/// 1975), the latter with an `endYear` of `\N`, plus a 2001 miniseries that IMDb also lists
+/// without an end year.
+fn build_running_series_indexes() -> PreparedIndexes {
+    let options = TitleIndexOptions::default();
+    let index = Index::create_in_ram(build_title_schema(&options));
+    register_title_tokenizers(&index, &options).unwrap();
+    let fields = TitleFields::new(&index.schema()).unwrap();
+    let mut writer = index.writer::<TantivyDocument>(20_000_000).unwrap();
+    for (tconst, title_type, title, start_year, end_year) in [
+        ("tt0000101", "tvSeries", "Harbor Lights", 1995, Some(2005)),
+        ("tt0000102", "tvSeries", "Harbor Watch", 1999, None),
+        ("tt0000103", "tvSeries", "Harbor Patrol", 1975, None),
+        ("tt0000104", "tvSeries", "Harbor Days", 1975, Some(1979)),
+        ("tt0000105", "tvMiniSeries", "Harbor Nights", 2001, None),
+    ] {
+        let mut doc = TantivyDocument::default();
+        doc.add_text(fields.tconst, tconst);
+        doc.add_text(fields.title_type, title_type);
+        doc.add_text(fields.primary_title, title);
+        doc.add_text(fields.primary_title_sort, title_sort_key(title, false));
+        doc.add_text(
+            fields.primary_title_sort_stripped,
+            title_sort_key(title, true),
+        );
+        doc.add_text(fields.search_titles, title);
+        doc.add_i64(fields.start_year, start_year);
+        if let Some(end_year) = end_year {
+            doc.add_i64(fields.end_year, end_year);
+        }
+        doc.add_f64(fields.average_rating, 8.0);
+        doc.add_i64(fields.num_votes, 10_000);
+        doc.add_i64(fields.is_adult, 0);
+        writer.add_document(doc).unwrap();
+    }
+    writer.commit().unwrap();
+    PreparedIndexes {
+        titles: TitleIndex::from_index(&index, &options).unwrap(),
+        names: build_test_name_index(),
+    }
+}
+
+#[tokio::test]
+async fn running_series_are_not_dropped_by_default_year_filters() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_running_series_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let series = fetch_titles(&app, "/titles/search?query=harbor").await?;
+    assert!(series.results.iter().all(|title| title.is_series));
+
+    // The implicit 1980 floor drops the old finished series but not the old running one.
+    let mut searched = fetch_tconsts(&app, "/titles/search?query=harbor").await?;
+    searched.sort();
+    assert_eq!(searched, ["tt0000101", "tt0000102", "tt0000103"]);
+    let explicit = fetch_tconsts(&app, "/titles/search?query=harbor&start_year_min=1980").await?;
+    assert!(!explicit.contains(&"tt0000103".to_string()));
+
+    // Browsing applies the implicit defaults, none of which look at the end year.
+    let browsed = fetch_tconsts(&app, "/titles/search").await?;
+    assert!(browsed.contains(&"tt0000102".to_string()));
+
+    let ended = fetch_tconsts(&app, "/titles/search?query=harbor&end_year_max=2010").await?;
+    assert_eq!(ended, ["tt0000101"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn end_year_floor_keeps_still_running_series() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_running_series_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let mut since_2000 =
+        fetch_tconsts(&app, "/titles/search?query=harbor&end_year_min=2000").await?;
+    since_2000.sort();
+    assert_eq!(since_2000, ["tt0000101", "tt0000102", "tt0000103"]);
+    let mut since_2010 =
+        fetch_tconsts(&app, "/titles/search?query=harbor&end_year_min=2010").await?;
+    since_2010.sort();
+    assert_eq!(since_2010, ["tt0000102", "tt0000103"]);
+    // A running series has not ended yet, so it cannot have ended in the future either.
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=harbor&end_year_min=2999").await?,
+        Vec::<String>::new()
+    );
+    // A miniseries without an end year aired within its start year rather than still running.
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?query=harbor&title_type=tvMiniSeries&end_year_min=2000"
+        )
+        .await?,
+        Vec::<String>::new()
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn episodes_are_listed_by_season_then_episode() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_series_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0903747/episodes")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::TitleEpisodesResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.total, 4);
+    let order: Vec<&str> = parsed.episodes.iter().map(|e| e.tconst.as_str()).collect();
+    assert_eq!(order, ["tt0959621", "tt1054724", "tt1232244", "tt1232249"]);
+    assert_eq!(parsed.episodes[0].primary_title, "Pilot");
+    assert_eq!(parsed.episodes[0].average_rating, Some(8.5));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0903747/episodes?offset=1&limit=2")
+                .body(Body::empty())?,
+        )
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let page: imdb_rs::api::types::TitleEpisodesResponse = from_slice(&bytes)?;
+    assert_eq!(page.total, 4);
+    let order: Vec<&str> = page.episodes.iter().map(|e| e.tconst.as_str()).collect();
+    assert_eq!(order, ["tt1054724", "tt1232244"]);
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0903747/episodes?offset=20000")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt9999999/episodes")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_search_filters_episodes_by_series_and_season() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_series_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    // Episodes are neither movies nor series, so the browse defaults must not hide them.
+    assert_eq!(
+        fetch_titles(&app, "/titles/search?parent=tt0903747")
+            .await?
+            .total,
+        4
+    );
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?parent=tt0903747&season=1&sort=title_asc"
+        )
+        .await?,
+        ["tt1054724", "tt0959621"]
+    );
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=pilot&parent=tt0903747").await?,
+        ["tt0959621"]
+    );
+    assert!(
+        fetch_tconsts(&app, "/titles/search?parent=tt0903747&season=3")
+            .await?
+            .is_empty()
+    );
+    // A season on its own also selects episodes, across every series.
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?season=1&sort=title_asc").await?,
+        ["tt1054724", "tt0959621"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn known_for_titles_keep_imdb_order() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/names/nm0000206")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::NameSearchResult = from_slice(&bytes)?;
+    assert_eq!(
+        parsed.known_for_titles.unwrap(),
+        ["tt0133093", "tt0111257", "tt0234215"]
+    );
+    assert_eq!(parsed.imdb_url, "https://www.imdb.com/name/nm0000206/");
+    Ok(())
+}
+
+#[tokio::test]
+async fn known_for_titles_resolve_against_the_title_index() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+    let fetch = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            let etag = response.headers().get("etag").cloned();
+            let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+            TestResult::Ok((etag, bytes))
+        }
+    };
+
+    let (plain_etag, bytes) = fetch("/names/nm0000206").await?;
+    let plain: imdb_rs::api::types::NameSearchResult = from_slice(&bytes)?;
+    assert!(plain.known_for.is_none());
+
+    // tt0111257 is not in the title index and is skipped.
+    let (resolved_etag, bytes) = fetch("/names/nm0000206?resolve_known_for=true").await?;
+    let resolved: imdb_rs::api::types::NameSearchResult = from_slice(&bytes)?;
+    let known_for: Vec<(&str, &str, Option<i64>)> = resolved
+        .known_for
+        .as_deref()
+        .unwrap_or_default()
+        .iter()
+        .map(|title| {
+            (
+                title.tconst.as_str(),
+                title.primary_title.as_str(),
+                title.start_year,
+            )
+        })
+        .collect();
+    assert_eq!(
+        known_for,
+        [
+            ("tt0133093", "The Matrix", Some(1999)),
+            ("tt0234215", "The Matrix Reloaded", Some(2003)),
+        ]
+    );
+    assert_eq!(resolved.known_for_titles.unwrap().len(), 3);
+    assert_ne!(plain_etag, resolved_etag);
+
+    let (_, bytes) = fetch("/names/search?query=keanu&resolve_known_for=true").await?;
+    let search: imdb_rs::api::types::NameSearchResponse = from_slice(&bytes)?;
+    assert_eq!(search.results[0].known_for.as_ref().map(Vec::len), Some(2));
+    Ok(())
+}
+
+#[tokio::test]
+async fn normalized_title_scores_top_out_at_one() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let page = fetch_titles(&app, "/titles/search?query=matrix&normalize_scores=true").await?;
+    let scores: Vec<f32> = page
+        .results
+        .iter()
+        .filter_map(|title| title.score)
+        .collect();
+    assert_eq!(scores.len(), 4);
+    assert_eq!(scores[0], 1.0);
+    assert!(scores[1..].iter().all(|score| *score < 1.0 && *score > 0.0));
+
+    let later = fetch_titles(
+        &app,
+        "/titles/search?query=matrix&normalize_scores=true&offset=1&limit=1",
+    )
+    .await?;
+    assert_eq!(later.results[0].score, Some(scores[1]));
+
+    let raw = fetch_titles(&app, "/titles/search?query=matrix").await?;
+    assert!(raw.results[0].score.unwrap() > 1.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn fuzzy_matching_can_be_disabled_per_request() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let typo = fetch_tconsts(&app, "/titles/search?query=matrx").await?;
+    assert_eq!(typo, ["tt0133093"]);
+    let exact = fetch_tconsts(&app, "/titles/search?query=matrx&fuzzy=false").await?;
+    assert!(exact.is_empty());
+
+    for (uri, expected) in [
+        ("/names/search?query=reves", 1),
+        ("/names/search?query=reves&fuzzy=false", 0),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: imdb_rs::api::types::NameSearchResponse = from_slice(&bytes)?;
+        assert_eq!(parsed.total, expected, "{uri}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn fuzzy_distance_scales_with_query_length_or_config() -> TestResult<()> {
+    let auto = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+    // Two edits away from "matrix": too many for a four-letter word.
+    assert!(
+        fetch_tconsts(&auto, "/titles/search?query=mtrx")
+            .await?
+            .is_empty()
+    );
+
+    let config = imdb_rs::config::AppConfig {
+        fuzzy_distance: FuzzyDistance::Fixed(2),
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let loose = imdb_rs::api::router(imdb_rs::api::AppState::new(build_test_indexes(), config));
+    assert_eq!(
+        fetch_tconsts(&loose, "/titles/search?query=mtrx").await?,
+        ["tt0133093"]
+    );
+    assert!(
+        fetch_tconsts(&loose, "/titles/search?query=mtrx&fuzzy=false")
+            .await?
+            .is_empty()
+    );
+
+    // Each word gets its own distance: the long typo tolerates two edits while "ran" next to it
+    // still has to match exactly, rather than also finding "Man".
+    let app = app_with_titles(
+        &[
+            TestTitle::new("tt0089881", "Ran"),
+            TestTitle::new("tt0328107", "Man on Fire"),
+            TestTitle::new("tt0088247", "The Terminator"),
+        ],
+        imdb_rs::config::AppConfig::default(),
+    );
+    let mut found = fetch_tconsts(&app, "/titles/search?query=ran%20termnatr").await?;
+    found.sort();
+    assert_eq!(found, ["tt0088247", "tt0089881"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn freshness_window_lifts_recent_titles_only_when_requested() -> TestResult<()> {
+    use chrono::Datelike;
+
+    let current_year = i64::from(chrono::Utc::now().year());
+    let title = |tconst, year, rating, votes| TestTitle {
+        year,
+        rating,
+        votes,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", current_year - 30, 8.0, 200_000),
+        title("tt0000002", current_year - 1, 7.0, 40_000),
+    ];
+    let app = app_with_titles(
+        &titles,
+        imdb_rs::config::AppConfig {
+            disable_implicit_defaults: true,
+            ..imdb_rs::config::AppConfig::default()
+        },
+    );
+
+    let plain = fetch_tconsts(&app, "/titles/search?query=harbor").await?;
+    assert_eq!(plain, ["tt0000001", "tt0000002"]);
+    let fresh = fetch_tconsts(&app, "/titles/search?query=harbor&boost_recent_years=5").await?;
+    assert_eq!(fresh, ["tt0000002", "tt0000001"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn phrase_match_ranks_the_words_in_order_first() -> TestResult<()> {
+    let title = |tconst, title, votes| TestTitle {
+        votes,
+        ..TestTitle::new(tconst, title)
+    };
+    let titles = [
+        title("tt0000001", "Enter the Matrix", 5_000),
+        title("tt0000002", "Matrix of the Mind", 900_000),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let any = fetch_tconsts(&app, "/titles/search?query=the%20matrix").await?;
+    assert_eq!(any, ["tt0000002", "tt0000001"]);
+    // The phrase match ranks the exact title first without dropping the looser match.
+    let phrase = fetch_tconsts(&app, "/titles/search?query=the%20matrix&match=phrase").await?;
+    assert_eq!(phrase, ["tt0000001", "tt0000002"]);
+    let single = fetch_tconsts(&app, "/titles/search?query=mind&match=phrase").await?;
+    assert_eq!(single, ["tt0000002"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn debug_flag_exposes_the_assembled_query() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let plain = fetch_titles(&app, "/titles/search?query=matrix").await?;
+    assert!(plain.query_debug.is_none());
+    let debug = fetch_titles(&app, "/titles/search?query=matrix&debug=true").await?;
+    let query_debug = debug.query_debug.expect("query_debug present");
+    assert!(query_debug.contains("FuzzyTermQuery"), "{query_debug}");
+    assert!(query_debug.contains("RangeQuery"), "{query_debug}");
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/names/search?query=keanu&debug=true")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::NameSearchResponse = from_slice(&bytes)?;
+    assert!(parsed.query_debug.is_some());
+    Ok(())
+}
+
+#[tokio::test]
+async fn highlight_marks_matched_title_words() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let highlighted = fetch_titles(&app, "/titles/search?query=reloaded&highlight=true").await?;
+    assert_eq!(
+        highlighted.results[0].highlighted_title.as_deref(),
+        Some("The Matrix <em>Reloaded</em>")
+    );
+
+    let plain = fetch_titles(&app, "/titles/search?query=reloaded").await?;
+    assert!(plain.results[0].highlighted_title.is_none());
+    let sorted = fetch_titles(
+        &app,
+        "/titles/search?query=reloaded&highlight=true&sort=votes_desc",
+    )
+    .await?;
+    assert!(sorted.results[0].highlighted_title.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn field_qualified_queries_are_limited_to_whitelisted_fields() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let allowed = fetch_tconsts(
+        &app,
+        "/titles/search?query=reloaded%20AND%20title_type:movie%20AND%20genres:action",
+    )
+    .await?;
+    assert_eq!(allowed, ["tt0234215"]);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/search?query=tconst:tt0133093")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: serde_json::Value = from_slice(&bytes)?;
+    let message = parsed["message"].as_str().unwrap_or_default();
+    assert!(message.contains("`tconst`"), "{message}");
+    assert!(message.contains("genres, title_type"), "{message}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn field_aliases_map_onto_schema_fields() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let by_year = fetch_tconsts(&app, "/titles/search?query=matrix%20AND%20year:2003").await?;
+    assert_eq!(by_year.len(), 2);
+    assert!(!by_year.contains(&"tt0133093".to_string()));
+    let by_range = fetch_tconsts(
+        &app,
+        "/titles/search?query=matrix%20AND%20year:%5B1990%20TO%202003%5D",
+    )
+    .await?;
+    assert_eq!(by_range.len(), 3);
+
+    let by_title = fetch_tconsts(
+        &app,
+        "/titles/search?query=title:resurrections%20AND%20genre:sci-fi",
+    )
+    .await?;
+    assert_eq!(by_title, ["tt10838180"]);
+
+    for (query, expected) in [
+        ("titleType:movie", "`titleType`"),
+        ("title:(matrix", "syntax error"),
+    ] {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/titles/search?query={query}"))
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{query}");
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let parsed: serde_json::Value = from_slice(&bytes)?;
+        let message = parsed["message"].as_str().unwrap_or_default();
+        assert!(message.contains(expected), "{message}");
+        assert!(
+            message.contains("title, genre, genres, title_type, year"),
+            "{message}"
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn empty_searches_can_suggest_a_respelled_query() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    // Two edits away from "matrix", beyond the regular typo tolerance.
+    let plain = fetch_titles(&app, "/titles/search?query=mtrx").await?;
+    assert_eq!(plain.total, 0);
+    assert!(plain.did_you_mean.is_none());
+
+    let suggested = fetch_titles(&app, "/titles/search?query=mtrx&suggest=true").await?;
+    assert_eq!(suggested.total, 0);
+    assert_eq!(suggested.did_you_mean.as_deref(), Some("matrix"));
+
+    // Searches with results, or without anything close, suggest nothing.
+    let found = fetch_titles(&app, "/titles/search?query=matrix&suggest=true").await?;
+    assert!(found.total > 0);
+    assert!(found.did_you_mean.is_none());
+    let unknown = fetch_titles(&app, "/titles/search?query=zzzzzz&suggest=true").await?;
+    assert!(unknown.did_you_mean.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn facet_counts_ignore_the_genre_filter() -> TestResult<()> {
+    let title = |tconst, genres| TestTitle {
+        genres,
+        votes: 10_000,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", &["Comedy", "Drama"]),
+        title("tt0000002", &["Drama"]),
+        title("tt0000003", &["Action", "Sci-Fi"]),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let faceted =
+        fetch_titles(&app, "/titles/search?query=harbor&genres=Drama&facets=true").await?;
+    assert_eq!(faceted.total, 2);
+    let counts = faceted.facet_counts.expect("facet_counts present");
+    let expected = [("Action", 1), ("Comedy", 1), ("Drama", 2), ("Sci-Fi", 1)];
+    assert_eq!(
+        counts,
+        expected
+            .iter()
+            .map(|(genre, count)| (genre.to_string(), *count))
+            .collect()
+    );
+
+    let plain = fetch_titles(&app, "/titles/search?query=harbor").await?;
+    assert!(plain.facet_counts.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn responses_are_compressed_when_the_client_accepts_it() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+    let get = |uri: &str, encoding: &str| {
+        Request::builder()
+            .uri(uri)
+            .header("accept-encoding", encoding)
+            .body(Body::empty())
+    };
+
+    let response = app
+        .clone()
+        .oneshot(get("/titles/search?query=matrix&limit=50", "gzip")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-encoding"], "gzip");
+
+    let response = app.clone().oneshot(get("/filters", "br")?).await?;
+    assert_eq!(response.headers()["content-encoding"], "br");
+
+    let response = app.oneshot(get("/healthz", "gzip")?).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("content-encoding"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn openapi_spec_describes_the_routes() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/openapi.json")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let spec: serde_json::Value = from_slice(&bytes)?;
+    for path in [
+        "/titles/search",
+        "/titles/{tconst}",
+        "/names/search",
+        "/admin/reindex",
+    ] {
+        assert!(spec["paths"].get(path).is_some(), "{path} missing");
+    }
+    let params = spec["paths"]["/titles/search"]["get"]["parameters"]
+        .as_array()
+        .expect("search parameters");
+    let names: Vec<_> = params
+        .iter()
+        .filter_map(|param| param["name"].as_str())
+        .collect();
+    assert!(
+        names.contains(&"match") && names.contains(&"genres"),
+        "{names:?}"
+    );
+    let sorts = &spec["components"]["schemas"]["SortMode"]["enum"];
+    assert!(
+        sorts
+            .as_array()
+            .is_some_and(|sorts| sorts.iter().any(|sort| sort == "rating_desc")),
+        "{sorts}"
+    );
+
+    let mut refs = Vec::new();
+    collect_refs(&spec, &mut refs);
+    for schema in refs {
+        let name = schema.trim_start_matches("#/components/schemas/");
+        assert!(
+            spec["components"]["schemas"].get(name).is_some(),
+            "dangling {schema}"
+        );
+    }
+
+    let response = app
+        .oneshot(Request::builder().uri("/docs").body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    assert!(String::from_utf8(bytes.to_vec())?.contains("/openapi.json"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn stats_reports_document_counts() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/stats").body(Body::empty())?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let stats: imdb_rs::api::types::StatsResponse = from_slice(&bytes)?;
+    assert_eq!(stats.titles.num_docs, CATALOG.len() as u64);
+    assert_eq!(stats.names.num_docs, 1);
+    // Test indexes are committed directly rather than through the index build.
+    assert!(stats.titles.last_build.is_none());
+
+    let response = app
+        .oneshot(Request::builder().uri("/meta").body(Body::empty())?)
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let meta: imdb_rs::api::types::MetaResponse = from_slice(&bytes)?;
+    assert!(meta.titles.is_none() && meta.names.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn repeated_title_searches_are_served_from_the_cache() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+    let search_cache = |app: axum::Router| async move {
+        let response = app
+            .oneshot(Request::builder().uri("/stats").body(Body::empty())?)
+            .await?;
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let stats: imdb_rs::api::types::StatsResponse = from_slice(&bytes)?;
+        TestResult::Ok((stats.search_cache.hits, stats.search_cache.misses))
+    };
+
+    let first = fetch_tconsts(&app, "/titles/search?query=matrix").await?;
+    assert_eq!(search_cache(app.clone()).await?, (0, 1));
+    let second = fetch_tconsts(&app, "/titles/search?query=%20matrix%20").await?;
+    assert_eq!(second, first);
+    assert_eq!(search_cache(app.clone()).await?, (1, 1));
+
+    let bypassed = fetch_tconsts(&app, "/titles/search?query=matrix&no_cache=true").await?;
+    assert_eq!(bypassed, first);
+    assert_eq!(search_cache(app.clone()).await?, (1, 1));
+
+    fetch_tconsts(&app, "/titles/search?query=matrix&limit=1").await?;
+    assert_eq!(search_cache(app.clone()).await?, (1, 2));
+
+    // CSV exports bypass the cache entirely.
+    for _ in 0..2 {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/titles/search?query=matrix&format=csv")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    assert_eq!(search_cache(app.clone()).await?, (1, 2));
+    Ok(())
+}
+
+#[tokio::test]
+async fn similar_titles_share_genres_or_fall_back_to_decade() -> TestResult<()> {
+    let title = |tconst, title_type, year, genres| TestTitle {
+        title_type,
+        year,
+        genres,
+        votes: 10_000,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", "movie", 2001, &["Action", "Sci-Fi"]),
+        title("tt0000002", "movie", 1980, &["Action"]),
+        title("tt0000003", "movie", 2001, &["Drama"]),
+        title("tt0000004", "tvSeries", 1995, &[]),
+        title("tt0000005", "tvSeries", 1998, &[]),
+        title("tt0000006", "tvSeries", 2005, &[]),
+        title("tt0000007", "movie", 1996, &[]),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    let fetch_similar = |uri: &'static str| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(uri).body(Body::empty())?)
+                .await?;
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+            let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+            let parsed: imdb_rs::api::types::SimilarTitlesResponse = from_slice(&bytes)?;
+            TestResult::Ok(
+                parsed
+                    .results
+                    .into_iter()
+                    .map(|result| result.tconst)
+                    .collect::<Vec<_>>(),
+            )
+        }
+    };
+
+    assert_eq!(
+        fetch_similar("/titles/tt0000001/similar").await?,
+        ["tt0000002"]
+    );
+    assert_eq!(
+        fetch_similar("/titles/tt0000004/similar").await?,
+        ["tt0000005"]
+    );
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt9999999/similar")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/titles/tt0000001/similar?limit=many")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let error: serde_json::Value = from_slice(&bytes)?;
+    assert!(error["message"].is_string(), "{error}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn similar_titles_rank_by_genre_overlap() -> TestResult<()> {
+    let title = |tconst, genres, rating, votes| TestTitle {
+        genres,
+        rating,
+        votes,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", &["Action", "Drama", "Sci-Fi"], 7.0, 10_000),
+        title("tt0000002", &["Action", "Drama", "Sci-Fi"], 6.0, 2_000),
+        title("tt0000003", &["Sci-Fi"], 8.5, 2_000_000),
+        title("tt0000004", &["Action", "Drama"], 5.0, 100),
+        title("tt0000005", &["Action", "Drama"], 5.0, 100),
+        title("tt0000006", &["Action", "Drama"], 5.0, 100),
+        title("tt0000007", &["Action", "Drama"], 5.0, 100),
+    ];
+    let top_similar = |genre_match_boost| {
+        let app = app_with_titles(
+            &titles,
+            imdb_rs::config::AppConfig {
+                genre_match_boost,
+                ..imdb_rs::config::AppConfig::default()
+            },
+        );
+        async move {
+            let response = app
+                .oneshot(
+                    Request::builder()
+                        .uri("/titles/tt0000001/similar")
+                        .body(Body::empty())?,
+                )
+                .await?;
+            let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+            let parsed: imdb_rs::api::types::SimilarTitlesResponse = from_slice(&bytes)?;
+            TestResult::Ok(parsed.results[0].tconst.clone())
+        }
+    };
+
+    // Without the boost the blockbuster sharing one genre wins on popularity alone.
+    assert_eq!(top_similar(0.0).await?, "tt0000003");
+    assert_eq!(top_similar(2.0).await?, "tt0000002");
+    Ok(())
+}
+
+#[tokio::test]
+async fn embedded_ids_pin_their_document_first() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let text_only = fetch_tconsts(&app, "/titles/search?query=matrix").await?;
+    assert_eq!(text_only[0], "tt0133093");
+    let mixed = fetch_tconsts(&app, "/titles/search?query=tt10838180%20matrix").await?;
+    assert_eq!(mixed[0], "tt10838180");
+    assert_eq!(mixed.len(), CATALOG.len());
+
+    // The operator joining an id to the words goes with it instead of dangling.
+    for query in ["tt0234215%20AND%20matrix", "matrix%20AND%20tt0234215"] {
+        let joined = fetch_tconsts(&app, &format!("/titles/search?query={query}")).await?;
+        assert_eq!(joined[0], "tt0234215", "{query}");
+        assert_eq!(joined.len(), CATALOG.len(), "{query}");
+    }
+
+    // An id inside a quoted phrase is part of the phrase, which no title contains.
+    let phrase = fetch_tconsts(&app, "/titles/search?query=%22the%20tt0234215%20matrix%22").await?;
+    assert!(phrase.is_empty(), "{phrase:?}");
+    let beside_phrase =
+        fetch_tconsts(&app, "/titles/search?query=%22the%20matrix%22%20tt0234215").await?;
+    assert_eq!(beside_phrase[0], "tt0234215");
+    assert!(beside_phrase.contains(&"tt0133093".to_string()));
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/names/search?query=NM0000206%20unrelated")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let parsed: imdb_rs::api::types::NameSearchResponse = from_slice(&bytes)?;
+    assert_eq!(parsed.results[0].nconst, "nm0000206");
+    Ok(())
+}
+
+#[tokio::test]
+async fn diagnostics_identify_the_filter_that_emptied_results() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let narrow = "/titles/search?query=matrix&start_year_min=2010&start_year_max=2012";
+    let plain = fetch_titles(&app, narrow).await?;
+    assert_eq!(plain.total, 0);
+    assert!(plain.diagnostics.is_none());
+
+    let diagnosed = fetch_titles(&app, &format!("{narrow}&diagnose=true")).await?;
+    let diagnostics = diagnosed.diagnostics.expect("diagnostics present");
+    let culprits: Vec<(&str, usize)> = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.results_without > 0)
+        .map(|diagnostic| (diagnostic.filter.as_str(), diagnostic.results_without))
+        .collect();
+    assert_eq!(culprits, [("start_year", CATALOG.len())]);
+    assert!(
+        diagnostics
+            .iter()
+            .any(|diagnostic| diagnostic.filter == "title_type")
+    );
+
+    let found = fetch_titles(&app, "/titles/search?query=matrix&diagnose=true").await?;
+    assert!(found.diagnostics.is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn configured_tiebreak_orders_equal_sort_values() -> TestResult<()> {
+    let title = |tconst, title, votes| TestTitle {
+        votes,
+        ..TestTitle::new(tconst, title)
+    };
+    let titles = [
+        title("tt0000001", "Alpha Harbor", 1_000),
+        title("tt0000002", "Zeta Harbor", 5_000),
+        title("tt0000003", "Mid Harbor", 3_000),
+    ];
+    let app_with = |tiebreak| {
+        app_with_titles(
+            &titles,
+            imdb_rs::config::AppConfig {
+                tiebreak,
+                ..imdb_rs::config::AppConfig::default()
+            },
+        )
+    };
+
+    let uri = "/titles/search?query=harbor&sort=rating_desc";
+    let by_votes = app_with(TieBreak::Votes);
+    assert_eq!(
+        fetch_tconsts(&by_votes, uri).await?,
+        ["tt0000002", "tt0000003", "tt0000001"]
+    );
+    assert_eq!(
+        fetch_tconsts(&by_votes, &format!("{uri}&limit=1&offset=1")).await?,
+        ["tt0000003"]
+    );
+
+    let by_title = app_with(TieBreak::Title);
+    assert_eq!(
+        fetch_tconsts(&by_title, uri).await?,
+        ["tt0000001", "tt0000003", "tt0000002"]
+    );
+    assert_eq!(
+        fetch_tconsts(&by_title, &format!("{uri}&limit=1&offset=1")).await?,
+        ["tt0000003"]
+    );
+
+    let by_tconst = app_with(TieBreak::Tconst);
+    assert_eq!(
+        fetch_tconsts(
+            &by_tconst,
+            "/titles/search?query=harbor&sort=votes_asc&limit=2"
+        )
+        .await?,
+        ["tt0000001", "tt0000003"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn equal_sort_values_page_in_tconst_order_by_default() -> TestResult<()> {
+    let title = |tconst| TestTitle::new(tconst, "Harbor");
+    // Indexed against tconst order so document order cannot pass for the tiebreak.
+    let titles = [
+        title("tt0000004"),
+        title("tt0000002"),
+        title("tt0000003"),
+        title("tt0000001"),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    for sort in ["relevance", "votes_desc", "rating_desc"] {
+        let uri = format!("/titles/search?query=harbor&sort={sort}");
+        assert_eq!(
+            fetch_tconsts(&app, &uri).await?,
+            ["tt0000001", "tt0000002", "tt0000003", "tt0000004"],
+            "{sort}"
+        );
+        let mut paged = Vec::new();
+        for offset in 0..4 {
+            paged.extend(fetch_tconsts(&app, &format!("{uri}&limit=1&offset={offset}")).await?);
+        }
+        assert_eq!(
+            paged,
+            ["tt0000001", "tt0000002", "tt0000003", "tt0000004"],
+            "{sort}"
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn person_filter_matches_credited_titles() -> TestResult<()> {
+    let app = imdb_rs::api::router(imdb_rs::api::AppState::new(
+        build_test_indexes(),
+        imdb_rs::config::AppConfig::default(),
+    ));
+
+    let keanu = fetch_tconsts(&app, "/titles/search?person=nm0000206").await?;
+    assert_eq!(keanu, ["tt0133093"]);
+    let with_query = fetch_tconsts(&app, "/titles/search?query=matrix&person=nm0905154").await?;
+    assert_eq!(with_query, ["tt0133093"]);
+    let stranger = fetch_tconsts(&app, "/titles/search?query=matrix&person=nm9999999").await?;
+    assert!(stranger.is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn runtime_sort_puts_titles_without_runtime_last() -> TestResult<()> {
+    let title = |tconst, runtime| TestTitle {
+        runtime,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", None),
+        title("tt0000002", Some(95)),
+        title("tt0000003", Some(210)),
+        title("tt0000004", Some(120)),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=harbor&sort=runtime_desc").await?,
+        ["tt0000003", "tt0000004", "tt0000002", "tt0000001"]
+    );
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=harbor&sort=runtime_asc").await?,
+        ["tt0000002", "tt0000004", "tt0000003", "tt0000001"]
+    );
+    let page = fetch_titles(
+        &app,
+        "/titles/search?query=harbor&sort=runtime_desc&limit=2&offset=1",
+    )
+    .await?;
+    assert_eq!(page.total, 4);
+    let runtimes: Vec<_> = page.results.iter().map(|r| r.runtime_minutes).collect();
+    assert_eq!(runtimes, [Some(120), Some(95)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn year_sort_orders_chronologically_with_undated_titles_last() -> TestResult<()> {
+    let title = |tconst, year| TestTitle {
+        year,
+        ..TestTitle::new(tconst, "Harbor Lights")
+    };
+    let titles = [
+        title("tt0000001", 0),
+        title("tt0000002", 1995),
+        title("tt0000003", 2010),
+    ];
+    let app = app_with_titles(
+        &titles, // The implicit 1980 floor would hide the undated title.
+        imdb_rs::config::AppConfig {
+            disable_implicit_defaults: true,
+            ..imdb_rs::config::AppConfig::default()
+        },
+    );
+
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=harbor&sort=year_desc").await?,
+        ["tt0000003", "tt0000002", "tt0000001"]
+    );
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=harbor&sort=year_asc").await?,
+        ["tt0000002", "tt0000003", "tt0000001"]
+    );
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?query=harbor&sort=year_asc&limit=1&offset=2"
+        )
+        .await?,
+        ["tt0000001"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn title_sort_orders_alphabetically_and_can_ignore_articles() -> TestResult<()> {
+    let title = |tconst, title| TestTitle::new(tconst, title);
+    let titles = [
+        title("tt0000001", "The Harbor"),
+        title("tt0000002", "Zebra Harbor"),
+        title("tt0000003", "A Quiet Harbor"),
+        title("tt0000004", "Harbor Lights"),
+    ];
+    let app = app_with_titles(&titles, imdb_rs::config::AppConfig::default());
+
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=harbor&sort=title_asc").await?,
+        ["tt0000003", "tt0000004", "tt0000001", "tt0000002"]
+    );
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?query=harbor&sort=title_asc&strip_articles=true"
+        )
+        .await?,
+        ["tt0000001", "tt0000004", "tt0000003", "tt0000002"]
+    );
+    assert_eq!(
+        fetch_tconsts(
+            &app,
+            "/titles/search?query=harbor&sort=title_desc&strip_articles=true&limit=2"
+        )
+        .await?,
+        ["tt0000002", "tt0000003"]
+    );
+    Ok(())
+}
+
+/// Ranks by fewest votes, ignoring the text score entirely.
+struct FewestVotesScorer;
+
+impl imdb_rs::api::TitleScorer for FewestVotesScorer {
+    fn score(
+        &self,
+        _base: tantivy::Score,
+        result: &imdb_rs::api::types::TitleSearchResult,
+        _query: Option<&str>,
+    ) -> f32 {
+        1.0 / (1.0 + result.num_votes.unwrap_or(0) as f32)
+    }
+}
+
+#[tokio::test]
+async fn custom_scorer_replaces_default_relevance() -> TestResult<()> {
+    let app = imdb_rs::api::router(
+        imdb_rs::api::AppState::new(
+            catalog_indexes(CATALOG),
+            imdb_rs::config::AppConfig::default(),
+        )
+        .with_scorer(FewestVotesScorer),
+    );
+
+    let mut expected: Vec<&TestTitle> = CATALOG.iter().collect();
+    expected.sort_by_key(|title| title.votes);
+    let expected: Vec<&str> = expected.iter().map(|title| title.tconst).collect();
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=matrix").await?,
+        expected
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn debug_score_explains_relevance_scores() -> TestResult<()> {
+    let app = app_with_titles(CATALOG, imdb_rs::config::AppConfig::default());
+
+    let plain = fetch_titles(&app, "/titles/search?query=matrix").await?;
+    assert!(
+        plain
+            .results
+            .iter()
+            .all(|result| result.score_breakdown.is_none())
+    );
+
+    let debugged = fetch_titles(
+        &app,
+        "/titles/search?query=matrix&debug_score=true&genres=Action",
+    )
+    .await?
+    .results;
+    assert!(!debugged.is_empty());
+    for result in &debugged {
+        let breakdown = result.score_breakdown.expect("breakdown requested");
+        let score = result.score.expect("relevance score");
+        assert!(
+            (breakdown.total() - score).abs() <= score.abs() * 1e-4,
+            "{breakdown:?} vs {score}"
+        );
+        assert!(breakdown.genre_multiplier > 1.0);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn admin_reindex_rebuilds_and_swaps_indexes_in_the_background() -> TestResult<()> {
+    let dir = std::env::temp_dir().join(format!("imdb-rs-reindex-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir)?;
+    // Already on disk, so the job only rebuilds.
+    for (name, contents) in [
+        (
+            "title.basics.tsv",
+            "tconst\ttitleType\tprimaryTitle\toriginalTitle\tisAdult\tstartYear\tendYear\truntimeMinutes\tgenres\n\
+             tt2543164\tmovie\tArrival\tArrival\t0\t2016\t\\N\t116\tDrama,Sci-Fi\n",
+        ),
+        (
+            "title.ratings.tsv",
+            "tconst\taverageRating\tnumVotes\ntt2543164\t7.9\t800000\n",
+        ),
+        (
+            "title.akas.tsv",
+            "titleId\tordering\ttitle\tregion\tlanguage\ttypes\tattributes\tisOriginalTitle\n",
+        ),
+        (
+            "name.basics.tsv",
+            "nconst\tprimaryName\tbirthYear\tdeathYear\tprimaryProfession\tknownForTitles\n\
+             nm0000206\tKeanu Reeves\t1964\t\\N\tactor\ttt0133093\n",
+        ),
+    ] {
+        std::fs::write(dir.join(name), contents)?;
+    }
+    let config = imdb_rs::config::AppConfig {
+        data_dir: dir.clone(),
+        index_dir: dir.join("index"),
+        admin_token: Some("secret".into()),
+        index_principals: false,
+        index_episodes: false,
+        ..imdb_rs::config::AppConfig::default()
+    };
+    let app = app_with_titles(CATALOG, config);
+    let admin = |method: &str, uri: &str| {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("authorization", "Bearer secret")
+            .body(Body::empty())
+    };
+
+    let response = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/admin/reindex")
+                .body(Body::empty())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app
+        .clone()
+        .oneshot(admin("POST", "/admin/reindex")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let job: imdb_rs::api::types::ReindexJob = from_slice(&bytes)?;
+    assert_eq!(job.status, imdb_rs::api::types::JobStatus::Running);
+
+    let response = app
+        .clone()
+        .oneshot(admin("POST", "/admin/reindex")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let job = wait_for_reindex(&app, job.job_id).await?;
+    assert_eq!(
+        job.status,
+        imdb_rs::api::types::JobStatus::Succeeded,
+        "{:?}",
+        job.error
+    );
+    assert!(job.finished_at.is_some());
+
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=arrival").await?,
+        ["tt2543164"]
+    );
+    assert!(
+        fetch_tconsts(&app, "/titles/search?query=matrix")
+            .await?
+            .is_empty()
+    );
+    let response = app
+        .clone()
+        .oneshot(Request::builder().uri("/meta").body(Body::empty())?)
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let meta: imdb_rs::api::types::MetaResponse = from_slice(&bytes)?;
+    let titles = meta.titles.expect("title build metadata");
+    assert_eq!(titles.documents, 1);
+    assert_eq!(
+        titles.schema_version,
+        imdb_rs::indexer::TITLE_SCHEMA_VERSION
+    );
+    assert!(titles.sources.contains_key("title.basics.tsv"));
+    assert_eq!(meta.names.expect("name build metadata").documents, 1);
+    // The same record is written next to the live index for operators.
+    let live_dir = dir
+        .join("index")
+        .join(std::fs::read_to_string(dir.join("index/titles.current"))?);
+    let written: imdb_rs::indexer::BuildMeta =
+        from_slice(&std::fs::read(live_dir.join("build_meta.json"))?)?;
+    assert_eq!(written, titles);
+
+    // Each rebuild lands in a new directory; the one it replaced is removed after the swap.
+    let live_titles = || std::fs::read_to_string(dir.join("index/titles.current"));
+    let first = live_titles()?;
+    assert!(dir.join("index").join(&first).is_dir());
+    let response = app
+        .clone()
+        .oneshot(admin("POST", "/admin/reindex")?)
+        .await?;
+    let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+    let job: imdb_rs::api::types::ReindexJob = from_slice(&bytes)?;
+    let job = wait_for_reindex(&app, job.job_id).await?;
+    assert_eq!(job.status, imdb_rs::api::types::JobStatus::Succeeded);
+    let second = live_titles()?;
+    assert_ne!(first, second);
+    assert!(dir.join("index").join(&second).is_dir());
+    assert!(!dir.join("index").join(&first).exists());
+    assert_eq!(
+        fetch_tconsts(&app, "/titles/search?query=arrival").await?,
+        ["tt2543164"]
+    );
+
+    let response = app
+        .clone()
+        .oneshot(admin("GET", "/admin/reindex/99")?)
+        .await?;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    std::fs::remove_dir_all(&dir)?;
+    Ok(())
+}
+
+/// Polls `/admin/reindex/{job_id}` until the job stops running.
+async fn wait_for_reindex(
+    app: &axum::Router,
+    job_id: u64,
+) -> TestResult<imdb_rs::api::types::ReindexJob> {
+    loop {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri(format!("/admin/reindex/{job_id}"))
+                    .header("authorization", "Bearer secret")
+                    .body(Body::empty())?,
+            )
+            .await?;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = body::to_bytes(response.into_body(), usize::MAX).await?;
+        let job: imdb_rs::api::types::ReindexJob = from_slice(&bytes)?;
+        if job.status != imdb_rs::api::types::JobStatus::Running {
+            return Ok(job);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    }
+}
+
+/// Every `$ref` in an OpenAPI document.
+fn collect_refs(value: &serde_json::Value, refs: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                match value.as_str() {
+                    Some(target) if key == "$ref" => refs.push(target.to_string()),
+                    _ => collect_refs(value, refs),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter().for_each(|item| collect_refs(item, refs)),
+        _ => {}
+    }
+}
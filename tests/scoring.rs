@@ -1,38 +1,56 @@
-use imdb_rs::api::compute_title_relevance_score;
+use chrono::{Datelike, Utc};
 use imdb_rs::api::types::TitleSearchResult;
+use imdb_rs::api::{
+    ColdStartTier, RECENT_WINDOW_BOOST, ScoringConfig, compute_title_relevance_score,
+    genre_match_multiplier, normalize_scores, score_breakdown, score_title, title_popularity,
+};
+
+/// A movie result carrying only its id and title; each test overrides the fields it scores.
+fn fixture(tconst: &str, primary_title: &str) -> TitleSearchResult {
+    TitleSearchResult {
+        tconst: tconst.into(),
+        imdb_url: String::new(),
+        primary_title: primary_title.into(),
+        original_title: None,
+        title_type: Some("movie".into()),
+        is_series: false,
+        start_year: None,
+        end_year: None,
+        genres: None,
+        average_rating: None,
+        num_votes: None,
+        popularity: None,
+        popularity_tier: None,
+        runtime_minutes: None,
+        overview: None,
+        localized_title: None,
+        highlighted_title: None,
+        score_breakdown: None,
+        score: None,
+        sort_value: None,
+    }
+}
 
 #[test]
 fn relevance_score_rewards_rating_votes_and_recency() {
     let base = 1.0;
     let high = TitleSearchResult {
-        tconst: "tt1".into(),
-        primary_title: "High".into(),
-        original_title: None,
-        title_type: Some("movie".into()),
         start_year: Some(2020),
         end_year: Some(2020),
-        genres: None,
         average_rating: Some(8.5),
         num_votes: Some(50_000),
-        score: None,
-        sort_value: None,
+        ..fixture("tt1", "High")
     };
     let low = TitleSearchResult {
-        tconst: "tt2".into(),
-        primary_title: "Low".into(),
-        original_title: None,
-        title_type: Some("movie".into()),
         start_year: Some(1990),
         end_year: Some(1990),
-        genres: None,
         average_rating: Some(6.0),
         num_votes: Some(10),
-        score: None,
-        sort_value: None,
+        ..fixture("tt2", "Low")
     };
 
-    let high_score = compute_title_relevance_score(base, &high, Some("high"));
-    let low_score = compute_title_relevance_score(base, &low, Some("low"));
+    let high_score = compute_title_relevance_score(base, &high, Some("high"), None);
+    let low_score = compute_title_relevance_score(base, &low, Some("low"), None);
 
     assert!(high_score > low_score);
 }
@@ -41,34 +59,25 @@ fn relevance_score_rewards_rating_votes_and_recency() {
 fn higher_rating_and_votes_outweigh_recency() {
     let base = 2.0;
     let recent = TitleSearchResult {
-        tconst: "tt_new".into(),
-        primary_title: "One Piece".into(),
-        original_title: None,
         title_type: Some("tvSeries".into()),
+        is_series: true,
         start_year: Some(2023),
-        end_year: None,
-        genres: None,
         average_rating: Some(8.3),
         num_votes: Some(179_650),
-        score: None,
-        sort_value: None,
+        ..fixture("tt_new", "One Piece")
     };
     let classic = TitleSearchResult {
-        tconst: "tt_classic".into(),
-        primary_title: "One Piece".into(),
-        original_title: None,
         title_type: Some("tvSeries".into()),
+        is_series: true,
         start_year: Some(1999),
         end_year: Some(1999),
-        genres: None,
         average_rating: Some(9.0),
         num_votes: Some(321_631),
-        score: None,
-        sort_value: None,
+        ..fixture("tt_classic", "One Piece")
     };
 
-    let recent_score = compute_title_relevance_score(base, &recent, Some("one piece"));
-    let classic_score = compute_title_relevance_score(base, &classic, Some("one piece"));
+    let recent_score = compute_title_relevance_score(base, &recent, Some("one piece"), None);
+    let classic_score = compute_title_relevance_score(base, &classic, Some("one piece"), None);
 
     assert!(
         classic_score > recent_score,
@@ -79,38 +88,158 @@ fn higher_rating_and_votes_outweigh_recency() {
 #[test]
 fn exact_title_match_outranks_partial_even_with_lower_base() {
     let exact = TitleSearchResult {
-        tconst: "tt_exact".into(),
-        primary_title: "Up".into(),
-        original_title: None,
-        title_type: Some("movie".into()),
         start_year: Some(2009),
         end_year: Some(2009),
-        genres: None,
         average_rating: Some(8.3),
         num_votes: Some(1_201_529),
-        score: None,
-        sort_value: None,
+        ..fixture("tt_exact", "Up")
     };
 
     let partial = TitleSearchResult {
-        tconst: "tt_partial".into(),
-        primary_title: "No Way Up".into(),
-        original_title: None,
-        title_type: Some("movie".into()),
         start_year: Some(2024),
         end_year: Some(2024),
-        genres: None,
         average_rating: Some(4.6),
         num_votes: Some(11_321),
-        score: None,
-        sort_value: None,
+        ..fixture("tt_partial", "No Way Up")
     };
 
-    let exact_score = compute_title_relevance_score(0.75, &exact, Some("up"));
-    let partial_score = compute_title_relevance_score(5.0, &partial, Some("up"));
+    let exact_score = compute_title_relevance_score(0.75, &exact, Some("up"), None);
+    let partial_score = compute_title_relevance_score(5.0, &partial, Some("up"), None);
 
     assert!(
         exact_score > partial_score,
         "exact title match with better rating should outrank partial match"
     );
 }
+
+#[test]
+fn titles_matching_more_requested_genres_rank_higher() {
+    let requested = vec!["Action".to_string(), "Sci-Fi".to_string()];
+    let both = TitleSearchResult {
+        start_year: Some(2010),
+        end_year: Some(2010),
+        genres: Some(vec!["Action".into(), "Sci-Fi".into()]),
+        average_rating: Some(7.0),
+        num_votes: Some(50_000),
+        ..fixture("tt_both", "Both")
+    };
+    let one = TitleSearchResult {
+        tconst: "tt_one".into(),
+        primary_title: "One".into(),
+        genres: Some(vec!["Action".into(), "Drama".into()]),
+        ..both.clone()
+    };
+
+    let both_score = compute_title_relevance_score(1.0, &both, None, None)
+        * genre_match_multiplier(&both, &requested, 0.5);
+    let one_score = compute_title_relevance_score(1.0, &one, None, None)
+        * genre_match_multiplier(&one, &requested, 0.5);
+
+    assert!(both_score > one_score);
+    assert_eq!(genre_match_multiplier(&one, &requested, 0.0), 1.0);
+}
+
+#[test]
+fn normalized_scores_are_relative_to_the_best_hit() {
+    let mut scores = [Some(8.0), Some(2.0), None, Some(4.0)];
+    normalize_scores(scores.iter_mut());
+    assert_eq!(scores, [Some(1.0), Some(0.25), None, Some(0.5)]);
+
+    let mut untouched = [Some(0.0), None];
+    normalize_scores(untouched.iter_mut());
+    assert_eq!(untouched, [Some(0.0), None]);
+}
+
+#[test]
+fn freshness_window_boosts_only_recent_titles() {
+    let current_year = i64::from(Utc::now().year());
+    let title = |tconst: &str, year: i64| TitleSearchResult {
+        start_year: Some(year),
+        average_rating: Some(7.0),
+        num_votes: Some(50_000),
+        ..fixture(tconst, "Signal")
+    };
+    let recent = title("tt2", current_year - 1);
+    let older = title("tt1", current_year - 20);
+
+    assert_eq!(
+        compute_title_relevance_score(1.0, &older, None, Some(5)),
+        compute_title_relevance_score(1.0, &older, None, None)
+    );
+    let unboosted = compute_title_relevance_score(1.0, &recent, None, None);
+    let boosted = compute_title_relevance_score(1.0, &recent, None, Some(5));
+    assert!((boosted / unboosted - RECENT_WINDOW_BOOST as f32).abs() < 1e-4);
+}
+
+#[test]
+fn smaller_vote_prior_lets_low_vote_ratings_count() {
+    let title = TitleSearchResult {
+        start_year: Some(2015),
+        end_year: Some(2015),
+        average_rating: Some(9.0),
+        num_votes: Some(800),
+        ..fixture("tt1", "Niche")
+    };
+    let full_corpus = ScoringConfig::default();
+    let subset = ScoringConfig {
+        m_prior: 200.0,
+        ..ScoringConfig::default()
+    };
+
+    let full_wr = full_corpus.weighted_rating(9.0, 800.0);
+    let subset_wr = subset.weighted_rating(9.0, 800.0);
+    assert!(full_wr < 7.0, "{full_wr}");
+    assert!(subset_wr > 8.5, "{subset_wr}");
+    assert!(
+        score_title(&subset, 1.0, &title, None, None)
+            > score_title(&full_corpus, 1.0, &title, None, None)
+    );
+    assert_eq!(
+        score_title(&full_corpus, 1.0, &title, None, None),
+        compute_title_relevance_score(1.0, &title, None, None)
+    );
+}
+
+#[test]
+fn cold_start_tiers_parse_and_apply_in_order() {
+    let tier: ColdStartTier = "50:0.2".parse().unwrap();
+    assert_eq!(tier, ColdStartTier::new(50.0, 0.2));
+    assert!("50".parse::<ColdStartTier>().is_err());
+
+    let title = TitleSearchResult {
+        start_year: Some(2015),
+        end_year: Some(2015),
+        average_rating: Some(7.0),
+        num_votes: Some(100),
+        ..fixture("tt1", "Obscure")
+    };
+    let undamped = ScoringConfig {
+        cold_start: Vec::new(),
+        ..ScoringConfig::default()
+    };
+    let damped = score_title(&ScoringConfig::default(), 1.0, &title, None, None);
+    let plain = score_title(&undamped, 1.0, &title, None, None);
+    assert!((damped / plain - 0.5).abs() < 1e-4, "{damped} / {plain}");
+}
+
+#[test]
+fn popularity_favours_well_known_titles_regardless_of_query() {
+    let title = |tconst: &str, rating: f64, votes: Option<i64>| TitleSearchResult {
+        start_year: Some(2010),
+        average_rating: Some(rating),
+        num_votes: votes,
+        ..fixture(tconst, "Harbor")
+    };
+    let scoring = ScoringConfig::default();
+    let famous = title_popularity(&scoring, &title("tt1", 8.0, Some(1_500_000))).unwrap();
+    // A perfect rating from a handful of voters is shrunk towards the global mean.
+    let obscure = title_popularity(&scoring, &title("tt2", 9.8, Some(40))).unwrap();
+    assert!(famous > obscure);
+
+    let breakdown = score_breakdown(&scoring, 1.0, &title("tt1", 8.0, Some(1_500_000)), None);
+    assert_eq!(
+        famous,
+        breakdown.rating_component + breakdown.popularity_component
+    );
+    assert_eq!(title_popularity(&scoring, &title("tt3", 8.0, None)), None);
+}